@@ -0,0 +1,280 @@
+//! Named benchmark scenarios behind `axm bench`.
+//!
+//! Each scenario runs a fixed workload a few times after warming up and
+//! reports the median and p95 wall time. The report can be saved as JSON
+//! and later passed back as a baseline to flag regressions.
+
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use axm_engine::agent::{Agent, AlwaysCall};
+use axm_engine::cards::{full_deck, Card};
+use axm_engine::engine::Engine;
+use axm_engine::hand_rank;
+use axm_engine::logger::{format_hand_id, HandLogger, HandRecord};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::play_agent_hand;
+
+const RANDOM_HANDS: usize = 50_000;
+const SIM_HANDS: usize = 200;
+const RECORDS: usize = 2_000;
+
+pub struct Scenario {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// Left out unless named explicitly.
+    pub slow: bool,
+    run: fn(&Fixture) -> u64,
+}
+
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "eval-random7",
+        about: "table evaluator on random 7-card hands",
+        slow: false,
+        run: eval_random7,
+    },
+    Scenario {
+        name: "eval-random7-baseline",
+        about: "evaluate_hand on the same hands",
+        slow: false,
+        run: eval_random7_baseline,
+    },
+    Scenario {
+        name: "eval-all7",
+        about: "table evaluator on all 133,784,560 7-card hands",
+        slow: true,
+        run: eval_all7,
+    },
+    Scenario {
+        name: "sim-hands",
+        about: "full heads-up hands between always-call agents",
+        slow: false,
+        run: sim_hands,
+    },
+    Scenario {
+        name: "jsonl-write",
+        about: "HandLogger writing hand records",
+        slow: false,
+        run: jsonl_write,
+    },
+    Scenario {
+        name: "jsonl-read",
+        about: "parsing a JSONL hand history",
+        slow: false,
+        run: jsonl_read,
+    },
+    Scenario {
+        name: "sqlite-export",
+        about: "axm export --format sqlite",
+        slow: false,
+        run: sqlite_export,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Scenario> {
+    SCENARIOS.iter().find(|s| s.name == name)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub iterations: u32,
+    /// Work items per iteration (hands evaluated, records written, ...).
+    pub ops: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub ops_per_sec: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub version: String,
+    pub scenarios: Vec<ScenarioResult>,
+}
+
+/// Inputs shared by all scenarios, built once and untimed.
+pub struct Fixture {
+    hands: Vec<[Card; 7]>,
+    records: Vec<HandRecord>,
+    dir: PathBuf,
+}
+
+impl Fixture {
+    pub fn new() -> std::io::Result<Self> {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let mut deck = full_deck();
+        let hands = (0..RANDOM_HANDS)
+            .map(|_| {
+                deck.shuffle(&mut rng);
+                deck[..7].try_into().expect("7 cards")
+            })
+            .collect();
+        let mut agents: [Box<dyn Agent>; 2] = [Box::new(AlwaysCall), Box::new(AlwaysCall)];
+        let records = (0..RECORDS)
+            .map(|i| {
+                let seed = i as u64;
+                let mut engine = Engine::new(Some(seed), 1);
+                let _ = play_agent_hand(&mut engine, &mut agents);
+                crate::sim_record(&engine, format_hand_id("19700101", i as u32 + 1), seed)
+            })
+            .collect();
+        let dir = std::env::temp_dir().join(format!("axm-bench-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let fixture = Self {
+            hands,
+            records,
+            dir,
+        };
+        jsonl_write(&fixture);
+        Ok(fixture)
+    }
+
+    fn jsonl(&self) -> PathBuf {
+        self.dir.join("hands.jsonl")
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Runs `scenario` `warmup` times untimed, then `iterations` times.
+pub fn measure(
+    scenario: &Scenario,
+    fixture: &Fixture,
+    warmup: u32,
+    iterations: u32,
+) -> ScenarioResult {
+    for _ in 0..warmup {
+        (scenario.run)(fixture);
+    }
+    let mut ops = 0;
+    let mut times: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            ops = (scenario.run)(fixture);
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    let median = times[times.len() / 2];
+    // nearest-rank percentile
+    let p95 = times[(times.len() * 95).div_ceil(100) - 1];
+    ScenarioResult {
+        name: scenario.name.to_string(),
+        iterations: times.len() as u32,
+        ops,
+        median_ns: median.as_nanos() as u64,
+        p95_ns: p95.as_nanos() as u64,
+        ops_per_sec: ops as f64 / median.as_secs_f64().max(1e-9),
+    }
+}
+
+/// Median change against `baseline` in percent, for scenarios in both.
+pub fn compare(report: &BenchReport, baseline: &BenchReport) -> Vec<(String, f64)> {
+    report
+        .scenarios
+        .iter()
+        .filter_map(|r| {
+            let base = baseline.scenarios.iter().find(|b| b.name == r.name)?;
+            let change = (r.median_ns as f64 / base.median_ns.max(1) as f64 - 1.0) * 100.0;
+            Some((r.name.clone(), change))
+        })
+        .collect()
+}
+
+fn eval_random7(f: &Fixture) -> u64 {
+    for hand in &f.hands {
+        std::hint::black_box(hand_rank::evaluate(hand));
+    }
+    f.hands.len() as u64
+}
+
+fn eval_random7_baseline(f: &Fixture) -> u64 {
+    for hand in &f.hands {
+        std::hint::black_box(axm_engine::hand::evaluate_hand(hand));
+    }
+    f.hands.len() as u64
+}
+
+fn eval_all7(_: &Fixture) -> u64 {
+    let deck = full_deck();
+    let mut hand = [deck[0]; 7];
+    let mut idx = [0usize, 1, 2, 3, 4, 5, 6];
+    let mut count = 0u64;
+    loop {
+        for (slot, &i) in hand.iter_mut().zip(&idx) {
+            *slot = deck[i];
+        }
+        std::hint::black_box(hand_rank::evaluate(&hand));
+        count += 1;
+        // next 7-subset of the 52 cards
+        let Some(i) = (0..7).rev().find(|&i| idx[i] < 52 - 7 + i) else {
+            return count;
+        };
+        idx[i] += 1;
+        for j in i + 1..7 {
+            idx[j] = idx[j - 1] + 1;
+        }
+    }
+}
+
+fn sim_hands(_: &Fixture) -> u64 {
+    let mut agents: [Box<dyn Agent>; 2] = [Box::new(AlwaysCall), Box::new(AlwaysCall)];
+    for i in 0..SIM_HANDS {
+        let mut engine = Engine::new(Some(i as u64), 1);
+        let _ = play_agent_hand(&mut engine, &mut agents);
+    }
+    SIM_HANDS as u64
+}
+
+fn jsonl_write(f: &Fixture) -> u64 {
+    let mut logger = HandLogger::create(f.jsonl()).expect("bench output file");
+    for rec in &f.records {
+        logger.write(rec).expect("bench write");
+    }
+    f.records.len() as u64
+}
+
+fn jsonl_read(f: &Fixture) -> u64 {
+    let file = std::fs::File::open(f.jsonl()).expect("bench input file");
+    let mut n = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line.expect("bench read");
+        let rec: HandRecord = serde_json::from_str(&line).expect("bench record");
+        std::hint::black_box(rec);
+        n += 1;
+    }
+    n
+}
+
+fn sqlite_export(f: &Fixture) -> u64 {
+    let db = f.dir.join("hands.sqlite");
+    let (input, output) = (f.jsonl(), db);
+    let mut sink = BufWriter::new(std::io::sink());
+    let code = crate::run(
+        [
+            "axm",
+            "export",
+            "--input",
+            &input.to_string_lossy(),
+            "--format",
+            "sqlite",
+            "--output",
+            &output.to_string_lossy(),
+        ],
+        &mut sink,
+        &mut std::io::sink(),
+    );
+    assert_eq!(code, 0, "sqlite export failed");
+    let _ = sink.flush();
+    f.records.len() as u64
+}
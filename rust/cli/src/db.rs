@@ -0,0 +1,442 @@
+//! `axm db`: the SQLite store of ingested hand histories (ADR-0002).
+//!
+//! Hands are normalized into `hands`, `players`, `actions` and `showdowns`.
+//! A hand is identified by the file it came from and its `hand_id`, since
+//! separate runs can reuse ids; the `player_stats` view aggregates win
+//! rate, net chips, VPIP and PFR per player. `schema_version` records
+//! every migration applied, and opening a store runs the ones it is
+//! missing. A store from a newer build is refused rather than guessed at.
+//!
+//! Ingestion is incremental and idempotent. `ingested_files` remembers how
+//! far into each file (in decompressed bytes) the store has read, so a
+//! later run only reads what was appended since. It also keeps a hash of
+//! the last line read and where that line starts; a file that got smaller
+//! on disk or no longer has that line there was rewritten, so its hands
+//! are dropped and it is read again from the start. Hands are upserted by file and `hand_id`, so reading a line
+//! twice changes nothing. A torn final line is left for the next run; an
+//! invalid record rolls back that file's batch.
+
+use std::path::Path;
+
+use axm_engine::logger::HandRecord;
+use axm_engine::player::PlayerAction;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use sha2::{Digest, Sha256};
+
+use crate::records::{RecordError, RecordReader};
+
+/// Default store location.
+pub const DEFAULT_PATH: &str = "data/db.sqlite";
+
+/// Forward migrations; entry `i` takes the schema to version `i + 1`.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE hands (
+        id INTEGER PRIMARY KEY,
+        file TEXT NOT NULL,
+        hand_id TEXT NOT NULL,
+        schema_version INTEGER NOT NULL,
+        seed TEXT,
+        level INTEGER,
+        sb INTEGER,
+        bb INTEGER,
+        button TEXT,
+        board TEXT NOT NULL,
+        result TEXT,
+        end_reason TEXT,
+        ts TEXT,
+        deck_commitment TEXT,
+        raw_json TEXT NOT NULL,
+        UNIQUE (file, hand_id)
+    );
+    CREATE INDEX hands_by_hand_id ON hands (hand_id);
+    CREATE TABLE players (
+        hand INTEGER NOT NULL REFERENCES hands(id) ON DELETE CASCADE,
+        seat INTEGER NOT NULL,
+        player_id TEXT NOT NULL,
+        stack_start INTEGER,
+        hole_cards TEXT NOT NULL,
+        net INTEGER,
+        PRIMARY KEY (hand, seat)
+    );
+    CREATE TABLE actions (
+        hand INTEGER NOT NULL REFERENCES hands(id) ON DELETE CASCADE,
+        idx INTEGER NOT NULL,
+        seat INTEGER NOT NULL,
+        street TEXT NOT NULL,
+        action TEXT NOT NULL,
+        size INTEGER,
+        amount INTEGER,
+        PRIMARY KEY (hand, idx)
+    );
+    CREATE TABLE showdowns (
+        hand INTEGER NOT NULL REFERENCES hands(id) ON DELETE CASCADE,
+        winner INTEGER NOT NULL,
+        notes TEXT,
+        PRIMARY KEY (hand, winner)
+    );
+    CREATE TABLE ingested_files (
+        path TEXT PRIMARY KEY NOT NULL,
+        size INTEGER NOT NULL,
+        offset INTEGER NOT NULL,
+        lines INTEGER NOT NULL,
+        hands INTEGER NOT NULL,
+        last_start INTEGER NOT NULL,
+        last_hash TEXT,
+        ingested_at TEXT NOT NULL
+    );",
+    "CREATE VIEW player_stats AS
+    SELECT p.player_id AS player_id,
+        COUNT(*) AS hands,
+        SUM(h.result = p.player_id) AS wins,
+        CAST(SUM(h.result = p.player_id) AS REAL) / COUNT(*) AS win_rate,
+        SUM(COALESCE(p.net, 0)) AS net,
+        AVG(p.net) AS ev,
+        CAST(SUM(EXISTS (
+            SELECT 1 FROM actions a
+            WHERE a.hand = p.hand AND a.seat = p.seat AND a.street = 'Preflop'
+                AND a.action IN ('call', 'bet', 'raise', 'all_in')
+        )) AS REAL) / COUNT(*) AS vpip,
+        CAST(SUM(EXISTS (
+            SELECT 1 FROM actions a
+            WHERE a.hand = p.hand AND a.seat = p.seat AND a.street = 'Preflop'
+                AND a.action IN ('bet', 'raise', 'all_in')
+        )) AS REAL) / COUNT(*) AS pfr
+    FROM players p JOIN hands h ON h.id = p.hand
+    GROUP BY p.player_id;",
+];
+
+/// Schema version this build writes.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+pub struct Store {
+    conn: Connection,
+}
+
+/// What `Store::ingest_file` added.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Ingested {
+    /// Hands written, new or replacing a stored one with the same id.
+    pub hands: u64,
+    /// The file was already fully ingested and unchanged on disk.
+    pub unchanged: bool,
+    /// The file was rewritten since last time and was read from the start.
+    pub restarted: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FileState {
+    pub path: String,
+    pub size: u64,
+    pub offset: u64,
+    pub lines: u64,
+    pub hands: u64,
+    /// Where the last line read starts, and its SHA-256 (`None` until a
+    /// line has been read).
+    pub last_start: u64,
+    pub last_hash: Option<String>,
+}
+
+impl Store {
+    /// Opens (creating if needed) the store at `path` and migrates it to
+    /// `SCHEMA_VERSION`.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let mut store = Self::open_unmigrated(path)?;
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// Opens without migrating, for reporting the stored version.
+    pub fn open_unmigrated(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    format!("Failed to create directory {}: {}", parent.display(), e)
+                })?;
+            }
+        }
+        let conn = Connection::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        conn.execute_batch(
+            "PRAGMA foreign_keys = ON;
+            CREATE TABLE IF NOT EXISTS schema_version (
+                version INTEGER PRIMARY KEY NOT NULL,
+                applied_at TEXT NOT NULL
+            );",
+        )
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        Ok(Self { conn })
+    }
+
+    /// Highest migration applied; 0 for a new store.
+    pub fn version(&self) -> Result<u32, String> {
+        self.conn
+            .query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+                [],
+                |r| r.get(0),
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    /// Applies missing migrations, each in its own transaction. Returns
+    /// the version before and after.
+    pub fn migrate(&mut self) -> Result<(u32, u32), String> {
+        let from = self.version()?;
+        if from > SCHEMA_VERSION {
+            return Err(format!(
+                "Database schema version {} is newer than this build ({})",
+                from, SCHEMA_VERSION
+            ));
+        }
+        for (i, sql) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+            let version = i as u32 + 1;
+            let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+            tx.execute_batch(sql)
+                .and_then(|_| {
+                    tx.execute(
+                        "INSERT INTO schema_version (version, applied_at)
+                        VALUES (?1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+                        [version],
+                    )
+                })
+                .and_then(|_| tx.commit())
+                .map_err(|e| format!("Migration {} failed: {}", version, e))?;
+        }
+        Ok((from, SCHEMA_VERSION))
+    }
+
+    /// Reads what `path` gained since the last ingest, in one transaction.
+    pub fn ingest_file(&mut self, path: &Path) -> Result<Ingested, String> {
+        let key = file_key(path);
+        let size = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .len();
+        let mut ingested = Ingested::default();
+        let state = self.file_state(&key)?;
+        let intact = match &state {
+            Some(state) => size >= state.size && prefix_intact(path, state),
+            None => true,
+        };
+        let (mut offset, mut lines, mut last_start, mut last_hash) = match state {
+            Some(_) if !intact => {
+                ingested.restarted = true;
+                (0, 0, 0, None)
+            }
+            Some(state) if size == state.size => {
+                ingested.unchanged = true;
+                return Ok(ingested);
+            }
+            Some(state) => (state.offset, state.lines, state.last_start, state.last_hash),
+            None => (0, 0, 0, None),
+        };
+        let reader =
+            RecordReader::open_at(path, offset, lines as usize).map_err(|e| e.to_string())?;
+
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        if ingested.restarted {
+            tx.execute("DELETE FROM hands WHERE file = ?1", [&key])
+                .map_err(|e| e.to_string())?;
+        }
+        for line in reader {
+            let line = line.map_err(|e| e.to_string())?;
+            if line.torn {
+                // the writer may still be finishing it
+                break;
+            }
+            let rec = line.parse().map_err(|e: RecordError| e.to_string())?;
+            upsert_hand(&tx, &key, &rec, &line.text).map_err(|e| {
+                format!(
+                    "Failed to store hand {} from {}:{}: {}",
+                    rec.hand_id, line.source, line.line, e
+                )
+            })?;
+            ingested.hands += 1;
+            last_start = offset;
+            last_hash = Some(fingerprint(&line.text));
+            offset = line.offset;
+            lines = line.line as u64;
+        }
+        tx.execute(
+            "INSERT INTO ingested_files (path, size, offset, lines, hands, last_start,
+                last_hash, ingested_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?7, ?8, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+            ON CONFLICT(path) DO UPDATE SET
+                size = excluded.size,
+                offset = excluded.offset,
+                lines = excluded.lines,
+                last_start = excluded.last_start,
+                last_hash = excluded.last_hash,
+                hands = CASE WHEN ?6 THEN excluded.hands
+                    ELSE ingested_files.hands + excluded.hands END,
+                ingested_at = excluded.ingested_at",
+            params![
+                key,
+                size as i64,
+                offset as i64,
+                lines as i64,
+                ingested.hands as i64,
+                ingested.restarted,
+                last_start as i64,
+                last_hash
+            ],
+        )
+        .and_then(|_| tx.commit())
+        .map_err(|e| e.to_string())?;
+        Ok(ingested)
+    }
+
+    pub fn file_state(&self, key: &str) -> Result<Option<FileState>, String> {
+        self.conn
+            .query_row(
+                "SELECT path, size, offset, lines, hands, last_start, last_hash
+                FROM ingested_files WHERE path = ?1",
+                [key],
+                |r| {
+                    Ok(FileState {
+                        path: r.get(0)?,
+                        size: r.get::<_, i64>(1)? as u64,
+                        offset: r.get::<_, i64>(2)? as u64,
+                        lines: r.get::<_, i64>(3)? as u64,
+                        hands: r.get::<_, i64>(4)? as u64,
+                        last_start: r.get::<_, i64>(5)? as u64,
+                        last_hash: r.get(6)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn files(&self) -> Result<Vec<FileState>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path FROM ingested_files ORDER BY path")
+            .map_err(|e| e.to_string())?;
+        let keys = stmt
+            .query_map([], |r| r.get::<_, String>(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| e.to_string())?;
+        keys.iter()
+            .filter_map(|k| self.file_state(k).transpose())
+            .collect()
+    }
+
+    pub fn hand_count(&self) -> Result<u64, String> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM hands", [], |r| r.get::<_, i64>(0))
+            .map(|n| n as u64)
+            .map_err(|e| e.to_string())
+    }
+}
+
+// Replaces everything stored for `rec.hand_id` from `file`.
+fn upsert_hand(tx: &Transaction, file: &str, rec: &HandRecord, raw: &str) -> rusqlite::Result<()> {
+    // players, actions and showdowns go with it
+    tx.execute(
+        "DELETE FROM hands WHERE file = ?1 AND hand_id = ?2",
+        params![file, rec.hand_id],
+    )?;
+    tx.execute(
+        "INSERT INTO hands (file, hand_id, schema_version, seed, level, sb, bb, button, board,
+            result, end_reason, ts, deck_commitment, raw_json)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+        params![
+            file,
+            rec.hand_id,
+            rec.schema_version,
+            // u64 seeds do not fit SQLite's signed integers
+            rec.seed.map(|s| s.to_string()),
+            rec.level,
+            rec.sb,
+            rec.bb,
+            rec.button,
+            axm_engine::cards::format_cards(&rec.board),
+            rec.result,
+            rec.end_reason,
+            rec.ts,
+            rec.deck_commitment,
+            raw,
+        ],
+    )?;
+    let hand = tx.last_insert_rowid();
+    for (seat, p) in rec.players.iter().enumerate() {
+        let net = rec.net_result.as_ref().and_then(|n| n.get(&p.id)).copied();
+        tx.execute(
+            "INSERT INTO players (hand, seat, player_id, stack_start, hole_cards, net)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                hand,
+                seat as i64,
+                p.id,
+                p.stack_start,
+                axm_engine::cards::format_cards(&p.hole_cards),
+                net
+            ],
+        )?;
+    }
+    for (idx, a) in rec.actions.iter().enumerate() {
+        let (action, size) = match a.action {
+            PlayerAction::Fold => ("fold", None),
+            PlayerAction::Check => ("check", None),
+            PlayerAction::Call => ("call", None),
+            PlayerAction::Bet(n) => ("bet", Some(n)),
+            PlayerAction::Raise(n) => ("raise", Some(n)),
+            PlayerAction::AllIn => ("all_in", None),
+        };
+        tx.execute(
+            "INSERT INTO actions (hand, idx, seat, street, action, size, amount)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                hand,
+                idx as i64,
+                a.player_id as i64,
+                format!("{:?}", a.street),
+                action,
+                size,
+                a.amount
+            ],
+        )?;
+    }
+    if let Some(sd) = &rec.showdown {
+        for &winner in &sd.winners {
+            tx.execute(
+                "INSERT OR IGNORE INTO showdowns (hand, winner, notes) VALUES (?1, ?2, ?3)",
+                params![hand, winner as i64, sd.notes],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// Whether the last line ingested from `path` is still where it was, so
+// what follows `state.offset` is new. Anything unreadable counts as changed.
+fn prefix_intact(path: &Path, state: &FileState) -> bool {
+    let Some(hash) = &state.last_hash else {
+        return state.offset == 0;
+    };
+    let Ok(mut reader) = RecordReader::open_at(path, state.last_start, 0) else {
+        return false;
+    };
+    match reader.next() {
+        Some(Ok(line)) => {
+            !line.torn && line.offset == state.offset && fingerprint(&line.text) == *hash
+        }
+        _ => false,
+    }
+}
+
+// Hex SHA-256 of a line.
+fn fingerprint(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// Files are tracked by absolute path so `data/x` and `./data/x` match.
+fn file_key(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
@@ -0,0 +1,150 @@
+//! Duplicate-dealt head-to-head evaluation between two agents.
+//!
+//! Every deal is played twice from the same hand seed with the agents swapping
+//! seats, so both see the same cards from each side, at the same blind
+//! level. Levels follow the schedule deal by deal, as `sim` does hand by
+//! hand. Results are reported for agent A in big blinds, each hand measured
+//! against the big blind it was played at; B's numbers are the mirror
+//! image.
+
+use axm_engine::agent::Agent;
+use axm_engine::blinds::{BlindSchedule, SIMULATED_HAND_TIME};
+use axm_engine::deck::derive_hand_seed;
+use axm_engine::engine::{EndReason, Engine};
+use axm_engine::errors::GameError;
+
+use crate::play_agent_hand;
+
+/// z for a two-sided 95% interval.
+const Z_95: f64 = 1.96;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvalReport {
+    pub hands: u32,
+    pub deals: u32,
+    /// Big blind of the last hand played.
+    pub big_blind: u32,
+    /// Hands with a positive / negative result for A.
+    pub a_wins: u32,
+    pub b_wins: u32,
+    /// A's mean result per hand, in big blinds per 100 hands.
+    pub bb_per_100: f64,
+    /// Standard error of `bb_per_100`, taken over deals.
+    pub std_err: f64,
+    /// A's chips won at showdown and without one, in big blinds.
+    pub showdown_bb: f64,
+    pub non_showdown_bb: f64,
+    /// Actions the engine refused and played as check/fold, for A and B.
+    pub rejected: [usize; 2],
+}
+
+impl EvalReport {
+    pub fn ci95(&self) -> (f64, f64) {
+        (
+            self.bb_per_100 - Z_95 * self.std_err,
+            self.bb_per_100 + Z_95 * self.std_err,
+        )
+    }
+
+    /// Wins, bb/100, interval and showdown split for A, or with `mirror` for B.
+    pub fn summary(&self, mirror: bool) -> String {
+        let sign = if mirror { -1.0 } else { 1.0 };
+        let (lo, hi) = self.ci95();
+        let (lo, hi) = if mirror { (-hi, -lo) } else { (lo, hi) };
+        let wins = if mirror { self.b_wins } else { self.a_wins };
+        format!(
+            "wins={} bb/100={:+.2} se={:.2} ci95=[{:+.2}, {:+.2}] showdown={:+.1}bb non-showdown={:+.1}bb",
+            wins,
+            rounded(sign * self.bb_per_100, 2),
+            self.std_err,
+            rounded(lo, 2),
+            rounded(hi, 2),
+            rounded(sign * self.showdown_bb, 1),
+            rounded(sign * self.non_showdown_bb, 1),
+        )
+    }
+}
+
+// Rounds to `places` decimals and turns a negative zero positive, so a
+// result that rounds to nothing prints as `+0.00` rather than `-0.00`.
+fn rounded(x: f64, places: i32) -> f64 {
+    let scale = 10f64.powi(places);
+    let r = (x * scale).round() / scale;
+    if r == 0.0 {
+        0.0
+    } else {
+        r
+    }
+}
+
+/// Plays `hands` hands with `agents = [A, B]`, starting at `level` of
+/// `schedule`. An odd count plays the last deal only once.
+pub fn run_duplicate(
+    agents: &mut [Box<dyn Agent>; 2],
+    hands: u32,
+    seed: u64,
+    level: u8,
+    schedule: &BlindSchedule,
+) -> Result<EvalReport, GameError> {
+    let mut report = EvalReport {
+        hands,
+        ..EvalReport::default()
+    };
+    // A's bb per hand for each deal
+    let mut samples: Vec<f64> = Vec::new();
+    let mut played = 0u32;
+    let mut deal = 0u64;
+    while played < hands {
+        let deal_seed = derive_hand_seed(seed, deal);
+        let deal_level = schedule.level_at(level, deal as u32, SIMULATED_HAND_TIME * deal as u32);
+        let mut deal_bb = 0.0;
+        let mut deal_hands = 0u32;
+        for a_seat in [0usize, 1] {
+            if played == hands {
+                break;
+            }
+            if a_seat == 1 {
+                agents.swap(0, 1);
+            }
+            let mut engine = Engine::with_schedule(Some(deal_seed), deal_level, schedule.clone());
+            let played_hand = play_agent_hand(&mut engine, agents);
+            if a_seat == 1 {
+                agents.swap(0, 1);
+            }
+            let rejected = played_hand?;
+            report.rejected[0] += rejected[a_seat];
+            report.rejected[1] += rejected[1 - a_seat];
+            let bb = engine.blinds().1;
+            report.big_blind = bb;
+            let net = engine.net_result().ok_or(GameError::HandNotInProgress)?[a_seat];
+            match net.cmp(&0) {
+                std::cmp::Ordering::Greater => report.a_wins += 1,
+                std::cmp::Ordering::Less => report.b_wins += 1,
+                std::cmp::Ordering::Equal => {}
+            }
+            let net_bb = net as f64 / bb as f64;
+            if engine.end_reason() == Some(EndReason::Showdown) {
+                report.showdown_bb += net_bb;
+            } else {
+                report.non_showdown_bb += net_bb;
+            }
+            deal_bb += net_bb;
+            deal_hands += 1;
+            played += 1;
+        }
+        samples.push(deal_bb / deal_hands as f64);
+        deal += 1;
+    }
+    report.deals = samples.len() as u32;
+
+    if played > 0 {
+        report.bb_per_100 = (report.showdown_bb + report.non_showdown_bb) / played as f64 * 100.0;
+    }
+    let n = samples.len() as f64;
+    if n > 1.0 {
+        let mean = samples.iter().sum::<f64>() / n;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        report.std_err = (var / n).sqrt() * 100.0;
+    }
+    Ok(report)
+}
@@ -1,488 +1,39 @@
 use clap::{Parser, Subcommand, ValueEnum};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::io::IsTerminal;
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+mod bench;
 mod config;
+mod db;
+mod eval;
+mod records;
+mod repair;
 pub mod ui;
+use axm_engine::agent::{Agent, AgentRegistry};
+use axm_engine::blinds::{BlindSchedule, ScheduleError, SIMULATED_HAND_TIME};
+use axm_engine::cards::{format_cards, parse_cards, Card, CardParseError};
+use axm_engine::deck::{derive_hand_seed, Deck};
 use axm_engine::engine::Engine;
+use axm_engine::equity::{self, EquityOptions, DEFAULT_ITERATIONS};
+use axm_engine::errors::GameError;
+use axm_engine::logger::{HandLogger, HandRecord, Rotation};
+use axm_engine::player::PlayerAction;
+use axm_engine::process_agent::{ProcessAgent, DEFAULT_TIMEOUT};
+use axm_engine::range::{range_equity, Range};
+use axm_engine::selftest;
+use axm_engine::session::Session;
+use axm_engine::verify;
 use rand::{seq::SliceRandom, RngCore, SeedableRng};
+use records::RecordReader;
 
 use std::collections::HashSet;
 
-fn ensure_no_reopen_after_short_all_in(
-    actions: &[serde_json::Value],
-    big_blind: i64,
-    min_chip_unit: i64,
-    starting_stacks: &HashMap<String, i64>,
-    hand_index: u64,
-) -> Result<(), String> {
-    #[derive(Clone, Copy)]
-    enum ActionKind {
-        Bet(i64),
-        Raise(i64),
-        AllIn(Option<i64>),
-        Call,
-        Check,
-        Fold,
-        Other,
-    }
-
-    let mut remaining = starting_stacks.clone();
-    let mut prev_street: Option<String> = None;
-    let mut street_committed: HashMap<String, i64> = HashMap::new();
-    let mut current_high: i64 = 0;
-    let mut last_full_raise: i64 = big_blind.max(min_chip_unit);
-    let mut reopen_blocked = false;
-
-    let extract_player_id = |act: &serde_json::Value| -> Option<String> {
-        act.get("player_id")
-            .and_then(|v| v.as_str().map(|s| s.to_string()))
-            .or_else(|| {
-                act.get("player_id").and_then(|v| {
-                    v.as_i64().map(|n| {
-                        let candidate = format!("p{}", n);
-                        if starting_stacks.contains_key(&candidate) {
-                            candidate
-                        } else {
-                            n.to_string()
-                        }
-                    })
-                })
-            })
-    };
-
-    for (idx, act) in actions.iter().enumerate() {
-        let Some(player_id) = extract_player_id(act) else {
-            continue;
-        };
-
-        if !starting_stacks.contains_key(&player_id) {
-            return Err(format!(
-                "Unknown player {} at hand {} (action #{})",
-                player_id,
-                hand_index,
-                idx + 1
-            ));
-        }
-
-        if let Some(street) = act.get("street").and_then(|s| s.as_str()) {
-            if prev_street.as_deref() != Some(street) {
-                prev_street = Some(street.to_string());
-                street_committed.clear();
-                current_high = 0;
-                last_full_raise = big_blind.max(min_chip_unit);
-                reopen_blocked = false;
-            }
-        }
-
-        let action_kind: ActionKind = match act.get("action") {
-            Some(serde_json::Value::Object(map)) => {
-                if let Some(amount) = map.get("Bet").and_then(|v| v.as_i64()) {
-                    ActionKind::Bet(amount)
-                } else if let Some(amount) = map.get("Raise").and_then(|v| v.as_i64()) {
-                    ActionKind::Raise(amount)
-                } else if let Some(amount) = map.get("AllIn").and_then(|v| v.as_i64()) {
-                    ActionKind::AllIn(Some(amount))
-                } else if map.get("Call").is_some() {
-                    ActionKind::Call
-                } else if map.get("Check").is_some() {
-                    ActionKind::Check
-                } else if map.get("Fold").is_some() {
-                    ActionKind::Fold
-                } else {
-                    ActionKind::Other
-                }
-            }
-            Some(serde_json::Value::String(name)) => match name.to_ascii_lowercase().as_str() {
-                "bet" => {
-                    return Err(format!(
-                        "Bet action missing amount at hand {} (action #{})",
-                        hand_index,
-                        idx + 1
-                    ))
-                }
-                "raise" => {
-                    return Err(format!(
-                        "Bet action missing amount at hand {} (action #{})",
-                        hand_index,
-                        idx + 1
-                    ))
-                }
-                "call" => ActionKind::Call,
-                "check" => ActionKind::Check,
-                "fold" => ActionKind::Fold,
-                "allin" | "all-in" => ActionKind::AllIn(None),
-                _ => ActionKind::Other,
-            },
-            _ => ActionKind::Other,
-        };
-
-        let commit_before = street_committed.get(&player_id).copied().unwrap_or(0);
-        let mut target_commit = commit_before;
-
-        match action_kind {
-            ActionKind::Bet(amount) => {
-                if amount % min_chip_unit != 0 {
-                    return Err(format!(
-                        "Invalid bet amount {} at hand {} (action #{})",
-                        amount,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                let min_bet = big_blind.max(min_chip_unit);
-                if amount < min_bet {
-                    return Err(format!(
-                        "Bet below minimum {} at hand {} (action #{})",
-                        min_bet,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                target_commit = amount;
-            }
-            ActionKind::Raise(amount) => {
-                if amount % min_chip_unit != 0 {
-                    return Err(format!(
-                        "Invalid raise amount {} at hand {} (action #{})",
-                        amount,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                let min_delta = last_full_raise.max(big_blind).max(min_chip_unit);
-                if amount < min_delta {
-                    return Err(format!(
-                        "Raise delta {} below minimum {} at hand {} (action #{})",
-                        amount,
-                        min_delta,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                target_commit = current_high + amount;
-            }
-            ActionKind::AllIn(Some(amount)) => {
-                if amount % min_chip_unit != 0 {
-                    return Err(format!(
-                        "Invalid all-in amount {} at hand {} (action #{})",
-                        amount,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                target_commit = commit_before + amount;
-            }
-            ActionKind::AllIn(None) => {
-                let remaining_stack = remaining.get(&player_id).copied().unwrap_or(0);
-                if remaining_stack % min_chip_unit != 0 {
-                    return Err(format!(
-                        "Invalid all-in amount {} at hand {} (action #{})",
-                        remaining_stack,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                target_commit = commit_before + remaining_stack;
-            }
-            ActionKind::Call => {
-                target_commit = current_high.max(commit_before);
-            }
-            ActionKind::Check | ActionKind::Fold | ActionKind::Other => {}
-        }
-
-        let mut delta_chips = target_commit.saturating_sub(commit_before);
-        if let Some(rem) = remaining.get(&player_id) {
-            if delta_chips > *rem {
-                delta_chips = *rem;
-            }
-        }
-        let new_commit = commit_before + delta_chips;
-
-        if delta_chips > 0 {
-            if let Some(rem) = remaining.get_mut(&player_id) {
-                if *rem < delta_chips {
-                    return Err(format!(
-                        "Player {} commits more chips than stack at hand {} (action #{})",
-                        player_id,
-                        hand_index,
-                        idx + 1
-                    ));
-                }
-                *rem -= delta_chips;
-            }
-        }
-
-        let extra = new_commit.saturating_sub(current_high);
-        if extra > 0 {
-            if reopen_blocked {
-                return Err(format!(
-                    "Betting illegally reopened after short all-in at hand {} (action #{})",
-                    hand_index,
-                    idx + 1
-                ));
-            }
-            let min_full_raise = last_full_raise.max(big_blind).max(min_chip_unit);
-            if extra < min_full_raise {
-                reopen_blocked = true;
-            } else {
-                last_full_raise = extra;
-                reopen_blocked = false;
-            }
-            current_high = new_commit;
-        } else {
-            current_high = current_high.max(new_commit);
-        }
-
-        street_committed.insert(player_id, new_commit);
-    }
-
-    Ok(())
-}
-
-fn validate_dealing_meta(
-    meta: &serde_json::Map<String, serde_json::Value>,
-    button: Option<&str>,
-    starting_stacks: &HashMap<String, i64>,
-    hand_index: u64,
-) -> Result<(), String> {
-    if starting_stacks.is_empty() {
-        return Ok(());
-    }
-    let player_count = starting_stacks.len();
-    let rounds = 2; // Texas Hold'em: two hole cards per player
-    let sb = meta.get("small_blind").and_then(|v| v.as_str());
-    let bb = meta.get("big_blind").and_then(|v| v.as_str());
-    if let Some(sb_id) = sb {
-        if !starting_stacks.contains_key(sb_id) {
-            return Err(format!(
-                "Invalid dealing order at hand {}: unknown small blind {}",
-                hand_index, sb_id
-            ));
-        }
-    }
-    if let Some(bb_id) = bb {
-        if !starting_stacks.contains_key(bb_id) {
-            return Err(format!(
-                "Invalid dealing order at hand {}: unknown big blind {}",
-                hand_index, bb_id
-            ));
-        }
-    }
-    if let (Some(btn), Some(sb_id)) = (button, sb) {
-        if sb_id != btn {
-            return Err(format!(
-                "Invalid dealing order at hand {}: button {} must match small blind {}",
-                hand_index, btn, sb_id
-            ));
-        }
-    }
-    if let (Some(sb_id), Some(bb_id)) = (sb, bb) {
-        if sb_id == bb_id {
-            return Err(format!(
-                "Invalid dealing order at hand {}: small blind and big blind must differ",
-                hand_index
-            ));
-        }
-        if player_count == 2 {
-            if let Some(expected_bb) = starting_stacks
-                .keys()
-                .find(|id| id.as_str() != sb_id)
-                .map(|s| s.as_str())
-            {
-                if bb_id != expected_bb {
-                    return Err(format!(
-                        "Invalid dealing order at hand {}: expected big blind {} but found {}",
-                        hand_index, expected_bb, bb_id
-                    ));
-                }
-            }
-        }
-    }
-    if let Some(seq_val) = meta.get("deal_sequence") {
-        let seq = seq_val.as_array().ok_or_else(|| {
-            format!(
-                "Invalid dealing order at hand {}: deal_sequence must be an array",
-                hand_index
-            )
-        })?;
-        let seq_ids: Option<Vec<&str>> = seq.iter().map(|v| v.as_str()).collect();
-        let seq_ids = seq_ids.ok_or_else(|| {
-            format!(
-                "Invalid dealing order at hand {}: deal_sequence must contain player identifiers",
-                hand_index
-            )
-        })?;
-        let expected_len = player_count * rounds;
-        if seq_ids.len() != expected_len {
-            return Err(format!(
-                "Invalid dealing order at hand {}: expected {} entries in deal_sequence but found {}",
-                hand_index,
-                expected_len,
-                seq_ids.len()
-            ));
-        }
-        let known: HashSet<&str> = starting_stacks.keys().map(|k| k.as_str()).collect();
-        if seq_ids.iter().any(|id| !known.contains(id)) {
-            return Err(format!(
-                "Invalid dealing order at hand {}: deal_sequence references unknown player",
-                hand_index
-            ));
-        }
-        let first_round = &seq_ids[..player_count];
-        if let Some(sb_id) = sb {
-            if first_round.first().copied() != Some(sb_id) {
-                return Err(format!(
-                    "Invalid dealing order at hand {}: expected {} to receive the first card",
-                    hand_index, sb_id
-                ));
-            }
-        }
-        if let Some(bb_id) = bb {
-            if player_count >= 2 {
-                if first_round.get(1).copied() != Some(bb_id) {
-                    return Err(format!(
-                        "Invalid dealing order at hand {}: expected {} to receive the second card",
-                        hand_index, bb_id
-                    ));
-                }
-            }
-        }
-        let first_round_set: HashSet<&str> = first_round.iter().copied().collect();
-        if first_round_set.len() != player_count {
-            return Err(format!(
-                "Invalid dealing order at hand {}: duplicate players in first deal round",
-                hand_index
-            ));
-        }
-        for round_idx in 1..rounds {
-            let chunk = &seq_ids[round_idx * player_count..(round_idx + 1) * player_count];
-            if chunk != first_round {
-                return Err(format!(
-                    "Invalid dealing order at hand {}: inconsistent card distribution order",
-                    hand_index
-                ));
-            }
-        }
-    }
-    if let Some(burn_val) = meta.get("burn_positions") {
-        let burn_arr = burn_val.as_array().ok_or_else(|| {
-            format!(
-                "Invalid dealing order at hand {}: burn_positions must be an array",
-                hand_index
-            )
-        })?;
-        let burn_positions: Option<Vec<i64>> = burn_arr.iter().map(|v| v.as_i64()).collect();
-        let burn_positions = burn_positions.ok_or_else(|| {
-            format!(
-                "Invalid dealing order at hand {}: burn_positions must contain integers",
-                hand_index
-            )
-        })?;
-        if burn_positions.len() != 3 {
-            return Err(format!(
-                "Invalid dealing order at hand {}: expected 3 burn positions",
-                hand_index
-            ));
-        }
-        let player_count_i64 = player_count as i64;
-        if player_count_i64 >= 2 {
-            let hole_cards = player_count_i64 * 2;
-            let expected = vec![
-                hole_cards + 1,
-                hole_cards + 1 + 3 + 1,
-                hole_cards + 1 + 3 + 1 + 1 + 1,
-            ];
-            if burn_positions != expected {
-                return Err(format!(
-                    "Invalid dealing order at hand {}: expected burn positions {:?} but found {:?}",
-                    hand_index, expected, burn_positions
-                ));
-            }
-        }
-    }
-    Ok(())
-}
-
-fn validate_roster_state(
-    prev: Option<&HashMap<String, i64>>,
-    current: &HashMap<String, i64>,
-    hands: u64,
-    err: &mut dyn Write,
-    ok: &mut bool,
-) {
-    if let Some(prev_map) = prev {
-        for (id, stack_start) in current {
-            if let Some(prev_stack) = prev_map.get(id) {
-                if *prev_stack != *stack_start {
-                    *ok = false;
-                    let _ = ui::write_error(
-                        err,
-                        &format!("Stack mismatch for {} at hand {}", id, hands),
-                    );
-                }
-                if *prev_stack <= 0 {
-                    *ok = false;
-                    let _ = ui::write_error(
-                        err,
-                        &format!(
-                            "Player {} reappeared after elimination at hand {}",
-                            id, hands
-                        ),
-                    );
-                }
-            } else {
-                *ok = false;
-                let _ =
-                    ui::write_error(err, &format!("Unexpected player {} at hand {}", id, hands));
-            }
-        }
-        for (id, prev_stack) in prev_map {
-            if !current.contains_key(id) && *prev_stack > 0 {
-                *ok = false;
-                let _ = ui::write_error(err, &format!("Missing player {} at hand {}", id, hands));
-            }
-        }
-    }
-    for (id, stack_start) in current {
-        if *stack_start <= 0 {
-            *ok = false;
-            let _ = ui::write_error(
-                err,
-                &format!(
-                    "Player {} has non-positive starting stack at hand {}",
-                    id, hands
-                ),
-            );
-        }
-    }
-}
-
 pub fn run<I, S>(args: I, out: &mut dyn Write, err: &mut dyn Write) -> i32
 where
     I: IntoIterator<Item = S>,
     S: AsRef<str>,
 {
-    fn strip_utf8_bom(s: &mut String) {
-        const UTF8_BOM: &str = "\u{feff}";
-        if s.starts_with(UTF8_BOM) {
-            s.drain(..UTF8_BOM.len());
-        }
-    }
-
-    fn read_text_auto(path: &str) -> Result<String, String> {
-        let mut content = if path.ends_with(".zst") {
-            // Read entire compressed file then decompress; more portable across platforms
-            let comp = std::fs::read(path).map_err(|e| e.to_string())?;
-            // Use a conservative initial capacity; zstd will grow as needed
-            let dec = zstd::bulk::decompress(&comp, 8 * 1024 * 1024).map_err(|e| e.to_string())?;
-            String::from_utf8(dec).map_err(|e| e.to_string())?
-        } else {
-            std::fs::read_to_string(path).map_err(|e| e.to_string())?
-        };
-        strip_utf8_bom(&mut content);
-        Ok(content)
-    }
     fn validate_speed(speed: Option<f64>) -> Result<(), String> {
         if let Some(s) = speed {
             if s <= 0.0 {
@@ -525,7 +76,7 @@ where
         seed: Option<u64>,
         err: &mut dyn Write,
     ) -> Option<i32> {
-        use std::io::{BufRead, BufReader, BufWriter};
+        use std::io::BufWriter;
 
         let threshold = std::env::var("AXM_DATASET_STREAM_THRESHOLD")
             .ok()
@@ -544,35 +95,24 @@ where
             })
             .unwrap_or(false);
 
-        let count_file = match std::fs::File::open(input) {
-            Ok(f) => f,
+        if input == records::STDIN {
+            // stdin can only be read once
+            return None;
+        }
+        let mut record_count = 0usize;
+        let reader = match RecordReader::open(input) {
+            Ok(r) => r,
             Err(e) => {
-                let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
+                let _ = ui::write_error(err, &e.to_string());
                 return Some(2);
             }
         };
-
-        let mut record_count = 0usize;
-        {
-            let reader = BufReader::new(count_file);
-            let mut first_line = true;
-            for line in reader.lines() {
-                match line {
-                    Ok(mut line) => {
-                        if first_line {
-                            strip_utf8_bom(&mut line);
-                            first_line = false;
-                        }
-                        if !line.trim().is_empty() {
-                            record_count += 1;
-                        }
-                    }
-                    Err(e) => {
-                        let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
-                        return Some(2);
-                    }
-                }
+        for line in reader {
+            if let Err(e) = line {
+                let _ = ui::write_error(err, &e.to_string());
+                return Some(2);
             }
+            record_count += 1;
         }
 
         if record_count == 0 {
@@ -640,44 +180,25 @@ where
         let mut test_writer =
             BufWriter::new(std::fs::File::create(out_root.join("test.jsonl")).unwrap());
 
-        let data_file = match std::fs::File::open(input) {
-            Ok(f) => f,
+        let reader = match RecordReader::open(input) {
+            Ok(r) => r,
             Err(e) => {
-                let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
+                let _ = ui::write_error(err, &e.to_string());
                 return Some(2);
             }
         };
-        let reader = BufReader::new(data_file);
-        let mut record_idx = 0usize;
-        let mut first_line = true;
-
-        for (line_idx, line_res) in reader.lines().enumerate() {
-            let mut line = match line_res {
-                Ok(line) => line,
+        for (record_idx, line) in reader.enumerate() {
+            let line = match line.and_then(|l| l.parse().map(|_| l)) {
+                Ok(l) => l.text,
                 Err(e) => {
-                    let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
+                    let _ = ui::write_error(err, &e.to_string());
                     return Some(2);
                 }
             };
-            if first_line {
-                strip_utf8_bom(&mut line);
-                first_line = false;
-            }
-            if line.trim().is_empty() {
-                continue;
-            }
-            if let Err(e) = serde_json::from_str::<axm_engine::logger::HandRecord>(&line) {
-                let _ = ui::write_error(
-                    err,
-                    &format!("Invalid record at line {}: {}", line_idx + 1, e),
-                );
-                return Some(2);
-            }
             let bucket = assignments
                 .get(record_idx)
                 .copied()
                 .unwrap_or(SplitSlot::Test);
-            record_idx += 1;
             match bucket {
                 SplitSlot::Train => {
                     let _ = writeln!(train_writer, "{}", line);
@@ -703,76 +224,10 @@ where
             p1: u64,
             skipped: u64,
             corrupted: u64,
+            first_corrupted: Option<String>,
             stats_ok: bool,
         }
 
-        fn consume_stats_content(content: String, state: &mut StatsState, err: &mut dyn Write) {
-            let has_trailing_nl = content.ends_with('\n');
-            let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
-            for (i, line) in lines.iter().enumerate() {
-                let parsed: serde_json::Value = match serde_json::from_str(line) {
-                    Ok(v) => v,
-                    Err(_) => {
-                        if i == lines.len() - 1 && !has_trailing_nl {
-                            state.skipped += 1;
-                        } else {
-                            state.corrupted += 1;
-                        }
-                        continue;
-                    }
-                };
-
-                let rec: axm_engine::logger::HandRecord =
-                    match serde_json::from_value(parsed.clone()) {
-                        Ok(v) => v,
-                        Err(_) => {
-                            state.corrupted += 1;
-                            continue;
-                        }
-                    };
-
-                if let Some(net_obj) = parsed.get("net_result").and_then(|v| v.as_object()) {
-                    let mut sum = 0i64;
-                    let mut invalid = false;
-                    for (player, val) in net_obj {
-                        if let Some(n) = val.as_i64() {
-                            sum += n;
-                        } else {
-                            invalid = true;
-                            state.stats_ok = false;
-                            let _ = ui::write_error(
-                                err,
-                                &format!(
-                                    "Invalid net_result value for {} at hand {}",
-                                    player, rec.hand_id
-                                ),
-                            );
-                        }
-                    }
-                    if sum != 0 {
-                        state.stats_ok = false;
-                        let _ = ui::write_error(
-                            err,
-                            &format!("Chip conservation violated at hand {}", rec.hand_id),
-                        );
-                    }
-                    if invalid {
-                        continue;
-                    }
-                }
-
-                state.hands += 1;
-                if let Some(r) = rec.result.as_deref() {
-                    if r == "p0" {
-                        state.p0 += 1;
-                    }
-                    if r == "p1" {
-                        state.p1 += 1;
-                    }
-                }
-            }
-        }
-
         let path = Path::new(input);
         let mut state = StatsState {
             hands: 0,
@@ -780,44 +235,57 @@ where
             p1: 0,
             skipped: 0,
             corrupted: 0,
+            first_corrupted: None,
             stats_ok: true,
         };
-
-        if path.is_dir() {
-            let mut stack = vec![path.to_path_buf()];
-            while let Some(d) = stack.pop() {
-                let rd = match std::fs::read_dir(&d) {
-                    Ok(v) => v,
-                    Err(_) => continue,
-                };
-                for entry in rd.flatten() {
-                    let p = entry.path();
-                    if p.is_dir() {
-                        stack.push(p);
-                        continue;
-                    }
-                    let name = p.file_name().and_then(|s| s.to_str()).unwrap_or("");
-                    if !(name.ends_with(".jsonl") || name.ends_with(".jsonl.zst")) {
-                        continue;
-                    }
-                    match read_text_auto(p.to_str().unwrap()) {
-                        Ok(s) => consume_stats_content(s, &mut state, err),
-                        Err(e) => {
-                            let _ = ui::write_error(
-                                err,
-                                &format!("Failed to read {}: {}", p.display(), e),
-                            );
-                            state.stats_ok = false;
-                        }
+        let reader = match RecordReader::open(input) {
+            Ok(r) => r,
+            Err(e) => {
+                let _ = ui::write_error(err, &e.to_string());
+                return 2;
+            }
+        };
+        for line in reader {
+            let line = match line {
+                Ok(l) => l,
+                Err(e) => {
+                    // an unreadable file in a directory does not stop the scan
+                    let _ = ui::write_error(err, &e.to_string());
+                    state.stats_ok = false;
+                    continue;
+                }
+            };
+            let rec = match line.parse() {
+                Ok(v) => v,
+                Err(e) => {
+                    // a torn final line is an interrupted write, not corruption
+                    if !e.is_data() && line.torn {
+                        state.skipped += 1;
+                    } else {
+                        state.corrupted += 1;
+                        state.first_corrupted.get_or_insert(e.to_string());
                     }
+                    continue;
+                }
+            };
+
+            if let Some(net) = &rec.net_result {
+                if net.values().sum::<i64>() != 0 {
+                    state.stats_ok = false;
+                    let _ = ui::write_error(
+                        err,
+                        &format!("Chip conservation violated at hand {}", rec.hand_id),
+                    );
                 }
             }
-        } else {
-            match read_text_auto(input) {
-                Ok(s) => consume_stats_content(s, &mut state, err),
-                Err(e) => {
-                    let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
-                    return 2;
+
+            state.hands += 1;
+            if let Some(r) = rec.result.as_deref() {
+                if r == "p0" {
+                    state.p0 += 1;
+                }
+                if r == "p1" {
+                    state.p1 += 1;
                 }
             }
         }
@@ -825,7 +293,11 @@ where
         if state.corrupted > 0 {
             let _ = ui::write_error(
                 err,
-                &format!("Skipped {} corrupted record(s)", state.corrupted),
+                &format!(
+                    "Skipped {} corrupted record(s); first: {}",
+                    state.corrupted,
+                    state.first_corrupted.as_deref().unwrap_or_default()
+                ),
             );
         }
         if state.skipped > 0 {
@@ -851,7 +323,7 @@ where
         }
     }
 
-    fn export_sqlite(content: &str, output: &str, err: &mut dyn Write) -> i32 {
+    fn export_sqlite(input: &str, output: &str, err: &mut dyn Write) -> i32 {
         enum ExportAttemptError {
             Busy(String),
             Fatal(String),
@@ -868,7 +340,10 @@ where
             )
         }
 
-        fn export_sqlite_attempt(content: &str, output: &str) -> Result<(), ExportAttemptError> {
+        fn export_sqlite_attempt(input: &str, output: &str) -> Result<(), ExportAttemptError> {
+            let reader =
+                RecordReader::open(input).map_err(|e| ExportAttemptError::Fatal(e.to_string()))?;
+
             let output_path = std::path::Path::new(output);
             if let Some(parent) = output_path.parent() {
                 if !parent.as_os_str().is_empty() {
@@ -898,6 +373,22 @@ where
                 }
             })?;
 
+            // the export is a throwaway snapshot of `input`, rebuilt on every
+            // run; never let it wipe an `axm db` store
+            let is_store = tx
+                .query_row(
+                    "SELECT COUNT(*) FROM sqlite_master WHERE name = 'schema_version'",
+                    [],
+                    |r| r.get::<_, i64>(0),
+                )
+                .map_err(|e| ExportAttemptError::Fatal(format!("Failed to read schema: {}", e)))?;
+            if is_store > 0 {
+                return Err(ExportAttemptError::Fatal(format!(
+                    "{} is an axm db store; use `axm db ingest` to add hands to it",
+                    output
+                )));
+            }
+
             tx.execute("DROP TABLE IF EXISTS hands", []).map_err(|e| {
                 if sqlite_busy(&e) {
                     ExportAttemptError::Busy(format!("reset schema: {}", e))
@@ -939,14 +430,12 @@ where
                     }
                 })?;
 
-            for (line_idx, line) in content.lines().enumerate() {
-                let raw = line.trim();
-                if raw.is_empty() {
-                    continue;
-                }
-
-                let record: axm_engine::logger::HandRecord = serde_json::from_str(raw)
-                    .map_err(|e| ExportAttemptError::Fatal(format!("Invalid record: {}", e)))?;
+            for line in reader {
+                let line = line.map_err(|e| ExportAttemptError::Fatal(e.to_string()))?;
+                let raw = line.text.trim();
+                let record = line
+                    .parse()
+                    .map_err(|e| ExportAttemptError::Fatal(e.to_string()))?;
 
                 let axm_engine::logger::HandRecord {
                     hand_id,
@@ -984,9 +473,8 @@ where
                 .map_err(|e| {
                     if sqlite_busy(&e) {
                         ExportAttemptError::Busy(format!(
-                            "insert record at line {}: {}",
-                            line_idx + 1,
-                            e
+                            "insert record at {}:{}: {}",
+                            line.source, line.line, e
                         ))
                     } else {
                         ExportAttemptError::Fatal(format!("Failed to insert record: {}", e))
@@ -1012,13 +500,19 @@ where
             .and_then(|v| v.parse::<u32>().ok())
             .filter(|&v| v > 0)
             .unwrap_or(3);
+        // a retry would need to read stdin again
+        let max_attempts = if input == records::STDIN {
+            1
+        } else {
+            max_attempts
+        };
         let backoff_ms = std::env::var("AXM_EXPORT_SQLITE_RETRY_SLEEP_MS")
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
             .unwrap_or(50);
 
         for attempt in 1..=max_attempts {
-            match export_sqlite_attempt(content, output) {
+            match export_sqlite_attempt(input, output) {
                 Ok(()) => return 0,
                 Err(ExportAttemptError::Busy(msg)) => {
                     if attempt == max_attempts {
@@ -1297,8 +791,8 @@ where
     }
 
     const COMMANDS: &[&str] = &[
-        "play", "replay", "stats", "verify", "deal", "bench", "sim", "eval", "export", "dataset",
-        "cfg", "doctor", "rng", "serve", "train",
+        "play", "replay", "stats", "verify", "deal", "equity", "bench", "selftest", "sim", "eval",
+        "export", "dataset", "repair", "db", "cfg", "doctor", "rng", "serve", "train",
     ];
     let argv: Vec<String> = args.into_iter().map(|s| s.as_ref().to_string()).collect();
     if argv.iter().any(|a| a == "--help" || a == "-h") {
@@ -1371,6 +865,9 @@ where
                 hands,
                 seed,
                 level,
+                blinds,
+                ai,
+                log_dir,
             } => {
                 let hands = hands.unwrap_or(1);
                 let seed = seed.unwrap_or_else(|| rand::random());
@@ -1402,26 +899,60 @@ where
                     hands,
                     seed
                 );
+                let schedule = match load_schedule(blinds.as_deref()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
                 let _ = writeln!(out, "Level: {}", level);
-                let mut eng = Engine::new(Some(seed), level);
-                eng.shuffle();
+                let registry = AgentRegistry::with_builtins();
+                let ai = ai.as_deref().unwrap_or("hand-strength");
+                // with --vs ai the other seat is put on autopilot
+                let mut agents =
+                    match create_agents(&registry, ["always-call", ai], seed, DEFAULT_TIMEOUT) {
+                        Ok(a) => a,
+                        Err(msg) => {
+                            let _ = ui::write_error(err, &msg);
+                            return 2;
+                        }
+                    };
+                let mut logger = match log_dir.as_deref().map(open_daily_log).transpose() {
+                    Ok(l) => l,
+                    Err(msg) => {
+                        let _ = ui::write_error(err, &msg);
+                        return 2;
+                    }
+                };
+                // stacks carry over, so the run is one session named after its first hand
+                let mut session_id = None;
+                let mut session = Session::new(Some(seed), level, schedule);
                 let scripted = std::env::var("AXM_TEST_INPUT").ok();
                 let mut played = 0u32;
+                let mut cur_level = level;
                 for i in 1..=hands {
-                    // simple level progression: +1 every 2 hands
-                    let cur_level: u8 = level.saturating_add(((i - 1) / 2) as u8);
-                    if i > 1 {
+                    if session.is_over() {
+                        break;
+                    }
+                    if session.next_level() != cur_level {
+                        cur_level = session.next_level();
                         let _ = writeln!(out, "Level: {}", cur_level);
                     }
-                    let (sb, bb) = match cur_level {
-                        1 => (50, 100),
-                        2 => (75, 150),
-                        3 => (100, 200),
-                        _ => (150, 300),
-                    };
-                    let _ = writeln!(out, "Blinds: SB={} BB={}", sb, bb);
+                    let blinds = session.engine().schedule().level(cur_level);
+                    match blinds.ante {
+                        0 => {
+                            let _ = writeln!(out, "Blinds: SB={} BB={}", blinds.sb, blinds.bb);
+                        }
+                        ante => {
+                            let _ = writeln!(
+                                out,
+                                "Blinds: SB={} BB={} Ante={}",
+                                blinds.sb, blinds.bb, ante
+                            );
+                        }
+                    }
                     let _ = writeln!(out, "Hand {}", i);
-                    let _ = eng.deal_hand();
                     match vs {
                         Vs::Human => {
                             // prompt once; in tests, read from AXM_TEST_INPUT
@@ -1432,32 +963,86 @@ where
                             }
                         }
                         Vs::Ai => {
-                            let _ = writeln!(out, "ai: check");
+                            let hand_seed = session.next_hand_seed();
+                            let res = session.play_hand(|engine, seat, _| {
+                                let action = match engine.observation(seat) {
+                                    Some(obs) => agents[seat].act(&obs),
+                                    None => PlayerAction::Fold,
+                                };
+                                let who = if seat == 1 { "ai" } else { "p0" };
+                                let _ = writeln!(out, "{}: {}", who, action_label(&action));
+                                action
+                            });
+                            if let Err(e) = res {
+                                let _ = ui::write_error(err, &format!("Hand {} aborted: {}", i, e));
+                                return 2;
+                            }
+                            if let Some(l) = logger.as_mut() {
+                                let hand_id = l.next_id();
+                                let session_id = session_id.get_or_insert_with(|| hand_id.clone());
+                                let written = match session.engine().hand_record(hand_id) {
+                                    Some(mut record) => {
+                                        record.seed = Some(hand_seed);
+                                        record.session = Some(session_id.clone());
+                                        l.write(&record)
+                                    }
+                                    None => Ok(()),
+                                };
+                                if let Err(e) = written {
+                                    let _ = ui::write_error(
+                                        err,
+                                        &format!("Failed to write hand log: {}", e),
+                                    );
+                                    return 2;
+                                }
+                            }
                         }
                     }
                     played += 1;
                 }
+                report_faults(&agents, session.rejected(), err);
                 let _ = writeln!(out, "Session hands={}", hands);
                 let _ = writeln!(out, "Hands played: {} (completed)", played);
+                if matches!(vs, Vs::Ai) {
+                    let summary = session.summary();
+                    let winner = summary
+                        .winner
+                        .map(|w| format!("p{}", w))
+                        .unwrap_or_else(|| "none".to_string());
+                    let _ = writeln!(
+                        out,
+                        "Summary: winner={} level={} stacks=p0:{} p1:{}",
+                        winner,
+                        summary.level_reached,
+                        summary.final_stacks[0],
+                        summary.final_stacks[1]
+                    );
+                }
                 0
             }
             Commands::Replay { input, speed } => {
-                match read_text_auto(&input) {
-                    Ok(content) => {
-                        // Validate speed via helper for clarity and future reuse
-                        if let Err(msg) = validate_speed(speed) {
-                            let _ = ui::write_error(err, &msg);
-                            return 2;
-                        }
-                        let count = content.lines().filter(|l| !l.trim().is_empty()).count();
-                        let _ = writeln!(out, "Replayed: {} hands", count);
-                        0
-                    }
+                let reader = match RecordReader::open(&input) {
+                    Ok(r) => r,
                     Err(e) => {
-                        let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
-                        2
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                // Validate speed via helper for clarity and future reuse
+                if let Err(msg) = validate_speed(speed) {
+                    let _ = ui::write_error(err, &msg);
+                    return 2;
+                }
+                let mut count = 0usize;
+                for line in reader {
+                    if let Err(e) = line.and_then(|l| l.parse()) {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
                     }
+                    count += 1;
                 }
+                let _ = writeln!(out, "Replayed: {} hands", count);
+                0
             }
             Commands::Stats { input } => run_stats(&input, out, err),
             Commands::Verify { input } => {
@@ -1465,8 +1050,8 @@ where
                 let mut ok = true;
                 let mut hands = 0u64;
                 let mut game_over = false;
-                let mut stacks_after_hand: HashMap<String, i64> = HashMap::new();
-                const MIN_CHIP_UNIT: i64 = 25;
+                let mut stacks_after_hand: BTreeMap<String, i64> = BTreeMap::new();
+                let mut session: Option<String> = None;
                 let Some(path) = input else {
                     let _ = ui::write_error(err, "input required");
                     return 2;
@@ -1477,225 +1062,118 @@ where
                         && &s[8..9] == "-"
                         && s[9..].chars().all(|c| c.is_ascii_digit())
                 };
-                match read_text_auto(&path) {
-                    Ok(content) => {
-                        for line in content.lines().filter(|l| !l.trim().is_empty()) {
-                            hands += 1;
-                            if game_over {
+                let reader = match RecordReader::open(&path) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                for line in reader {
+                    let line = match line {
+                        Ok(l) => l,
+                        Err(e) => {
+                            let _ = ui::write_error(err, &e.to_string());
+                            return 2;
+                        }
+                    };
+                    hands += 1;
+                    let rec: HandRecord = match line.parse() {
+                        Ok(rec) => rec,
+                        Err(e) => {
+                            ok = false;
+                            let _ = ui::write_error(err, &e.to_string());
+                            continue;
+                        }
+                    };
+                    // stacks only carry over within a session
+                    if rec.session != session {
+                        stacks_after_hand.clear();
+                        game_over = false;
+                        session = rec.session.clone();
+                    }
+                    if game_over {
+                        ok = false;
+                        let _ = ui::write_error(
+                            err,
+                            &format!("Hand {} recorded after player elimination", hands),
+                        );
+                    }
+                    let ctx = match verify::HandContext::from_record(&rec, hands) {
+                        Ok(ctx) => ctx,
+                        Err(violation) => {
+                            ok = false;
+                            let _ = ui::write_error(err, &violation.message);
+                            continue;
+                        }
+                    };
+                    let mut violations = Vec::new();
+                    if !rec.players.is_empty() {
+                        let prev_state = if stacks_after_hand.is_empty() {
+                            None
+                        } else {
+                            Some(&stacks_after_hand)
+                        };
+                        violations.extend(verify::check_roster(&ctx, prev_state));
+                        stacks_after_hand = ctx.starting_stacks.clone();
+                        for id in rec.net_result.iter().flat_map(|nr| nr.keys()) {
+                            if !stacks_after_hand.contains_key(id) {
                                 ok = false;
                                 let _ = ui::write_error(
                                     err,
-                                    &format!("Hand {} recorded after player elimination", hands),
+                                    &format!(
+                                        "Unknown player {} in net_result at hand {}",
+                                        id, hands
+                                    ),
                                 );
                             }
-                            // parse as Value first to validate optional net_result chip conservation
-                            let v: serde_json::Value = match serde_json::from_str(line) {
-                                Ok(v) => v,
-                                Err(_) => {
-                                    ok = false;
-                                    let _ = ui::write_error(err, "Invalid record");
-                                    continue;
-                                }
-                            };
-                            let mut starting_stacks: Option<HashMap<String, i64>> = None;
-                            if let Some(players) = v.get("players").and_then(|p| p.as_array()) {
-                                let mut start_map = HashMap::new();
-                                for player in players {
-                                    let Some(id) = player.get("id").and_then(|x| x.as_str()) else {
-                                        continue;
-                                    };
-                                    let stack = player
-                                        .get("stack_start")
-                                        .and_then(|x| x.as_i64())
-                                        .unwrap_or(0);
-                                    start_map.insert(id.to_string(), stack);
-                                }
-                                let prev_state = if stacks_after_hand.is_empty() {
-                                    None
-                                } else {
-                                    Some(&stacks_after_hand)
-                                };
-                                validate_roster_state(prev_state, &start_map, hands, err, &mut ok);
-                                starting_stacks = Some(start_map.clone());
-                                stacks_after_hand = start_map;
-                                if let Some(nr_obj) =
-                                    v.get("net_result").and_then(|x| x.as_object())
-                                {
-                                    for id in nr_obj.keys() {
-                                        if !stacks_after_hand.contains_key(id) {
-                                            ok = false;
-                                            let _ = ui::write_error(
-                                                err,
-                                                &format!(
-                                                    "Unknown player {} in net_result at hand {}",
-                                                    id, hands
-                                                ),
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                            if let (Some(ref start_map), Some(meta_obj)) = (
-                                starting_stacks.as_ref(),
-                                v.get("meta").and_then(|m| m.as_object()),
-                            ) {
-                                let button_id = v.get("button").and_then(|b| b.as_str());
-                                if let Err(msg) =
-                                    validate_dealing_meta(meta_obj, button_id, start_map, hands)
-                                {
-                                    ok = false;
-                                    let _ = ui::write_error(err, &msg);
-                                }
-                            }
-                            let mut big_blind = MIN_CHIP_UNIT;
-                            if let Some(blinds_val) = v.get("blinds") {
-                                if let Some(bb) = blinds_val.get("bb").and_then(|x| x.as_i64()) {
-                                    big_blind = bb;
-                                } else if let Some(arr) = blinds_val.as_array() {
-                                    if arr.len() >= 2 {
-                                        if let Some(bb) = arr[1].as_i64() {
-                                            big_blind = bb;
-                                        }
-                                    }
-                                }
-                            }
-                            if big_blind < MIN_CHIP_UNIT {
-                                big_blind = MIN_CHIP_UNIT;
-                            }
-                            if let Some(actions) = v.get("actions").and_then(|a| a.as_array()) {
-                                if let Some(ref start_map) = starting_stacks {
-                                    if let Err(msg) = ensure_no_reopen_after_short_all_in(
-                                        actions,
-                                        big_blind,
-                                        MIN_CHIP_UNIT,
-                                        start_map,
-                                        hands,
-                                    ) {
-                                        ok = false;
-                                        let _ = ui::write_error(err, &msg);
-                                    }
-                                }
-                            }
-                            if let Some(nr) = v.get("net_result").and_then(|x| x.as_object()) {
-                                let mut sum: i64 = 0;
-                                for val in nr.values() {
-                                    if let Some(n) = val.as_i64() {
-                                        sum += n;
-                                    }
-                                }
-                                if sum != 0 {
-                                    ok = false;
-                                    let _ = ui::write_error(err, "Chip conservation violated");
-                                }
-                                for (id, delta) in nr.iter() {
-                                    if let Some(val) = delta.as_i64() {
-                                        let entry =
-                                            stacks_after_hand.entry(id.clone()).or_insert(0);
-                                        *entry += val;
-                                    }
-                                }
-                                if stacks_after_hand.values().any(|stack| *stack <= 0) {
-                                    game_over = true;
-                                }
-                            }
-                            match serde_json::from_value::<axm_engine::logger::HandRecord>(
-                                v.clone(),
-                            ) {
-                                Ok(rec) => {
-                                    if rec.board.len() != 5 {
-                                        ok = false;
-                                        let _ = ui::write_error(
-                                            err,
-                                            &format!(
-                                                "Invalid board length at hand {}: expected 5 cards but found {}",
-                                                hands,
-                                                rec.board.len()
-                                            ),
-                                        );
-                                    }
-
-                                    let mut seen_cards: HashSet<axm_engine::cards::Card> =
-                                        HashSet::new();
-                                    let mut duplicate_cards: HashSet<axm_engine::cards::Card> =
-                                        HashSet::new();
-                                    {
-                                        let mut record_card = |card: axm_engine::cards::Card| {
-                                            if !seen_cards.insert(card) {
-                                                duplicate_cards.insert(card);
-                                            }
-                                        };
-                                        for card in &rec.board {
-                                            record_card(*card);
-                                        }
-                                        if let Some(players) =
-                                            v.get("players").and_then(|p| p.as_array())
-                                        {
-                                            for player in players {
-                                                let pid = player
-                                                    .get("id")
-                                                    .and_then(|x| x.as_str())
-                                                    .unwrap_or("unknown");
-                                                if let Some(hole_cards) = player
-                                                    .get("hole_cards")
-                                                    .and_then(|h| h.as_array())
-                                                {
-                                                    for card_val in hole_cards {
-                                                        match serde_json::from_value::<
-                                                            axm_engine::cards::Card,
-                                                        >(
-                                                            card_val.clone()
-                                                        ) {
-                                                            Ok(card) => record_card(card),
-                                                            Err(_) => {
-                                                                ok = false;
-                                                                let _ = ui::write_error(
-                                                                    err,
-                                                                    &format!(
-                                                                        "Invalid card specification for {} at hand {}",
-                                                                        pid,
-                                                                        hands
-                                                                    ),
-                                                                );
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    if !duplicate_cards.is_empty() {
-                                        ok = false;
-                                        let mut cards: Vec<String> = duplicate_cards
-                                            .iter()
-                                            .map(|card| format!("{:?} {:?}", card.rank, card.suit))
-                                            .collect();
-                                        cards.sort();
-                                        let _ = ui::write_error(
-                                            err,
-                                            &format!(
-                                                "Duplicate card(s) detected at hand {}: {}",
-                                                hands,
-                                                cards.join(", ")
-                                            ),
-                                        );
-                                    }
-
-                                    if !valid_id(&rec.hand_id) {
-                                        ok = false;
-                                        let _ = ui::write_error(err, "Invalid hand_id");
-                                    }
-                                }
-                                Err(_) => {
-                                    ok = false;
-                                    let _ = ui::write_error(err, "Invalid record");
-                                }
-                            }
                         }
                     }
-                    Err(e) => {
-                        let _ = ui::write_error(err, &format!("Failed to read {}: {}", path, e));
-                        return 2;
+                    violations.extend(verify::verify_hand(&ctx, &rec.actions));
+                    violations.extend(verify::check_board(&ctx, &rec));
+                    for violation in &violations {
+                        ok = false;
+                        let _ = ui::write_error(err, &violation.message);
+                    }
+                    if let Some(nr) = &rec.net_result {
+                        if nr.values().sum::<i64>() != 0 {
+                            ok = false;
+                            let _ = ui::write_error(err, "Chip conservation violated");
+                        }
+                        for (id, delta) in nr {
+                            *stacks_after_hand.entry(id.clone()).or_insert(0) += delta;
+                        }
+                        if stacks_after_hand.values().any(|stack| *stack <= 0) {
+                            game_over = true;
+                        }
+                    }
+                    let mut seen_cards: HashSet<Card> = HashSet::new();
+                    let mut duplicate_cards: HashSet<Card> = HashSet::new();
+                    let hole_cards = rec.players.iter().flat_map(|p| &p.hole_cards);
+                    for &card in rec.board.iter().chain(hole_cards) {
+                        if !seen_cards.insert(card) {
+                            duplicate_cards.insert(card);
+                        }
+                    }
+                    if !duplicate_cards.is_empty() {
+                        ok = false;
+                        let mut cards: Vec<String> = duplicate_cards
+                            .iter()
+                            .map(|card| card.to_string())
+                            .collect();
+                        cards.sort();
+                        let _ = ui::write_error(
+                            err,
+                            &format!(
+                                "Duplicate card(s) detected at hand {}: {}",
+                                hands,
+                                cards.join(", ")
+                            ),
+                        );
+                    }
+                    if !valid_id(&rec.hand_id) {
+                        ok = false;
+                        let _ = ui::write_error(err, "Invalid hand_id");
                     }
                 }
                 let status = if ok { "OK" } else { "FAIL" };
@@ -1712,46 +1190,113 @@ where
                 ai_b,
                 hands,
                 seed,
+                level,
+                blinds,
+                ai_timeout_ms,
             } => {
                 if ai_a == ai_b {
                     let _ = ui::write_error(err, "Warning: identical AI models");
                 }
-                let mut a_wins = 0u32;
-                let mut b_wins = 0u32;
+                let level = level.unwrap_or(1);
+                let schedule = match load_schedule(blinds.as_deref()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                let registry = AgentRegistry::with_builtins();
                 let s = seed.unwrap_or_else(|| rand::random());
-                let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(s);
-                for _ in 0..hands {
-                    if (rng.next_u32() & 1) == 0 {
-                        a_wins += 1;
-                    } else {
-                        b_wins += 1;
+                let mut agents =
+                    match create_agents(&registry, [&ai_a, &ai_b], s, bot_timeout(ai_timeout_ms)) {
+                        Ok(a) => a,
+                        Err(msg) => {
+                            let _ = ui::write_error(err, &msg);
+                            return 2;
+                        }
+                    };
+                let r = match eval::run_duplicate(&mut agents, hands, s, level, &schedule) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &format!("Failed to play hand: {}", e));
+                        return 2;
                     }
-                }
-                let _ = writeln!(out, "Eval: hands={} A:{} B:{}", hands, a_wins, b_wins);
+                };
+                report_faults(&agents, r.rejected, err);
+                let _ = writeln!(
+                    out,
+                    "Eval: hands={} deals={} (duplicate) seed={} level={}",
+                    r.hands, r.deals, s, level
+                );
+                let _ = writeln!(out, "A: {} {}", ai_a, r.summary(false));
+                let _ = writeln!(out, "B: {} {}", ai_b, r.summary(true));
                 0
             }
-            Commands::Bench => {
-                // quick bench: evaluate 200 unique 7-card draws from shuffled deck
-                use axm_engine::cards::Card;
-                use axm_engine::deck::Deck;
-                let start = std::time::Instant::now();
-                let mut cnt = 0u64;
-                let mut deck = Deck::new_with_seed(1);
-                deck.shuffle();
-                for _ in 0..200 {
-                    if deck.remaining() < 7 {
-                        deck.shuffle();
+            Commands::Bench {
+                scenario,
+                iterations,
+                warmup,
+                output,
+                baseline,
+                threshold,
+                list,
+            } => run_bench(
+                &scenario,
+                iterations.unwrap_or(5),
+                warmup.unwrap_or(1),
+                output.as_deref(),
+                baseline.as_deref(),
+                threshold.unwrap_or(10.0),
+                list,
+                out,
+                err,
+            ),
+            Commands::Repair { input } => run_repair(&input, out, err),
+            Commands::Db { action } => run_db(action, out, err),
+            Commands::Selftest { seven, threads } => {
+                let threads = threads
+                    .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+                    .unwrap_or(1);
+                let mut passed = true;
+                let mut reports = vec![selftest::check_five(threads)];
+                if seven {
+                    reports.push(selftest::check_seven(threads));
+                }
+                for report in reports {
+                    let _ = writeln!(
+                        out,
+                        "Self-test: {} cards, {} hands",
+                        report.cards, report.hands
+                    );
+                    let expected = report.expected.unwrap_or(report.counts);
+                    for ((category, got), want) in
+                        selftest::CATEGORIES.iter().zip(report.counts).zip(expected)
+                    {
+                        let status = if got == want {
+                            "ok".to_string()
+                        } else {
+                            format!("expected {}", want)
+                        };
+                        let _ = writeln!(out, "  {:?} {} {}", category, got, status);
                     }
-                    let mut arr: [Card; 7] = [deck.deal_card().unwrap(); 7];
-                    for i in 1..7 {
-                        arr[i] = deck.deal_card().unwrap();
+                    if report.cards == 7 {
+                        let _ = writeln!(out, "  evaluator mismatches: {}", report.mismatches);
                     }
-                    let _ = axm_engine::hand::evaluate_hand(&arr);
-                    cnt += 1;
+                    if let Some(hand) = &report.first_mismatch {
+                        let _ = ui::write_error(
+                            err,
+                            &format!("evaluate_hand_optimized differs on {}", format_cards(hand)),
+                        );
+                    }
+                    passed &= report.passed();
+                }
+                if passed {
+                    let _ = writeln!(out, "Self-test passed");
+                    0
+                } else {
+                    let _ = ui::write_error(err, "Self-test failed");
+                    2
                 }
-                let dur = start.elapsed();
-                let _ = writeln!(out, "Benchmark: {} iters in {:?}", cnt, dur);
-                0
             }
             Commands::Deal { seed } => {
                 let base_seed = seed.unwrap_or_else(|| rand::random());
@@ -1761,32 +1306,68 @@ where
                 let p = eng.players();
                 let hc1 = p[0].hole_cards();
                 let hc2 = p[1].hole_cards();
-                let fmt = |c: axm_engine::cards::Card| format!("{:?}{:?}", c.rank, c.suit);
-                let _ = writeln!(
-                    out,
-                    "Hole P1: {} {}",
-                    fmt(hc1[0].unwrap()),
-                    fmt(hc1[1].unwrap())
-                );
-                let _ = writeln!(
-                    out,
-                    "Hole P2: {} {}",
-                    fmt(hc2[0].unwrap()),
-                    fmt(hc2[1].unwrap())
-                );
+                let _ = writeln!(out, "Hole P1: {} {}", hc1[0].unwrap(), hc1[1].unwrap());
+                let _ = writeln!(out, "Hole P2: {} {}", hc2[0].unwrap(), hc2[1].unwrap());
                 let b = eng.board();
-                let _ = writeln!(
-                    out,
-                    "Board: {} {} {} {} {}",
-                    fmt(b[0]),
-                    fmt(b[1]),
-                    fmt(b[2]),
-                    fmt(b[3]),
-                    fmt(b[4])
-                );
+                let _ = writeln!(out, "Board: {} {} {} {} {}", b[0], b[1], b[2], b[3], b[4]);
+                0
+            }
+            Commands::Equity {
+                hands,
+                board,
+                dead,
+                iterations,
+                seed,
+            } => {
+                let cards = parse_cards(board.as_deref().unwrap_or(""))
+                    .and_then(|b| Ok((b, parse_cards(dead.as_deref().unwrap_or(""))?)));
+                let (board, dead) = match cards {
+                    Ok(v) => v,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                let opts = EquityOptions {
+                    iterations: iterations.unwrap_or(DEFAULT_ITERATIONS),
+                    seed: seed.unwrap_or_else(|| rand::random()),
+                    ..EquityOptions::default()
+                };
+                // anything other than two exact cards is read as a range
+                let holes: Vec<[Card; 2]> = match hands.iter().map(|h| parse_hole(h)).collect() {
+                    Ok(holes) => holes,
+                    Err(_) => return run_range_equity(&hands, &board, &dead, &opts, out, err),
+                };
+                let res = match equity::calculate(&holes, &board, &dead, &opts) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                if res.exact {
+                    let _ = writeln!(out, "Equity: exact ({} runouts)", res.runouts);
+                } else {
+                    let _ = writeln!(
+                        out,
+                        "Equity: monte carlo ({} trials, seed={})",
+                        res.runouts, opts.seed
+                    );
+                }
+                for (hole, eq) in holes.iter().zip(&res.hands) {
+                    let _ = writeln!(
+                        out,
+                        "{} win={:.2}% tie={:.2}% loss={:.2}% equity={:.2}%",
+                        format_cards(hole),
+                        eq.win_pct(res.runouts),
+                        eq.tie_pct(res.runouts),
+                        eq.loss_pct(res.runouts),
+                        eq.equity_pct(res.runouts)
+                    );
+                }
                 0
             }
-            Commands::Rng { seed } => {
+            Commands::Rng { seed, hand } => {
                 let s = seed.unwrap_or_else(|| rand::random());
                 let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(s);
                 let mut vals = vec![];
@@ -1794,6 +1375,20 @@ where
                     vals.push(rng.next_u64());
                 }
                 let _ = writeln!(out, "RNG sample: {:?}", vals);
+                // with --hand, --seed is the session seed
+                let hand_seed = match hand {
+                    Some(i) => {
+                        let hs = derive_hand_seed(s, i);
+                        let _ = writeln!(out, "Hand seed: {} (session {} hand {})", hs, s, i);
+                        hs
+                    }
+                    None => s,
+                };
+                let mut deck = Deck::new_with_seed(hand_seed);
+                deck.shuffle();
+                let order: Vec<String> = deck.order().iter().map(|c| c.to_string()).collect();
+                let _ = writeln!(out, "Deck: {}", order.join(" "));
+                let _ = writeln!(out, "Commitment: {}", deck.commitment());
                 0
             }
             Commands::Sim {
@@ -1802,6 +1397,10 @@ where
                 seed,
                 level,
                 resume,
+                blinds,
+                ai,
+                ai_timeout_ms,
+                log_dir,
             } => {
                 let total: usize = hands as usize;
                 if total == 0 {
@@ -1809,6 +1408,23 @@ where
                     return 2;
                 }
                 let level = level.unwrap_or(1);
+                let schedule = match load_schedule(blinds.as_deref()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                let registry = AgentRegistry::with_builtins();
+                let ai = ai.unwrap_or_else(|| "always-call".to_string());
+                let mut agents =
+                    match create_agents(&registry, [&ai, &ai], 0, bot_timeout(ai_timeout_ms)) {
+                        Ok(a) => a,
+                        Err(msg) => {
+                            let _ = ui::write_error(err, &msg);
+                            return 2;
+                        }
+                    };
                 let mut completed = 0usize;
                 let mut path = None;
                 if let Some(outp) = output.clone() {
@@ -1816,11 +1432,32 @@ where
                 }
                 // resume: count existing unique hand_ids and warn on duplicates
                 if let Some(res) = resume.as_ref() {
-                    let contents = std::fs::read_to_string(res).unwrap_or_default();
+                    // an interrupted run can leave half a record at the end
+                    match axm_engine::logger::truncate_torn_tail(res) {
+                        Ok(0) => {}
+                        Ok(n) => {
+                            let _ = writeln!(err, "Warning: dropped torn final line ({} bytes)", n);
+                        }
+                        Err(e) => {
+                            let _ = ui::write_error(err, &format!("Failed to open {}: {}", res, e));
+                            return 2;
+                        }
+                    }
+                    let lines = if std::path::Path::new(res).exists() {
+                        match RecordReader::open(res) {
+                            Ok(r) => Some(r),
+                            Err(e) => {
+                                let _ = ui::write_error(err, &e.to_string());
+                                return 2;
+                            }
+                        }
+                    } else {
+                        None
+                    };
                     let mut seen = std::collections::HashSet::new();
                     let mut dups = 0usize;
-                    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
-                        let hid = serde_json::from_str::<serde_json::Value>(line)
+                    for line in lines.into_iter().flatten().map_while(Result::ok) {
+                        let hid = serde_json::from_str::<serde_json::Value>(&line.text)
                             .ok()
                             .and_then(|v| {
                                 v.get("hand_id")
@@ -1843,8 +1480,6 @@ where
                     let _ = writeln!(out, "Resumed from {}", completed);
                 }
                 let base_seed = seed.unwrap_or_else(|| rand::random());
-                let mut eng = Engine::new(Some(base_seed), level);
-                eng.shuffle();
                 let break_after = std::env::var("AXM_SIM_BREAK_AFTER")
                     .ok()
                     .and_then(|v| v.parse::<usize>().ok());
@@ -1867,49 +1502,72 @@ where
                     return sim_run_fast(
                         total,
                         level,
-                        seed,
+                        &schedule,
+                        &mut agents,
                         base_seed,
                         break_after,
                         per_hand_delay,
                         completed,
-                        path.as_ref().map(|p| p.as_path()),
+                        path.as_deref(),
+                        log_dir.as_deref(),
                         out,
                         err,
                     );
                 }
+                let mut logger = match open_sim_log(path.as_deref(), log_dir.as_deref()) {
+                    Ok(l) => l,
+                    Err(msg) => {
+                        let _ = ui::write_error(err, &msg);
+                        return 2;
+                    }
+                };
+                let interrupted = watch_interrupt();
+                let mut rejected = [0; 2];
                 for i in completed..total {
+                    let hand_level =
+                        schedule.level_at(level, i as u32, SIMULATED_HAND_TIME * i as u32);
+                    let hand_seed = derive_hand_seed(base_seed, i as u64);
                     // create a fresh engine per hand to avoid residual hole cards
-                    let mut e = Engine::new(Some(base_seed + i as u64), level);
-                    e.shuffle();
-                    let _ = e.deal_hand();
-                    if let Some(p) = &path {
-                        let mut f = std::fs::OpenOptions::new()
-                            .create(true)
-                            .append(true)
-                            .open(p)
-                            .unwrap();
-                        let hand_id = format!("19700101-{:06}", i + 1);
-                        let board = e.board().clone();
-                        let rec = serde_json::json!({
-                            "hand_id": hand_id,
-                            "seed": seed,
-                            "level": level,
-                            "actions": [],
-                            "board": board,
-                            "result": null,
-                            "ts": null,
-                            "meta": null
-                        });
-                        let _ = writeln!(f, "{}", serde_json::to_string(&rec).unwrap());
+                    let mut e =
+                        Engine::with_schedule(Some(hand_seed), hand_level, schedule.clone());
+                    // reseed per hand so a resumed run replays identically
+                    reseed_agents(&mut agents, hand_seed);
+                    match play_agent_hand(&mut e, &mut agents) {
+                        Ok(n) => {
+                            rejected[0] += n[0];
+                            rejected[1] += n[1];
+                        }
+                        Err(e) => {
+                            let _ = ui::write_error(
+                                err,
+                                &format!("Failed to play hand {}: {}", i + 1, e),
+                            );
+                            return 2;
+                        }
+                    }
+                    if let Some(l) = logger.as_mut() {
+                        let hand_id = l.next_id();
+                        if l.write(&sim_record(&e, hand_id, hand_seed)).is_err() {
+                            let _ = ui::write_error(err, "Failed to write simulation output");
+                            return 2;
+                        }
                     }
                     completed += 1;
-                    if let Some(b) = break_after {
-                        if completed == b {
-                            let _ = writeln!(out, "Interrupted: saved {}/{}", completed, total);
-                            return 130;
+                    if break_after == Some(completed) || interrupted.load(Ordering::SeqCst) {
+                        // finish the compressed frame so the file can be resumed
+                        if logger.as_mut().is_some_and(|l| l.flush().is_err()) {
+                            let _ = ui::write_error(err, "Failed to flush simulation output");
+                            return 2;
                         }
+                        let _ = writeln!(out, "Interrupted: saved {}/{}", completed, total);
+                        return 130;
                     }
                 }
+                if logger.as_mut().is_some_and(|l| l.flush().is_err()) {
+                    let _ = ui::write_error(err, "Failed to flush simulation output");
+                    return 2;
+                }
+                report_faults(&agents, rejected, err);
                 let _ = writeln!(out, "Simulated: {} hands", completed);
                 0
             }
@@ -1918,10 +1576,10 @@ where
                 format,
                 output,
             } => {
-                let content = match std::fs::read_to_string(&input) {
-                    Ok(c) => c,
+                let reader = match RecordReader::open(&input) {
+                    Ok(r) => r,
                     Err(e) => {
-                        let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
+                        let _ = ui::write_error(err, &e.to_string());
                         return 2;
                     }
                 };
@@ -1938,9 +1596,14 @@ where
                             })
                             .unwrap();
                         let _ = writeln!(w, "hand_id,seed,result,ts,actions,board");
-                        for line in content.lines().filter(|l| !l.trim().is_empty()) {
-                            let rec: axm_engine::logger::HandRecord =
-                                serde_json::from_str(line).unwrap();
+                        for line in reader {
+                            let rec = match line.and_then(|l| l.parse()) {
+                                Ok(rec) => rec,
+                                Err(e) => {
+                                    let _ = ui::write_error(err, &e.to_string());
+                                    return 2;
+                                }
+                            };
                             let seed = rec.seed.map(|v| v.to_string()).unwrap_or_else(|| "".into());
                             let result = rec.result.unwrap_or_default();
                             let ts = rec.ts.unwrap_or_default();
@@ -1957,17 +1620,14 @@ where
                         }
                         0
                     }
-                    f if f.eq_ignore_ascii_case("json") => {
-                        let mut arr = Vec::new();
-                        for line in content.lines().filter(|l| !l.trim().is_empty()) {
-                            let v: serde_json::Value = serde_json::from_str(line).unwrap();
-                            arr.push(v);
+                    f if f.eq_ignore_ascii_case("json") => match export_json(reader, &output) {
+                        Ok(()) => 0,
+                        Err(msg) => {
+                            let _ = ui::write_error(err, &msg);
+                            2
                         }
-                        let s = serde_json::to_string_pretty(&arr).unwrap();
-                        std::fs::write(&output, s).unwrap();
-                        0
-                    }
-                    f if f.eq_ignore_ascii_case("sqlite") => export_sqlite(&content, &output, err),
+                    },
+                    f if f.eq_ignore_ascii_case("sqlite") => export_sqlite(&input, &output, err),
                     _ => {
                         let _ = ui::write_error(err, "Unsupported format");
                         2
@@ -1987,17 +1647,23 @@ where
                 {
                     return code;
                 }
-                let content = std::fs::read_to_string(&input)
-                    .map_err(|e| {
-                        let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
-                        e
-                    })
-                    .unwrap();
-                let mut lines: Vec<String> = content
-                    .lines()
-                    .filter(|l| !l.trim().is_empty())
-                    .map(|s| s.to_string())
-                    .collect();
+                let reader = match RecordReader::open(&input) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &e.to_string());
+                        return 2;
+                    }
+                };
+                let mut lines: Vec<String> = Vec::new();
+                for line in reader {
+                    match line.and_then(|l| l.parse().map(|_| l)) {
+                        Ok(l) => lines.push(l.text),
+                        Err(e) => {
+                            let _ = ui::write_error(err, &e.to_string());
+                            return 2;
+                        }
+                    }
+                }
                 let n = lines.len();
                 if n == 0 {
                     let _ = ui::write_error(err, "Empty input");
@@ -2023,17 +1689,6 @@ where
                 let n_tr = ((tr * n as f64).round() as usize).min(n);
                 let n_va = ((va * n as f64).round() as usize).min(n.saturating_sub(n_tr));
                 let _n_te = n.saturating_sub(n_tr + n_va);
-                for (idx, raw) in lines.iter().enumerate() {
-                    let trimmed = raw.trim();
-                    if let Err(e) = serde_json::from_str::<axm_engine::logger::HandRecord>(trimmed)
-                    {
-                        let _ = ui::write_error(
-                            err,
-                            &format!("Invalid record at line {}: {}", idx + 1, e),
-                        );
-                        return 2;
-                    }
-                }
                 let (trv, rest) = lines.split_at(n_tr);
                 let (vav, tev) = rest.split_at(n_va);
                 std::fs::create_dir_all(&outdir).unwrap();
@@ -2052,52 +1707,50 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn sim_run_fast(
     total: usize,
     level: u8,
-    seed: Option<u64>,
+    schedule: &BlindSchedule,
+    agents: &mut [Box<dyn Agent>; 2],
     base_seed: u64,
     break_after: Option<usize>,
     per_hand_delay: Option<std::time::Duration>,
     mut completed: usize,
     path: Option<&std::path::Path>,
+    log_dir: Option<&str>,
     out: &mut dyn Write,
     err: &mut dyn Write,
 ) -> i32 {
-    let mut writer = match path {
-        Some(p) => match std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(p)
-        {
-            Ok(file) => Some(std::io::BufWriter::new(file)),
-            Err(e) => {
-                let _ = ui::write_error(err, &format!("Failed to open {}: {}", p.display(), e));
-                return 2;
-            }
-        },
-        None => None,
+    let mut writer = match open_sim_log(path, log_dir) {
+        Ok(w) => w,
+        Err(msg) => {
+            let _ = ui::write_error(err, &msg);
+            return 2;
+        }
     };
+    let interrupted = watch_interrupt();
 
+    let mut rejected = [0; 2];
     for i in completed..total {
-        let mut engine = Engine::new(Some(base_seed + i as u64), level);
-        engine.shuffle();
-        let _ = engine.deal_hand();
+        let hand_level = schedule.level_at(level, i as u32, SIMULATED_HAND_TIME * i as u32);
+        let hand_seed = derive_hand_seed(base_seed, i as u64);
+        let mut engine = Engine::with_schedule(Some(hand_seed), hand_level, schedule.clone());
+        reseed_agents(agents, hand_seed);
+        match play_agent_hand(&mut engine, agents) {
+            Ok(n) => {
+                rejected[0] += n[0];
+                rejected[1] += n[1];
+            }
+            Err(e) => {
+                let _ = ui::write_error(err, &format!("Failed to play hand {}: {}", i + 1, e));
+                return 2;
+            }
+        }
 
         if let Some(w) = writer.as_mut() {
-            let hand_id = format!("19700101-{:06}", i + 1);
-            let board = engine.board().clone();
-            let record = serde_json::json!({
-                "hand_id": hand_id,
-                "seed": seed,
-                "level": level,
-                "actions": [],
-                "board": board,
-                "result": null,
-                "ts": null,
-                "meta": null
-            });
-            if writeln!(w, "{}", serde_json::to_string(&record).unwrap()).is_err() {
+            let hand_id = w.next_id();
+            if w.write(&sim_record(&engine, hand_id, hand_seed)).is_err() {
                 let _ = ui::write_error(err, "Failed to write simulation output");
                 return 2;
             }
@@ -2109,17 +1762,15 @@ fn sim_run_fast(
             std::thread::sleep(delay);
         }
 
-        if let Some(b) = break_after {
-            if completed == b {
-                if let Some(w) = writer.as_mut() {
-                    if w.flush().is_err() {
-                        let _ = ui::write_error(err, "Failed to flush simulation output");
-                        return 2;
-                    }
+        if break_after == Some(completed) || interrupted.load(Ordering::SeqCst) {
+            if let Some(w) = writer.as_mut() {
+                if w.flush().is_err() {
+                    let _ = ui::write_error(err, "Failed to flush simulation output");
+                    return 2;
                 }
-                let _ = writeln!(out, "Interrupted: saved {}/{}", completed, total);
-                return 130;
             }
+            let _ = writeln!(out, "Interrupted: saved {}/{}", completed, total);
+            return 130;
         }
     }
 
@@ -2130,10 +1781,485 @@ fn sim_run_fast(
         }
     }
 
+    report_faults(agents, rejected, err);
     let _ = writeln!(out, "Simulated: {} hands", completed);
     0
 }
 
+fn run_repair(input: &str, out: &mut dyn Write, err: &mut dyn Write) -> i32 {
+    let path = std::path::Path::new(input);
+    if input.ends_with(".zst") {
+        let _ = ui::write_error(err, "repair works on plain .jsonl files");
+        return 2;
+    }
+    let report = match repair::repair(path) {
+        Ok(r) => r,
+        Err(e) => {
+            let _ = ui::write_error(err, &format!("Failed to repair {}: {}", input, e));
+            return 2;
+        }
+    };
+    if report.quarantined.is_empty() {
+        let _ = writeln!(out, "Repair: no damage found ({} records)", report.kept);
+        return 0;
+    }
+    for bad in &report.quarantined {
+        let _ = writeln!(out, "Quarantined line {}: {}", bad.line, bad.error);
+    }
+    let _ = writeln!(
+        out,
+        "Repair: kept {} records, quarantined {} lines to {}",
+        report.kept,
+        report.quarantined.len(),
+        repair::quarantine_path(path).display()
+    );
+    0
+}
+
+fn run_db(action: DbCommand, out: &mut dyn Write, err: &mut dyn Write) -> i32 {
+    match action {
+        DbCommand::Ingest { input, db } => {
+            let path = std::path::Path::new(&input);
+            if input == records::STDIN {
+                let _ = ui::write_error(err, "db ingest needs a file or directory");
+                return 2;
+            }
+            let files = if path.is_dir() {
+                match records::log_files(path) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        let _ = ui::write_error(err, &format!("Failed to read {}: {}", input, e));
+                        return 2;
+                    }
+                }
+            } else {
+                vec![path.to_path_buf()]
+            };
+            let mut store = match db::Store::open(std::path::Path::new(&db)) {
+                Ok(s) => s,
+                Err(msg) => {
+                    let _ = ui::write_error(err, &msg);
+                    return 2;
+                }
+            };
+            let mut hands = 0u64;
+            let mut unchanged = 0usize;
+            for file in &files {
+                // files before a failure stay committed
+                let ingested = match store.ingest_file(file) {
+                    Ok(i) => i,
+                    Err(msg) => {
+                        let _ = ui::write_error(err, &msg);
+                        return 2;
+                    }
+                };
+                if ingested.restarted {
+                    let _ = writeln!(
+                        err,
+                        "Warning: {} was rewritten since last ingest; read again from the start",
+                        file.display()
+                    );
+                }
+                if ingested.unchanged {
+                    unchanged += 1;
+                } else if ingested.hands > 0 {
+                    let _ = writeln!(
+                        out,
+                        "Ingested {} hands from {}",
+                        ingested.hands,
+                        file.display()
+                    );
+                }
+                hands += ingested.hands;
+            }
+            let total = store.hand_count().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "DB: ingested {} hands from {} file(s) ({} unchanged); {} hands in {}",
+                hands,
+                files.len(),
+                unchanged,
+                total,
+                db
+            );
+            0
+        }
+        DbCommand::Migrate { db } => {
+            let migrated =
+                db::Store::open_unmigrated(std::path::Path::new(&db)).and_then(|mut s| s.migrate());
+            match migrated {
+                Ok((from, to)) if from == to => {
+                    let _ = writeln!(out, "DB: schema version {} (up to date)", to);
+                    0
+                }
+                Ok((from, to)) => {
+                    let _ = writeln!(out, "DB: schema version {} -> {}", from, to);
+                    0
+                }
+                Err(msg) => {
+                    let _ = ui::write_error(err, &msg);
+                    2
+                }
+            }
+        }
+        DbCommand::Status { db } => {
+            let path = std::path::Path::new(&db);
+            if !path.exists() {
+                let _ = ui::write_error(err, &format!("No database at {}", db));
+                return 2;
+            }
+            let status = db::Store::open_unmigrated(path).and_then(|s| {
+                let version = s.version()?;
+                // a store before version 1 has no tables yet
+                if version == 0 {
+                    return Ok((version, 0, Vec::new()));
+                }
+                Ok((version, s.hand_count()?, s.files()?))
+            });
+            let (version, hands, files) = match status {
+                Ok(v) => v,
+                Err(msg) => {
+                    let _ = ui::write_error(err, &msg);
+                    return 2;
+                }
+            };
+            let _ = writeln!(
+                out,
+                "Schema version: {} (this build: {})",
+                version,
+                db::SCHEMA_VERSION
+            );
+            if version < db::SCHEMA_VERSION {
+                let _ = writeln!(out, "Pending migrations: run `axm db migrate`");
+            }
+            let _ = writeln!(out, "Hands: {}", hands);
+            let _ = writeln!(out, "Files: {}", files.len());
+            for f in files {
+                let _ = writeln!(
+                    out,
+                    "  {}: {} hands, {} lines, offset {}",
+                    f.path, f.hands, f.lines, f.offset
+                );
+            }
+            0
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_bench(
+    names: &[String],
+    iterations: u32,
+    warmup: u32,
+    output: Option<&str>,
+    baseline: Option<&str>,
+    threshold: f64,
+    list: bool,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> i32 {
+    if list {
+        for s in bench::SCENARIOS {
+            let slow = if s.slow { " (slow)" } else { "" };
+            let _ = writeln!(out, "{}  {}{}", s.name, s.about, slow);
+        }
+        return 0;
+    }
+    let mut selected = Vec::new();
+    for name in names {
+        match bench::find(name) {
+            Some(s) => selected.push(s),
+            None => {
+                let _ = ui::write_error(err, &format!("Unknown scenario: {}", name));
+                return 2;
+            }
+        }
+    }
+    if selected.is_empty() {
+        selected = bench::SCENARIOS.iter().filter(|s| !s.slow).collect();
+    }
+    let baseline: Option<bench::BenchReport> = match baseline {
+        Some(path) => match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()))
+        {
+            Ok(b) => Some(b),
+            Err(e) => {
+                let _ = ui::write_error(err, &format!("Failed to read baseline {}: {}", path, e));
+                return 2;
+            }
+        },
+        None => None,
+    };
+    let fixture = match bench::Fixture::new() {
+        Ok(f) => f,
+        Err(e) => {
+            let _ = ui::write_error(err, &format!("Failed to prepare benchmarks: {}", e));
+            return 2;
+        }
+    };
+
+    let mut report = bench::BenchReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        scenarios: Vec::new(),
+    };
+    for scenario in selected {
+        let r = bench::measure(scenario, &fixture, warmup, iterations);
+        let _ = writeln!(
+            out,
+            "Benchmark: {} median={:?} p95={:?} ops={} ops/s={:.0}",
+            r.name,
+            std::time::Duration::from_nanos(r.median_ns),
+            std::time::Duration::from_nanos(r.p95_ns),
+            r.ops,
+            r.ops_per_sec
+        );
+        report.scenarios.push(r);
+    }
+
+    if let Some(path) = output {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        if let Err(e) = std::fs::write(path, json + "\n") {
+            let _ = ui::write_error(err, &format!("Failed to write {}: {}", path, e));
+            return 2;
+        }
+    }
+    let mut regressed = false;
+    if let Some(base) = baseline {
+        for (name, change) in bench::compare(&report, &base) {
+            let _ = writeln!(out, "Baseline: {} {:+.1}%", name, change);
+            if change > threshold {
+                regressed = true;
+                let _ = ui::write_error(
+                    err,
+                    &format!(
+                        "Regression: {} is {:.1}% slower than baseline (threshold {}%)",
+                        name, change, threshold
+                    ),
+                );
+            }
+        }
+    }
+    if regressed {
+        2
+    } else {
+        0
+    }
+}
+
+fn run_range_equity(
+    ranges: &[String],
+    board: &[Card],
+    dead: &[Card],
+    opts: &EquityOptions,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> i32 {
+    let parsed: Result<Vec<Range>, _> = ranges.iter().map(|r| r.parse()).collect();
+    let res = parsed.and_then(|parsed| range_equity(&parsed, board, dead, opts));
+    let res = match res {
+        Ok(r) => r,
+        Err(e) => {
+            let _ = ui::write_error(err, &e.to_string());
+            return 2;
+        }
+    };
+    if res.exact {
+        let _ = writeln!(out, "Equity: exact ({} matchups)", res.samples);
+    } else {
+        let _ = writeln!(
+            out,
+            "Equity: monte carlo ({} trials, seed={})",
+            res.samples, opts.seed
+        );
+    }
+    for (range, eq) in ranges.iter().zip(&res.ranges) {
+        let _ = writeln!(
+            out,
+            "{} win={:.2}% tie={:.2}% loss={:.2}% equity={:.2}%",
+            range, eq.win, eq.tie, eq.loss, eq.equity
+        );
+    }
+    0
+}
+
+// Exactly two cards, e.g. "AhKh".
+fn parse_hole(s: &str) -> Result<[Card; 2], CardParseError> {
+    match parse_cards(s)?[..] {
+        [a, b] => Ok([a, b]),
+        _ => Err(CardParseError::InvalidCard(s.to_string())),
+    }
+}
+
+fn bot_timeout(ms: Option<u64>) -> std::time::Duration {
+    ms.map(std::time::Duration::from_millis)
+        .unwrap_or(DEFAULT_TIMEOUT)
+}
+
+// Builds the agents for both seats; `cmd:<program args>` runs an external bot.
+fn create_agents(
+    registry: &AgentRegistry,
+    names: [&str; 2],
+    seed: u64,
+    timeout: std::time::Duration,
+) -> Result<[Box<dyn Agent>; 2], String> {
+    let make = |name: &str, seed: u64| -> Result<Box<dyn Agent>, String> {
+        if let Some(cmd) = name.strip_prefix("cmd:") {
+            return ProcessAgent::spawn(cmd, timeout)
+                .map(|a| Box::new(a) as Box<dyn Agent>)
+                .map_err(|e| format!("Failed to start bot {}: {}", cmd, e));
+        }
+        registry.create(name, seed).ok_or_else(|| {
+            format!(
+                "Unknown AI: {} (available: {})",
+                name,
+                registry.names().join(", ")
+            )
+        })
+    };
+    Ok([make(names[0], seed)?, make(names[1], seed.wrapping_add(1))?])
+}
+
+// Matches the seeds `create_agents` hands out.
+fn reseed_agents(agents: &mut [Box<dyn Agent>; 2], seed: u64) {
+    for (i, agent) in agents.iter_mut().enumerate() {
+        agent.reseed(seed.wrapping_add(i as u64));
+    }
+}
+
+// `rejected` counts actions the engine refused, per agent.
+fn report_faults(agents: &[Box<dyn Agent>; 2], rejected: [usize; 2], err: &mut dyn Write) {
+    for (agent, refused) in agents.iter().zip(rejected) {
+        let n = agent.fault_count() + refused;
+        if n > 0 {
+            let _ = writeln!(
+                err,
+                "Warning: {} had {} late or illegal replies (played as check/fold)",
+                agent.name(),
+                n
+            );
+        }
+    }
+}
+
+// Steps one hand through the engine with each seat played by its agent. An
+// action the engine rejects is played as a check, or a fold facing a bet, so
+// the hand always finishes; returns how many were replaced for each seat.
+fn play_agent_hand(
+    engine: &mut Engine,
+    agents: &mut [Box<dyn Agent>; 2],
+) -> Result<[usize; 2], GameError> {
+    engine.start_hand()?;
+    let mut rejected = [0; 2];
+    while let Some(seat) = engine.to_act() {
+        let Some(obs) = engine.observation(seat) else {
+            break;
+        };
+        let action = agents[seat].act(&obs);
+        let fallback = if obs.legal.check {
+            PlayerAction::Check
+        } else {
+            PlayerAction::Fold
+        };
+        if engine.apply_action(seat, action).is_err() {
+            rejected[seat] += 1;
+            engine.apply_action(seat, fallback)?;
+        }
+    }
+    Ok(rejected)
+}
+
+fn action_label(action: &PlayerAction) -> String {
+    match action {
+        PlayerAction::Fold => "fold".to_string(),
+        PlayerAction::Check => "check".to_string(),
+        PlayerAction::Call => "call".to_string(),
+        PlayerAction::Bet(x) => format!("bet {}", x),
+        PlayerAction::Raise(x) => format!("raise {}", x),
+        PlayerAction::AllIn => "all-in".to_string(),
+    }
+}
+
+fn load_schedule(path: Option<&str>) -> Result<BlindSchedule, ScheduleError> {
+    match path {
+        Some(p) => BlindSchedule::load(p),
+        None => Ok(BlindSchedule::default()),
+    }
+}
+
+// Appends to `path` (`.jsonl.zst` is written compressed) or logs to daily
+// files under `log_dir`; either way ids come from the clock and continue
+// the day's sequence. Appended records carry no `ts` so runs with the same
+// seed on the same day produce identical files.
+fn open_sim_log(
+    path: Option<&std::path::Path>,
+    log_dir: Option<&str>,
+) -> Result<Option<HandLogger>, String> {
+    if let Some(dir) = log_dir {
+        return open_daily_log(dir).map(Some);
+    }
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let mut logger = HandLogger::append(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    logger.set_timestamps(false);
+    Ok(Some(logger))
+}
+
+fn open_daily_log(dir: &str) -> Result<HandLogger, String> {
+    HandLogger::daily(dir, Rotation::default())
+        .map_err(|e| format!("Failed to open {}: {}", dir, e))
+}
+
+// Writes `reader`'s records as one pretty-printed JSON array, a record at
+// a time, in the same layout as `serde_json::to_string_pretty`.
+fn export_json(reader: RecordReader, output: &str) -> Result<(), String> {
+    let write_err = |e: std::io::Error| format!("Failed to write {}: {}", output, e);
+    let file = std::fs::File::create(output).map_err(write_err)?;
+    let mut w = std::io::BufWriter::new(file);
+    let mut first = true;
+    for line in reader {
+        let line = line.map_err(|e| e.to_string())?;
+        let value: serde_json::Value = serde_json::from_str(&line.text)
+            .map_err(|e| format!("Invalid record at {}:{}: {}", line.source, line.line, e))?;
+        let pretty = serde_json::to_string_pretty(&value).expect("serialize");
+        w.write_all(if first { b"[\n  " } else { b",\n  " })
+            .map_err(write_err)?;
+        w.write_all(pretty.replace('\n', "\n  ").as_bytes())
+            .map_err(write_err)?;
+        first = false;
+    }
+    w.write_all(if first { b"[]" } else { b"\n]" })
+        .map_err(write_err)?;
+    w.flush().map_err(write_err)
+}
+
+// Set once Ctrl-C is pressed. The handler is installed on first use and
+// stays for the life of the process.
+fn watch_interrupt() -> &'static AtomicBool {
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        // without a handler Ctrl-C just kills the process as before
+        let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst));
+    });
+    &INTERRUPTED
+}
+
+fn sim_record(engine: &Engine, hand_id: String, hand_seed: u64) -> HandRecord {
+    let mut record = engine
+        .hand_record(hand_id.clone())
+        .unwrap_or_else(|| HandRecord {
+            hand_id,
+            ..Default::default()
+        });
+    record.seed = Some(hand_seed);
+    // every hand starts from fresh stacks, so none continues another
+    record.session = Some(record.hand_id.clone());
+    record
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "axm",
@@ -2147,6 +2273,27 @@ struct AxmCli {
     cmd: Commands,
 }
 
+#[derive(Subcommand, Debug)]
+enum DbCommand {
+    /// Add new hands from a JSONL file or directory
+    Ingest {
+        #[arg(long)]
+        input: String,
+        #[arg(long, default_value = db::DEFAULT_PATH)]
+        db: String,
+    },
+    /// Apply pending schema migrations
+    Migrate {
+        #[arg(long, default_value = db::DEFAULT_PATH)]
+        db: String,
+    },
+    /// Show schema version and ingested files
+    Status {
+        #[arg(long, default_value = db::DEFAULT_PATH)]
+        db: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum Commands {
     Play {
@@ -2158,6 +2305,15 @@ enum Commands {
         seed: Option<u64>,
         #[arg(long)]
         level: Option<u8>,
+        /// TOML blind schedule; defaults to the GAME_RULES structure
+        #[arg(long)]
+        blinds: Option<String>,
+        /// Agent for the AI seat with --vs ai
+        #[arg(long)]
+        ai: Option<String>,
+        /// Log hands to daily files under this directory (e.g. data/hands)
+        #[arg(long = "log-dir")]
+        log_dir: Option<String>,
     },
     Replay {
         #[arg(long)]
@@ -2178,6 +2334,14 @@ enum Commands {
         hands: u32,
         #[arg(long)]
         seed: Option<u64>,
+        #[arg(long)]
+        level: Option<u8>,
+        /// TOML blind schedule; defaults to the GAME_RULES structure
+        #[arg(long)]
+        blinds: Option<String>,
+        /// Reply timeout for `cmd:` bots
+        #[arg(long = "ai-timeout-ms")]
+        ai_timeout_ms: Option<u64>,
     },
     Verify {
         #[arg(long)]
@@ -2187,7 +2351,58 @@ enum Commands {
         #[arg(long)]
         seed: Option<u64>,
     },
-    Bench,
+    Equity {
+        /// Hole cards per player, e.g. AhKh QsQd
+        #[arg(long, num_args = 2.., required = true)]
+        hands: Vec<String>,
+        #[arg(long)]
+        board: Option<String>,
+        /// Cards removed from the deck
+        #[arg(long)]
+        dead: Option<String>,
+        /// Monte Carlo trials when the runouts are too many to enumerate
+        #[arg(long)]
+        iterations: Option<u64>,
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    Bench {
+        /// Scenario to run; repeatable. Defaults to all but the slow ones
+        #[arg(long)]
+        scenario: Vec<String>,
+        #[arg(long)]
+        iterations: Option<u32>,
+        #[arg(long)]
+        warmup: Option<u32>,
+        /// Write the JSON report here
+        #[arg(long)]
+        output: Option<String>,
+        /// Earlier JSON report to compare medians against
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Median slowdown in percent counted as a regression
+        #[arg(long)]
+        threshold: Option<f64>,
+        #[arg(long)]
+        list: bool,
+    },
+    /// SQLite store of ingested hands
+    Db {
+        #[command(subcommand)]
+        action: DbCommand,
+    },
+    /// Rewrite a damaged JSONL file, quarantining bad lines
+    Repair {
+        #[arg(long)]
+        input: String,
+    },
+    Selftest {
+        /// Also enumerate all 133,784,560 seven-card hands
+        #[arg(long)]
+        seven: bool,
+        #[arg(long)]
+        threads: Option<usize>,
+    },
     Sim {
         #[arg(long)]
         hands: u64,
@@ -2199,6 +2414,18 @@ enum Commands {
         level: Option<u8>,
         #[arg(long)]
         resume: Option<String>,
+        /// Log hands to daily files under this directory (e.g. data/hands)
+        #[arg(long = "log-dir", conflicts_with_all = ["output", "resume"])]
+        log_dir: Option<String>,
+        /// TOML blind schedule; defaults to the GAME_RULES structure
+        #[arg(long)]
+        blinds: Option<String>,
+        /// Agent playing both seats
+        #[arg(long)]
+        ai: Option<String>,
+        /// Reply timeout for `cmd:` bots
+        #[arg(long = "ai-timeout-ms")]
+        ai_timeout_ms: Option<u64>,
     },
     Export {
         #[arg(long)]
@@ -2227,6 +2454,9 @@ enum Commands {
     Rng {
         #[arg(long)]
         seed: Option<u64>,
+        /// Treat --seed as a session seed and show this hand's deck
+        #[arg(long)]
+        hand: Option<u64>,
     },
 }
 
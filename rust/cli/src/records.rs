@@ -0,0 +1,292 @@
+//! Streaming hand history input shared by the commands that read logs.
+//!
+//! An input is a JSONL file, a `.zst` compressed file, a directory (every
+//! `.jsonl` and `.jsonl.zst` file below it except `repair`'s quarantine
+//! files, in path order) or `-` for stdin, which may also be zstd
+//! compressed. Lines are read one at a time so memory stays flat however
+//! large the log is. Blank lines are skipped,
+//! a leading UTF-8 BOM is dropped from each file, and every line keeps its
+//! file and 1-based line number for error messages.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use axm_engine::logger::{is_zstd, HandRecord};
+
+use crate::repair::QUARANTINE_SUFFIX;
+
+/// Input name that reads stdin.
+pub const STDIN: &str = "-";
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// One non-blank line of input.
+#[derive(Debug, Clone)]
+pub struct RecordLine {
+    /// File the line came from, or `<stdin>`.
+    pub source: Rc<str>,
+    /// 1-based line number within `source`.
+    pub line: usize,
+    pub text: String,
+    /// Byte offset just past the line in the (decompressed) file.
+    pub offset: u64,
+    /// The line is the last in its file and has no newline, as left by an
+    /// interrupted write.
+    pub torn: bool,
+}
+
+impl RecordLine {
+    pub fn parse(&self) -> Result<HandRecord, RecordError> {
+        serde_json::from_str(&self.text).map_err(|error| RecordError::Parse {
+            source: self.source.clone(),
+            line: self.line,
+            error,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum RecordError {
+    Io {
+        source: String,
+        error: std::io::Error,
+    },
+    Parse {
+        source: Rc<str>,
+        line: usize,
+        error: serde_json::Error,
+    },
+}
+
+impl RecordError {
+    /// Whether a parse error is a complete JSON value of the wrong shape
+    /// rather than broken JSON.
+    pub fn is_data(&self) -> bool {
+        matches!(self, RecordError::Parse { error, .. } if error.is_data())
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Io { source, error } => write!(f, "Failed to read {}: {}", source, error),
+            RecordError::Parse {
+                source,
+                line,
+                error,
+            } => write!(f, "Invalid record at {}:{}: {}", source, line, error),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+/// Iterates the non-blank lines of an input. An unreadable file in a
+/// directory yields an error and reading moves on to the next file.
+pub struct RecordReader {
+    pending: VecDeque<PathBuf>,
+    stdin: bool,
+    current: Option<Current>,
+    buf: Vec<u8>,
+}
+
+struct Current {
+    reader: Box<dyn BufRead>,
+    source: Rc<str>,
+    line: usize,
+    offset: u64,
+}
+
+impl RecordReader {
+    /// Opens `input`; a single file that cannot be opened is an error here
+    /// rather than on the first read.
+    pub fn open(input: &str) -> Result<Self, RecordError> {
+        let mut reader = Self {
+            pending: VecDeque::new(),
+            stdin: false,
+            current: None,
+            buf: Vec::new(),
+        };
+        let path = Path::new(input);
+        if input == STDIN {
+            reader.stdin = true;
+        } else if path.is_dir() {
+            let files = log_files(path).map_err(|error| RecordError::Io {
+                source: input.to_string(),
+                error,
+            })?;
+            reader.pending = files.into();
+        } else {
+            reader.current = Some(open_file(path)?);
+        }
+        Ok(reader)
+    }
+
+    /// Reads the single file `path` from byte `offset` of its
+    /// (decompressed) content, numbering lines after `line`. A compressed
+    /// file is decompressed up to `offset` and the bytes discarded.
+    pub fn open_at(path: &Path, offset: u64, line: usize) -> Result<Self, RecordError> {
+        let mut cur = open_file(path)?;
+        let skipped = std::io::copy(&mut (&mut cur.reader).take(offset), &mut std::io::sink())
+            .map_err(|error| RecordError::Io {
+                source: path.display().to_string(),
+                error,
+            })?;
+        if skipped < offset {
+            return Err(RecordError::Io {
+                source: path.display().to_string(),
+                error: std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!("shorter than offset {}", offset),
+                ),
+            });
+        }
+        cur.line = line;
+        cur.offset = offset;
+        Ok(Self {
+            pending: VecDeque::new(),
+            stdin: false,
+            current: Some(cur),
+            buf: Vec::new(),
+        })
+    }
+
+    fn advance(&mut self) -> Option<Result<(), RecordError>> {
+        if self.stdin {
+            self.stdin = false;
+            let mut stdin = BufReader::new(std::io::stdin());
+            let compressed = stdin
+                .fill_buf()
+                .map(|b| b.starts_with(&ZSTD_MAGIC))
+                .unwrap_or(false);
+            let reader: Box<dyn BufRead> = if compressed {
+                match zstd::stream::read::Decoder::with_buffer(stdin) {
+                    Ok(d) => Box::new(BufReader::new(d)),
+                    Err(error) => {
+                        return Some(Err(RecordError::Io {
+                            source: "<stdin>".into(),
+                            error,
+                        }))
+                    }
+                }
+            } else {
+                Box::new(stdin)
+            };
+            self.current = Some(Current {
+                reader,
+                source: "<stdin>".into(),
+                line: 0,
+                offset: 0,
+            });
+            return Some(Ok(()));
+        }
+        let path = self.pending.pop_front()?;
+        Some(open_file(&path).map(|c| self.current = Some(c)))
+    }
+}
+
+impl Iterator for RecordReader {
+    type Item = Result<RecordLine, RecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(cur) = self.current.as_mut() else {
+                match self.advance()? {
+                    Ok(()) => continue,
+                    Err(e) => return Some(Err(e)),
+                }
+            };
+            self.buf.clear();
+            match cur.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => {
+                    self.current = None;
+                    continue;
+                }
+                Ok(n) => cur.offset += n as u64,
+                Err(error) => {
+                    let source = cur.source.to_string();
+                    self.current = None;
+                    return Some(Err(RecordError::Io { source, error }));
+                }
+            }
+            cur.line += 1;
+            let torn = !self.buf.ends_with(b"\n");
+            let mut bytes = self.buf.as_slice();
+            if cur.line == 1 {
+                bytes = bytes.strip_prefix("\u{feff}".as_bytes()).unwrap_or(bytes);
+            }
+            let text = match std::str::from_utf8(bytes) {
+                Ok(t) => t.trim_end_matches(['\n', '\r']),
+                Err(e) => {
+                    return Some(Err(RecordError::Io {
+                        source: format!("{}:{}", cur.source, cur.line),
+                        error: std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+                    }))
+                }
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            return Some(Ok(RecordLine {
+                source: cur.source.clone(),
+                line: cur.line,
+                text: text.to_string(),
+                offset: cur.offset,
+                torn,
+            }));
+        }
+    }
+}
+
+fn open_file(path: &Path) -> Result<Current, RecordError> {
+    let io = |error| RecordError::Io {
+        source: path.display().to_string(),
+        error,
+    };
+    let file = File::open(path).map_err(io)?;
+    let reader: Box<dyn BufRead> = if is_zstd(path) {
+        Box::new(BufReader::new(
+            zstd::stream::read::Decoder::new(file).map_err(io)?,
+        ))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    Ok(Current {
+        reader,
+        source: path.display().to_string().into(),
+        line: 0,
+        offset: 0,
+    })
+}
+
+/// Every `.jsonl` and `.jsonl.zst` file below `dir`, in path order,
+/// leaving out quarantine files.
+pub fn log_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_logs(dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_logs(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_logs(&path, files)?;
+            continue;
+        }
+        let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        if name.ends_with(QUARANTINE_SUFFIX) {
+            // lines `repair` set aside, not hands
+            continue;
+        }
+        if name.ends_with(".jsonl") || name.ends_with(".jsonl.zst") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! `axm repair`: rewrites a damaged JSONL hand history.
+//!
+//! Lines that parse as a `HandRecord` are kept byte for byte. Everything
+//! else, including a torn final line without a newline, goes to a
+//! quarantine file next to the input with its line number and the parse
+//! error, so nothing is thrown away. The input is replaced atomically.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use axm_engine::logger::HandRecord;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Quarantined {
+    /// 1-based line number in the damaged file.
+    pub line: usize,
+    pub error: String,
+    pub raw: String,
+}
+
+#[derive(Debug, Default)]
+pub struct RepairReport {
+    pub kept: usize,
+    pub quarantined: Vec<Quarantined>,
+}
+
+/// Suffix of the file bad lines are moved to. Directory inputs skip files
+/// ending in it.
+pub const QUARANTINE_SUFFIX: &str = ".quarantine.jsonl";
+
+/// `<input>.quarantine.jsonl`
+pub fn quarantine_path(input: &Path) -> PathBuf {
+    let mut name = input.as_os_str().to_os_string();
+    name.push(QUARANTINE_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Why `line` cannot be kept, or `None` if it is a valid record. `ended`
+/// says whether the line had its newline.
+fn check(line: &[u8], ended: bool) -> Option<String> {
+    if !ended {
+        return Some("torn final line (no newline)".to_string());
+    }
+    match std::str::from_utf8(line) {
+        Err(e) => Some(e.to_string()),
+        Ok(s) => serde_json::from_str::<HandRecord>(s.trim_start_matches('\u{feff}'))
+            .err()
+            .map(|e| e.to_string()),
+    }
+}
+
+// Calls `f` with the 1-based number, the bytes without the newline and
+// whether the newline was there for each non-blank line of `input`, one
+// line in memory at a time.
+fn for_each_line(
+    input: &Path,
+    mut f: impl FnMut(usize, &[u8], bool) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(File::open(input)?);
+    let mut buf = Vec::new();
+    let mut number = 0;
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let ended = buf.ends_with(b"\n");
+        if ended {
+            buf.pop();
+        }
+        if String::from_utf8_lossy(&buf).trim().is_empty() {
+            continue;
+        }
+        f(number, &buf, ended)?;
+    }
+}
+
+/// Splits `input`'s lines into kept and quarantined ones without
+/// changing anything.
+pub fn scan(input: &Path) -> std::io::Result<RepairReport> {
+    let mut report = RepairReport::default();
+    for_each_line(input, |line, bytes, ended| {
+        match check(bytes, ended) {
+            None => report.kept += 1,
+            Some(error) => report.quarantined.push(Quarantined {
+                line,
+                error,
+                raw: String::from_utf8_lossy(bytes).into_owned(),
+            }),
+        }
+        Ok(())
+    })?;
+    Ok(report)
+}
+
+/// Repairs `input` in place and appends bad lines to its quarantine file.
+/// An undamaged file is left untouched. The file is streamed twice, once
+/// to find the damage and once to copy the good lines, so memory does not
+/// grow with its size.
+pub fn repair(input: &Path) -> std::io::Result<RepairReport> {
+    let report = scan(input)?;
+    if report.quarantined.is_empty() {
+        return Ok(report);
+    }
+    let mut q = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(quarantine_path(input))?;
+    for bad in &report.quarantined {
+        writeln!(q, "{}", serde_json::to_string(bad).expect("serialize"))?;
+    }
+    q.sync_data()?;
+
+    let bad: HashSet<usize> = report.quarantined.iter().map(|q| q.line).collect();
+    let mut tmp = input.as_os_str().to_os_string();
+    tmp.push(".repair.tmp");
+    let tmp = PathBuf::from(tmp);
+    {
+        let mut w = BufWriter::new(File::create(&tmp)?);
+        for_each_line(input, |line, bytes, _| {
+            if !bad.contains(&line) {
+                w.write_all(bytes)?;
+                w.write_all(b"\n")?;
+            }
+            Ok(())
+        })?;
+        w.flush()?;
+        w.get_ref().sync_data()?;
+    }
+    std::fs::rename(&tmp, input)?;
+    Ok(report)
+}
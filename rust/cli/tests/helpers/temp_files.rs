@@ -256,12 +256,14 @@ mod tests {
                 player_id: 0,
                 street: Street::Preflop,
                 action: PlayerAction::Fold,
+                amount: None,
             }],
             board: Vec::new(),
             result: Some("fold".to_string()),
             ts: None,
             meta: None,
             showdown: None,
+            ..Default::default()
         }
     }
 
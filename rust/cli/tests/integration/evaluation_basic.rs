@@ -16,7 +16,15 @@ fn e1_eval_requires_hands_and_models() {
 #[test]
 fn e2_eval_warns_on_identical_models() {
     let cli = CliRunner::new().unwrap();
-    let res = cli.run(&["eval", "--ai-a", "same", "--ai-b", "same", "--hands", "4"]);
+    let res = cli.run(&[
+        "eval",
+        "--ai-a",
+        "always-call",
+        "--ai-b",
+        "always-call",
+        "--hands",
+        "4",
+    ]);
     assert_eq!(res.exit_code, 0);
     assert!(
         res.stderr.to_lowercase().contains("identical"),
@@ -41,3 +49,15 @@ fn e3_eval_is_deterministic_with_seed() {
         "same seed should produce identical results"
     );
 }
+
+#[test]
+fn e4_eval_rejects_unknown_agent() {
+    let cli = CliRunner::new().unwrap();
+    let res = cli.run(&["eval", "--ai-a", "nope", "--ai-b", "rand", "--hands", "2"]);
+    assert_eq!(res.exit_code, 2);
+    assert!(
+        res.stderr.contains("Unknown AI: nope"),
+        "stderr: {}",
+        res.stderr
+    );
+}
@@ -46,3 +46,95 @@ fn c3_play_vs_human_requires_tty() {
         res.stderr
     );
 }
+
+fn record_line(id: u32) -> String {
+    format!(
+        "{{\"hand_id\":\"19700101-{:06}\",\"seed\":{},\"actions\":[],\"board\":[],\"result\":\"p0\",\"ts\":null,\"meta\":null}}\n",
+        id, id
+    )
+}
+
+#[test]
+fn c9_stats_reads_stdin_with_dash() {
+    let cli = CliRunner::new().expect("CliRunner init");
+    let input = format!("{}\n{}", record_line(1), record_line(2));
+    let res = cli.run_with_input(&["stats", "--input", "-"], &input);
+    assert_eq!(res.exit_code, 0, "stderr: {}", res.stderr);
+    assert!(
+        res.stdout.contains("\"hands\": 2"),
+        "stdout: {}",
+        res.stdout
+    );
+}
+
+#[test]
+fn c10_invalid_record_reports_file_and_line() {
+    let tfm = TempFileManager::new().expect("tfm");
+    let content = format!("{}\n{}not json\n", record_line(1), record_line(2));
+    let path = tfm.create_file("bad.jsonl", &content).expect("file");
+    let out = tfm.create_file("bad.csv", "").expect("csv");
+    let cli = CliRunner::new().expect("CliRunner init");
+    let location = format!("Invalid record at {}:4:", path.display());
+    for args in [
+        vec!["replay", "--input", path.to_str().unwrap()],
+        vec!["verify", "--input", path.to_str().unwrap()],
+        vec![
+            "export",
+            "--input",
+            path.to_str().unwrap(),
+            "--format",
+            "csv",
+            "--output",
+            out.to_str().unwrap(),
+        ],
+    ] {
+        let res = cli.run(&args);
+        assert_ne!(res.exit_code, 0, "{:?}", args);
+        assert!(res.stderr.contains(&location), "{:?}: {}", args, res.stderr);
+    }
+}
+
+#[test]
+fn c11_directory_mixes_plain_and_compressed_files() {
+    let tfm = TempFileManager::new().expect("tfm");
+    let dir = tfm.create_directory("mixed").expect("dir");
+    std::fs::write(dir.join("a.jsonl"), record_line(1)).unwrap();
+    let compressed = zstd::encode_all(
+        format!("{}{}", record_line(2), record_line(3)).as_bytes(),
+        0,
+    )
+    .unwrap();
+    std::fs::write(dir.join("b.jsonl.zst"), compressed).unwrap();
+    std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+    let cli = CliRunner::new().expect("CliRunner init");
+    let res = cli.run(&["replay", "--input", &dir.to_string_lossy()]);
+    assert_eq!(res.exit_code, 0, "stderr: {}", res.stderr);
+    assert!(res.stdout.contains("Replayed: 3 hands"), "{}", res.stdout);
+}
+
+#[test]
+fn c12_export_json_matches_pretty_array() {
+    let tfm = TempFileManager::new().expect("tfm");
+    let content = format!("{}{}", record_line(1), record_line(2));
+    let path = tfm.create_file("in.jsonl", &content).expect("file");
+    let out = tfm.create_file("out.json", "").expect("json");
+    let cli = CliRunner::new().expect("CliRunner init");
+    let res = cli.run(&[
+        "export",
+        "--input",
+        &path.to_string_lossy(),
+        "--format",
+        "json",
+        "--output",
+        &out.to_string_lossy(),
+    ]);
+    assert_eq!(res.exit_code, 0, "stderr: {}", res.stderr);
+    let values: Vec<serde_json::Value> = content
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(
+        std::fs::read_to_string(&out).unwrap(),
+        serde_json::to_string_pretty(&values).unwrap()
+    );
+}
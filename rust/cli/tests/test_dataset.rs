@@ -18,6 +18,7 @@ fn mk_jsonl(name: &str, n: usize) -> PathBuf {
             player_id: 0,
             street: Street::Preflop,
             action: A::Bet(10),
+            amount: None,
         }],
         board: vec![Card {
             suit: S::Clubs,
@@ -27,6 +28,7 @@ fn mk_jsonl(name: &str, n: usize) -> PathBuf {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
     let mut s = String::new();
     for i in 0..n {
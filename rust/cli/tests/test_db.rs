@@ -0,0 +1,269 @@
+use std::fs;
+use std::path::PathBuf;
+
+use axm_cli::run;
+use axm_engine::logger::HandRecord;
+
+fn tmp_dir(name: &str) -> PathBuf {
+    let mut p = PathBuf::from("target");
+    p.push(format!("db_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&p);
+    fs::create_dir_all(&p).unwrap();
+    p
+}
+
+fn axm(args: &[&str]) -> (i32, String, String) {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let mut argv = vec!["axm"];
+    argv.extend_from_slice(args);
+    let code = run(argv, &mut out, &mut err);
+    (
+        code,
+        String::from_utf8_lossy(&out).into_owned(),
+        String::from_utf8_lossy(&err).into_owned(),
+    )
+}
+
+fn count(db: &PathBuf, sql: &str) -> i64 {
+    let conn = rusqlite::Connection::open(db).unwrap();
+    conn.query_row(sql, [], |r| r.get(0)).unwrap()
+}
+
+fn record(id: u32) -> String {
+    serde_json::to_string(&HandRecord {
+        hand_id: format!("20250101-{:06}", id),
+        result: Some("p0".into()),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn ingest_normalizes_sim_output_and_only_reads_new_lines() {
+    let dir = tmp_dir("sim");
+    let log = dir.join("hands.jsonl");
+    let db = dir.join("db.sqlite");
+    let (log_s, db_s) = (log.to_str().unwrap(), db.to_str().unwrap());
+    axm(&["sim", "--hands", "4", "--seed", "3", "--output", log_s]);
+
+    let (code, out, err) = axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    assert_eq!(code, 0, "{}", err);
+    assert!(out.contains("Ingested 4 hands"), "{}", out);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 4);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM players"), 8);
+    let actions: usize = fs::read_to_string(&log)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<HandRecord>(l).unwrap().actions.len())
+        .sum();
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM actions"), actions as i64);
+    assert_eq!(count(&db, "SELECT MAX(version) FROM schema_version"), 2);
+    assert_eq!(count(&db, "SELECT SUM(hands) FROM player_stats"), 8);
+
+    let (_, out, _) = axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    assert!(
+        out.contains("ingested 0 hands from 1 file(s) (1 unchanged)"),
+        "{}",
+        out
+    );
+
+    axm(&["sim", "--hands", "6", "--seed", "3", "--resume", log_s]);
+    let (_, out, _) = axm(&[
+        "db",
+        "ingest",
+        "--input",
+        dir.to_str().unwrap(),
+        "--db",
+        db_s,
+    ]);
+    assert!(out.contains("Ingested 2 hands"), "{}", out);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 6);
+    assert_eq!(count(&db, "SELECT lines FROM ingested_files"), 6);
+
+    let (code, out, _) = axm(&["db", "status", "--db", db_s]);
+    assert_eq!(code, 0);
+    assert!(out.contains("Schema version: 2"), "{}", out);
+    assert!(out.contains("Hands: 6"), "{}", out);
+    assert!(out.contains("6 hands, 6 lines"), "{}", out);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn torn_tail_waits_and_rewrites_are_upserted() {
+    let dir = tmp_dir("torn");
+    let log = dir.join("hands.jsonl");
+    let db = dir.join("db.sqlite");
+    let (log_s, db_s) = (log.to_str().unwrap(), db.to_str().unwrap());
+    let third = record(3);
+    fs::write(
+        &log,
+        format!("{}\n{}\n{}", record(1), record(2), &third[..10]),
+    )
+    .unwrap();
+    let (_, out, _) = axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    assert!(out.contains("Ingested 2 hands"), "{}", out);
+
+    // the writer finishes the line
+    fs::write(&log, format!("{}\n{}\n{}\n", record(1), record(2), third)).unwrap();
+    let (_, out, _) = axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    assert!(out.contains("Ingested 1 hands"), "{}", out);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 3);
+
+    // rewritten shorter: read again from the start, replacing its hands
+    fs::write(&log, format!("{}\n", record(2))).unwrap();
+    let (code, out, err) = axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    assert_eq!(code, 0);
+    assert!(err.contains("read again from the start"), "{}", err);
+    assert!(out.contains("Ingested 1 hands"), "{}", out);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 1);
+    assert_eq!(count(&db, "SELECT hands FROM ingested_files"), 1);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn separate_runs_with_the_same_ids_are_kept_apart() {
+    let dir = tmp_dir("runs");
+    let db = dir.join("db.sqlite");
+    let logs = dir.join("logs");
+    for name in ["a.jsonl", "b.jsonl"] {
+        let log = logs.join(name);
+        axm(&[
+            "sim",
+            "--hands",
+            "30",
+            "--seed",
+            "5",
+            "--output",
+            log.to_str().unwrap(),
+        ]);
+    }
+    fs::write(
+        logs.join("a.jsonl.quarantine.jsonl"),
+        "{\"line\":2,\"error\":\"bad\",\"raw\":\"x\"}\n",
+    )
+    .unwrap();
+    let db_s = db.to_str().unwrap();
+    let (code, out, err) = axm(&[
+        "db",
+        "ingest",
+        "--input",
+        logs.to_str().unwrap(),
+        "--db",
+        db_s,
+    ]);
+    assert_eq!(code, 0, "{}", err);
+    assert!(out.contains("ingested 60 hands from 2 file(s)"), "{}", out);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 60);
+    assert_eq!(count(&db, "SELECT COUNT(DISTINCT hand_id) FROM hands"), 30);
+    assert_eq!(
+        count(&db, "SELECT COUNT(*) FROM player_stats WHERE ev IS NULL"),
+        0
+    );
+    assert_eq!(count(&db, "SELECT SUM(net) FROM player_stats"), 0);
+    let (_, out, _) = axm(&["db", "status", "--db", db_s]);
+    assert!(out.contains("Hands: 60"), "{}", out);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn rewrites_that_keep_or_grow_the_length_are_read_again() {
+    let dir = tmp_dir("rewrite");
+    let log = dir.join("hands.jsonl");
+    let db = dir.join("db.sqlite");
+    let (log_s, db_s) = (log.to_str().unwrap(), db.to_str().unwrap());
+    let ingest = || axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    fs::write(
+        &log,
+        format!("{}\n{}\n{}\n", record(1), record(2), record(3)),
+    )
+    .unwrap();
+    ingest();
+
+    // same length, different last line
+    fs::write(
+        &log,
+        format!("{}\n{}\n{}\n", record(1), record(2), record(4)),
+    )
+    .unwrap();
+    let (code, _, err) = ingest();
+    assert_eq!(code, 0, "{}", err);
+    assert!(err.contains("read again from the start"), "{}", err);
+    assert_eq!(
+        count(&db, "SELECT COUNT(*) FROM hands WHERE hand_id LIKE '%3'"),
+        0
+    );
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 3);
+
+    // a line dropped from the middle, then more appended than it held
+    let mut text = format!("{}\n{}\n", record(1), record(4));
+    for id in 5..8 {
+        text.push_str(&format!("{}\n", record(id)));
+    }
+    fs::write(&log, text).unwrap();
+    let (code, _, err) = ingest();
+    assert_eq!(code, 0, "{}", err);
+    assert!(err.contains("read again from the start"), "{}", err);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 5);
+    assert_eq!(count(&db, "SELECT hands FROM ingested_files"), 5);
+
+    let (_, out, _) = ingest();
+    assert!(out.contains("(1 unchanged)"), "{}", out);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn invalid_record_rolls_back_the_file() {
+    let dir = tmp_dir("invalid");
+    let log = dir.join("hands.jsonl");
+    let db = dir.join("db.sqlite");
+    let (log_s, db_s) = (log.to_str().unwrap(), db.to_str().unwrap());
+    fs::write(&log, format!("{}\nnot json\n", record(1))).unwrap();
+    let (code, _, err) = axm(&["db", "ingest", "--input", log_s, "--db", db_s]);
+    assert_eq!(code, 2);
+    assert!(err.contains(&format!("{}:2:", log_s)), "{}", err);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM hands"), 0);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM ingested_files"), 0);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn migrations_run_forward_and_newer_stores_are_refused() {
+    let dir = tmp_dir("migrate");
+    let db = dir.join("db.sqlite");
+    let db_s = db.to_str().unwrap();
+    let (code, out, _) = axm(&["db", "migrate", "--db", db_s]);
+    assert_eq!(code, 0);
+    assert!(out.contains("schema version 0 -> 2"), "{}", out);
+    let (_, out, _) = axm(&["db", "migrate", "--db", db_s]);
+    assert!(out.contains("schema version 2 (up to date)"), "{}", out);
+
+    // a store last written by a version 1 build
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.execute_batch("DROP VIEW player_stats; DELETE FROM schema_version WHERE version = 2;")
+        .unwrap();
+    let (_, out, _) = axm(&["db", "status", "--db", db_s]);
+    assert!(out.contains("Pending migrations"), "{}", out);
+    let (_, out, _) = axm(&["db", "migrate", "--db", db_s]);
+    assert!(out.contains("schema version 1 -> 2"), "{}", out);
+
+    conn.execute(
+        "INSERT INTO schema_version (version, applied_at) VALUES (99, 'later')",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+    let log = dir.join("hands.jsonl");
+    fs::write(&log, format!("{}\n", record(1))).unwrap();
+    let (code, _, err) = axm(&[
+        "db",
+        "ingest",
+        "--input",
+        log.to_str().unwrap(),
+        "--db",
+        db_s,
+    ]);
+    assert_eq!(code, 2);
+    assert!(err.contains("newer than this build"), "{}", err);
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -9,6 +9,13 @@ fn deal_prints_board_and_holes() {
     let s = String::from_utf8_lossy(&out);
     assert!(s.contains("Hole P1:"));
     assert!(s.contains("Board:"));
+    // cards are printed as rank + suit, e.g. "Ah"
+    let board = s.lines().find(|l| l.starts_with("Board:")).unwrap();
+    let cards: Vec<&str> = board["Board:".len()..].split_whitespace().collect();
+    assert_eq!(cards.len(), 5);
+    for card in cards {
+        assert!(card.parse::<axm_engine::cards::Card>().is_ok(), "{}", card);
+    }
 }
 
 #[test]
@@ -20,3 +27,90 @@ fn rng_prints_sample() {
     let s = String::from_utf8_lossy(&out);
     assert!(s.contains("RNG sample:"));
 }
+
+#[test]
+fn equity_reports_each_hand() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm", "equity", "--hands", "AhKh", "QsQd", "--board", "7h8h2c",
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("Equity: exact (990 runouts)"), "{}", s);
+    assert!(s.contains("AhKh win="), "{}", s);
+    assert!(s.contains("QsQd win="), "{}", s);
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        ["axm", "equity", "--hands", "AhKh", "AhQd"],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 2);
+    assert!(String::from_utf8_lossy(&err).contains("Ah is used more than once"));
+}
+
+#[test]
+fn equity_accepts_ranges() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "equity",
+            "--hands",
+            "QQ+",
+            "AKs,99:0.5",
+            "--board",
+            "7h8h2cKd",
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("Equity: exact ("), "{}", s);
+    assert!(s.contains("QQ+ win="), "{}", s);
+    assert!(s.contains("AKs,99:0.5 win="), "{}", s);
+}
+
+#[test]
+fn rng_prints_deck_matching_sim_commitments() {
+    let path = std::env::temp_dir().join(format!("axm_rng_audit_{}.jsonl", std::process::id()));
+    let path_s = path.to_string_lossy().to_string();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm", "sim", "--hands", "3", "--seed", "8", "--output", &path_s,
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    let content = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let third: serde_json::Value = serde_json::from_str(content.lines().nth(2).unwrap()).unwrap();
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        ["axm", "rng", "--seed", "8", "--hand", "2"],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    let s = String::from_utf8_lossy(&out);
+    let deck = s.lines().find(|l| l.starts_with("Deck: ")).unwrap();
+    assert_eq!(deck.split_whitespace().count(), 53);
+    let hand_seed = third["seed"].as_u64().unwrap();
+    assert!(s.contains(&format!("Hand seed: {} ", hand_seed)), "{}", s);
+    let commitment = third["deck_commitment"].as_str().unwrap();
+    assert!(s.contains(&format!("Commitment: {}", commitment)), "{}", s);
+}
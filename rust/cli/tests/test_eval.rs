@@ -17,3 +17,168 @@ fn eval_reports_summary_for_two_ais() {
     assert!(s.contains("A:"));
     assert!(s.contains("B:"));
 }
+
+#[test]
+fn duplicate_eval_of_identical_agents_is_zero_sum_per_deal() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "eval",
+            "--ai-a",
+            "always-call",
+            "--ai-b",
+            "always-call",
+            "--hands",
+            "20",
+            "--seed",
+            "5",
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("deals=10 (duplicate)"), "{}", s);
+    // the same deterministic bot on both sides of every deal cancels out
+    assert!(s.contains("bb/100=+0.00"), "{}", s);
+    assert!(!s.contains("-0.0"), "{}", s);
+    assert!(s.contains("se=0.00"), "{}", s);
+}
+
+#[test]
+fn eval_reports_rate_error_and_interval() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "eval",
+            "--ai-a",
+            "hand-strength",
+            "--ai-b",
+            "random",
+            "--hands",
+            "9",
+            "--seed",
+            "3",
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("deals=5"), "{}", s);
+    for field in ["bb/100=", "se=", "ci95=[", "showdown=", "non-showdown="] {
+        assert!(s.contains(field), "missing {}: {}", field, s);
+    }
+}
+
+#[test]
+fn eval_plays_external_process_bot() {
+    let path = std::env::temp_dir().join(format!("axm_eval_bot_{}.sh", std::process::id()));
+    std::fs::write(
+        &path,
+        concat!(
+            r#"while read line; do seq=${line#*\"seq\":}; seq=${seq%%,*}; "#,
+            r#"echo "{\"seq\":$seq,\"action\":\"Check\"}"; done"#,
+            "\n"
+        ),
+    )
+    .unwrap();
+    let bot = format!("cmd:sh {}", path.display());
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "eval",
+            "--ai-a",
+            &bot,
+            "--ai-b",
+            "always-call",
+            "--hands",
+            "4",
+            "--seed",
+            "1",
+        ],
+        &mut out,
+        &mut err,
+    );
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(code, 0, "stderr: {}", String::from_utf8_lossy(&err));
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("A: cmd:sh"), "{}", s);
+    // checking into a bet is illegal and is played as a fold
+    let e = String::from_utf8_lossy(&err);
+    assert!(e.contains("late or illegal replies"), "{}", e);
+}
+
+#[test]
+fn eval_plays_at_the_given_blind_level() {
+    let blinds = std::env::temp_dir().join(format!("axm_eval_blinds_{}.toml", std::process::id()));
+    std::fs::write(
+        &blinds,
+        "hands_per_level = 1000\nlevels = [\n  { sb = 50, bb = 100 },\n  { sb = 500, bb = 1000 },\n]\n",
+    )
+    .unwrap();
+    let eval_at = |level: &str| {
+        let mut out: Vec<u8> = Vec::new();
+        let mut err: Vec<u8> = Vec::new();
+        let code = run(
+            [
+                "axm",
+                "eval",
+                "--ai-a",
+                "hand-strength",
+                "--ai-b",
+                "random",
+                "--hands",
+                "10",
+                "--seed",
+                "4",
+                "--blinds",
+                blinds.to_string_lossy().as_ref(),
+                "--level",
+                level,
+            ],
+            &mut out,
+            &mut err,
+        );
+        assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+        String::from_utf8_lossy(&out).into_owned()
+    };
+    let low = eval_at("1");
+    let high = eval_at("2");
+    let _ = std::fs::remove_file(&blinds);
+    assert!(low.contains("level=1"), "{}", low);
+    assert!(high.contains("level=2"), "{}", high);
+    // the same deals at ten times the blinds are a different game
+    let rate = |s: &str| s.lines().find(|l| l.contains("bb/100=")).map(str::to_owned);
+    assert_ne!(rate(&low), rate(&high));
+}
+
+#[test]
+fn eval_rejects_an_unreadable_blind_schedule() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "eval",
+            "--ai-a",
+            "random",
+            "--ai-b",
+            "random",
+            "--hands",
+            "2",
+            "--blinds",
+            "/nonexistent/blinds.toml",
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 2);
+    assert!(out.is_empty());
+}
@@ -21,6 +21,7 @@ fn mk_jsonl(name: &str, n: usize) -> PathBuf {
             player_id: 0,
             street: Street::Preflop,
             action: A::Bet(10),
+            amount: None,
         }],
         board: vec![Card {
             suit: S::Clubs,
@@ -30,6 +31,7 @@ fn mk_jsonl(name: &str, n: usize) -> PathBuf {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
     let mut s = String::new();
     for i in 0..n {
@@ -213,3 +215,28 @@ fn export_rejects_unknown_format() {
     assert!(stderr.contains("Unsupported format"));
     assert!(!output.exists());
 }
+
+#[test]
+fn export_to_sqlite_refuses_a_db_store() {
+    let input = mk_jsonl("export_in_store", 2);
+    let store = input.with_extension("db.sqlite");
+    let _ = fs::remove_file(&store);
+    let (input_s, store_s) = (input.to_string_lossy(), store.to_string_lossy());
+    let mut out = Vec::new();
+    let mut err = Vec::new();
+    let ingest = ["axm", "db", "ingest", "--input", &input_s, "--db", &store_s];
+    assert_eq!(run(ingest, &mut out, &mut err), 0);
+    let export = [
+        "axm", "export", "--input", &input_s, "--format", "sqlite", "--output", &store_s,
+    ];
+    let mut err = Vec::new();
+    assert_eq!(run(export, &mut out, &mut err), 2);
+    assert!(String::from_utf8_lossy(&err).contains("axm db store"));
+    let conn = Connection::open(&store).unwrap();
+    let hands: i64 = conn
+        .query_row("SELECT COUNT(*) FROM hands", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(hands, 2);
+    let _ = fs::remove_file(&store);
+    let _ = fs::remove_file(&input);
+}
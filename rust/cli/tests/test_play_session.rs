@@ -16,3 +16,42 @@ fn ai_session_counts_and_level_printed() {
     assert!(stdout.contains("Level: 2"));
     assert!(stdout.contains("Session hands=3"));
 }
+
+#[test]
+fn ai_session_logs_hands_as_one_session() {
+    let root =
+        std::path::PathBuf::from("target").join(format!("play_daily_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let dir = root.to_string_lossy().to_string();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "play",
+            "--vs",
+            "ai",
+            "--hands",
+            "3",
+            "--seed",
+            "9",
+            "--log-dir",
+            &dir,
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    let day = std::fs::read_dir(&root).unwrap().next().unwrap().unwrap();
+    let text = std::fs::read_to_string(day.path().join("hands-0001.jsonl")).unwrap();
+    let records: Vec<axm_engine::logger::HandRecord> = text
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records.len(), 3);
+    let first = records[0].hand_id.clone();
+    assert!(records
+        .iter()
+        .all(|r| r.session.as_deref() == Some(first.as_str()) && r.ts.is_some()));
+    let _ = std::fs::remove_dir_all(&root);
+}
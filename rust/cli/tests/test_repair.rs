@@ -0,0 +1,159 @@
+use std::fs;
+use std::path::PathBuf;
+
+use axm_cli::run;
+use axm_engine::logger::HandRecord;
+
+fn tmp_jsonl(name: &str) -> PathBuf {
+    let mut p = PathBuf::from("target");
+    p.push(format!("{}_{}.jsonl", name, std::process::id()));
+    if let Some(parent) = p.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    p
+}
+
+fn record(id: &str) -> String {
+    serde_json::to_string(&HandRecord {
+        hand_id: id.into(),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn repair_keeps_valid_records_and_quarantines_the_rest() {
+    let path = tmp_jsonl("repair");
+    let quarantine = PathBuf::from(format!("{}.quarantine.jsonl", path.display()));
+    let _ = fs::remove_file(&quarantine);
+    let torn = record("20250101-000004");
+    fs::write(
+        &path,
+        format!(
+            "{}\nnot json\n{}\n{}",
+            record("20250101-000001"),
+            record("20250101-000003"),
+            &torn[..20]
+        ),
+    )
+    .unwrap();
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        ["axm", "repair", "--input", &path.to_string_lossy()],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    let stdout = String::from_utf8_lossy(&out);
+    assert!(stdout.contains("Quarantined line 2:"), "{}", stdout);
+    assert!(
+        stdout.contains("Quarantined line 4: torn final line"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("kept 2 records, quarantined 2 lines"),
+        "{}",
+        stdout
+    );
+
+    let kept = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        kept,
+        format!(
+            "{}\n{}\n",
+            record("20250101-000001"),
+            record("20250101-000003")
+        )
+    );
+    let bad: Vec<serde_json::Value> = fs::read_to_string(&quarantine)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(bad.len(), 2);
+    assert_eq!(bad[0]["line"], 2);
+    assert_eq!(bad[0]["raw"], "not json");
+    assert_eq!(bad[1]["line"], 4);
+
+    out.clear();
+    let code = run(
+        ["axm", "repair", "--input", &path.to_string_lossy()],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    assert!(String::from_utf8_lossy(&out).contains("no damage found (2 records)"));
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(&quarantine);
+}
+
+#[test]
+fn sim_resume_drops_a_torn_final_line() {
+    let path = tmp_jsonl("resume_torn");
+    let _ = fs::remove_file(&path);
+    let p = path.to_string_lossy().to_string();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        ["axm", "sim", "--hands", "3", "--seed", "2", "--output", &p],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    let mut content = fs::read_to_string(&path).unwrap();
+    content.truncate(content.len() - 15);
+    fs::write(&path, &content).unwrap();
+
+    out.clear();
+    err.clear();
+    let code = run(
+        ["axm", "sim", "--hands", "3", "--seed", "2", "--resume", &p],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    assert!(String::from_utf8_lossy(&err).contains("dropped torn final line"));
+    assert!(String::from_utf8_lossy(&out).contains("Resumed from 2"));
+    let ids: Vec<String> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<HandRecord>(l).unwrap().hand_id)
+        .collect();
+    assert_eq!(ids.len(), 3);
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn directory_inputs_skip_quarantine_files() {
+    let dir = PathBuf::from("target").join(format!("repair_dir_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("hands.jsonl");
+    let input = path.to_string_lossy().to_string();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let sim = [
+        "axm", "sim", "--hands", "3", "--seed", "2", "--output", &input,
+    ];
+    assert_eq!(run(sim, &mut out, &mut err), 0);
+    let mut text = fs::read_to_string(&path).unwrap();
+    text.push_str("not json\n");
+    fs::write(&path, text).unwrap();
+    assert_eq!(
+        run(["axm", "repair", "--input", &input], &mut out, &mut err),
+        0
+    );
+    assert!(dir.join("hands.jsonl.quarantine.jsonl").exists());
+
+    let dir_input = dir.to_string_lossy().to_string();
+    for cmd in ["stats", "verify"] {
+        let mut out: Vec<u8> = Vec::new();
+        let mut err: Vec<u8> = Vec::new();
+        let code = run(["axm", cmd, "--input", &dir_input], &mut out, &mut err);
+        assert_eq!(code, 0, "{}: {}", cmd, String::from_utf8_lossy(&err));
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -26,6 +26,7 @@ fn replay_counts_hands_and_prints_summary() {
             player_id: 0,
             street: Street::Preflop,
             action: A::Bet(50),
+            amount: None,
         }],
         board: vec![Card {
             suit: S::Clubs,
@@ -35,6 +36,7 @@ fn replay_counts_hands_and_prints_summary() {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
     let rec2 = HandRecord {
         hand_id: "20250102-000002".into(),
@@ -1,19 +1,56 @@
 use axm_cli::run;
 
+fn play(args: &[&str]) -> String {
+    let mut out = Vec::new();
+    let mut err = Vec::new();
+    let mut argv = vec!["axm", "play", "--vs", "ai", "--hands", "3", "--seed", "1"];
+    argv.extend_from_slice(args);
+    let code = run(argv, &mut out, &mut err);
+    assert_eq!(code, 0, "stderr: {}", String::from_utf8_lossy(&err));
+    String::from_utf8_lossy(&out).into_owned()
+}
+
 #[test]
 fn level_progresses_over_session_and_blinds_printed() {
+    let path = std::env::temp_dir().join(format!("axm_turbo_{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        "hands_per_level = 2\nlevels = [\n  { sb = 50, bb = 100 },\n  { sb = 100, bb = 200, ante = 25 },\n]\n",
+    )
+    .unwrap();
+    let s = play(&["--level", "1", "--blinds", &path.to_string_lossy()]);
+    let _ = std::fs::remove_file(&path);
+    assert!(s.contains("Level: 1"));
+    assert!(s.contains("Level: 2"));
+    assert!(s.contains("Blinds: SB=100 BB=200 Ante=25"));
+}
+
+#[test]
+fn default_schedule_holds_level_for_fifteen_hands() {
+    let s = play(&["--level", "1"]);
+    assert!(s.contains("Blinds: SB=50 BB=100"));
+    assert!(!s.contains("Level: 2"));
+}
+
+#[test]
+fn invalid_schedule_is_rejected() {
+    let path = std::env::temp_dir().join(format!("axm_bad_{}.toml", std::process::id()));
+    std::fs::write(&path, "levels = [ { sb = 50, bb = 100 } ]\n").unwrap();
     let mut out = Vec::new();
     let mut err = Vec::new();
     let code = run(
         [
-            "axm", "play", "--vs", "ai", "--hands", "3", "--level", "1", "--seed", "1",
+            "axm",
+            "play",
+            "--vs",
+            "ai",
+            "--blinds",
+            &path.to_string_lossy(),
         ],
         &mut out,
         &mut err,
     );
-    assert_eq!(code, 0);
-    let s = String::from_utf8_lossy(&out);
-    assert!(s.contains("Level: 1"));
-    assert!(s.contains("Level: 2"));
-    assert!(s.contains("Blinds:"));
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(code, 2);
+    assert!(String::from_utf8_lossy(&err).contains("hands_per_level"));
 }
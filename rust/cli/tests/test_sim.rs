@@ -39,3 +39,162 @@ fn sim_runs_n_hands_and_writes_file() {
     let lines = contents.lines().filter(|l| !l.trim().is_empty()).count();
     assert_eq!(lines, 5);
 }
+
+#[test]
+fn sim_writes_version_3_records() {
+    let path = out_path("sim_v2");
+    let _ = fs::remove_file(&path);
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "sim",
+            "--hands",
+            "2",
+            "--seed",
+            "4",
+            "--output",
+            path.to_string_lossy().as_ref(),
+        ],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0);
+    let contents = fs::read_to_string(&path).unwrap();
+    for line in contents.lines() {
+        let rec: axm_engine::logger::HandRecord = serde_json::from_str(line).unwrap();
+        assert_eq!(rec.schema_version, 3);
+        assert_eq!((rec.sb, rec.bb), (Some(50), Some(100)));
+        assert_eq!(rec.players.len(), 2);
+        assert_eq!(rec.players[0].hole_cards.len(), 2);
+        assert!(rec.actions.iter().all(|a| a.amount.is_some()));
+        assert!(rec.end_reason.is_some());
+        assert!(rec.players.iter().all(|p| p.stack_start == Some(20_000)));
+        assert_eq!(rec.net_result.as_ref().map(|n| n.values().sum()), Some(0));
+        // hands are independent deals, not one session
+        assert_eq!(rec.session.as_deref(), Some(rec.hand_id.as_str()));
+    }
+    let mut out: Vec<u8> = Vec::new();
+    let code = run(
+        ["axm", "verify", "--input", path.to_string_lossy().as_ref()],
+        &mut out,
+        &mut err,
+    );
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+}
+
+#[test]
+fn sim_time_levels_follow_hand_count() {
+    let path = out_path("sim_time_levels");
+    let _ = fs::remove_file(&path);
+    let blinds = out_path("sim_time_levels_blinds").with_extension("toml");
+    fs::write(
+        &blinds,
+        "minutes_per_level = 1\nlevels = [\n  { sb = 50, bb = 100 },\n  { sb = 100, bb = 200 },\n]\n",
+    )
+    .unwrap();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "sim",
+            "--hands",
+            "3",
+            "--seed",
+            "2",
+            "--blinds",
+            blinds.to_string_lossy().as_ref(),
+            "--output",
+            path.to_string_lossy().as_ref(),
+        ],
+        &mut out,
+        &mut err,
+    );
+    let _ = fs::remove_file(&blinds);
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    // each simulated hand counts as a minute, however fast it ran
+    let bbs: Vec<Option<u32>> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|l| {
+            serde_json::from_str::<axm_engine::logger::HandRecord>(l)
+                .unwrap()
+                .bb
+        })
+        .collect();
+    assert_eq!(bbs, [Some(100), Some(200), Some(200)]);
+}
+
+#[test]
+fn sim_resumes_a_compressed_output() {
+    let plain = out_path("sim_plain5");
+    let zst = out_path("sim_zst").with_extension("jsonl.zst");
+    let _ = fs::remove_file(&plain);
+    let _ = fs::remove_file(&zst);
+    let sim = |hands: &str, flag: &str, path: &PathBuf| {
+        let mut out: Vec<u8> = Vec::new();
+        let mut err: Vec<u8> = Vec::new();
+        let p = path.to_string_lossy().to_string();
+        let code = run(
+            ["axm", "sim", "--hands", hands, "--seed", "9", flag, &p],
+            &mut out,
+            &mut err,
+        );
+        assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+        String::from_utf8_lossy(&out).to_string()
+    };
+    sim("5", "--output", &plain);
+    sim("3", "--output", &zst);
+    let stdout = sim("5", "--resume", &zst);
+    assert!(stdout.contains("Resumed from 3"), "{}", stdout);
+
+    let decoded = zstd::stream::decode_all(fs::File::open(&zst).unwrap()).unwrap();
+    assert_eq!(
+        String::from_utf8(decoded).unwrap(),
+        fs::read_to_string(&plain).unwrap()
+    );
+    let _ = fs::remove_file(&plain);
+    let _ = fs::remove_file(&zst);
+}
+
+#[test]
+fn sim_log_dir_continues_the_day_across_runs() {
+    let root = PathBuf::from("target").join(format!("sim_daily_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let dir = root.to_string_lossy().to_string();
+    for _ in 0..2 {
+        let mut out: Vec<u8> = Vec::new();
+        let mut err: Vec<u8> = Vec::new();
+        let code = run(
+            [
+                "axm",
+                "sim",
+                "--hands",
+                "3",
+                "--seed",
+                "4",
+                "--log-dir",
+                &dir,
+            ],
+            &mut out,
+            &mut err,
+        );
+        assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    }
+    let day = fs::read_dir(&root).unwrap().next().unwrap().unwrap();
+    let date = day.file_name().to_string_lossy().to_string();
+    let text = fs::read_to_string(day.path().join("hands-0001.jsonl")).unwrap();
+    let ids: Vec<String> = text
+        .lines()
+        .map(|l| {
+            serde_json::from_str::<axm_engine::logger::HandRecord>(l)
+                .unwrap()
+                .hand_id
+        })
+        .collect();
+    let expected: Vec<String> = (1..=6).map(|n| format!("{}-{:06}", date, n)).collect();
+    assert_eq!(ids, expected);
+    let _ = fs::remove_dir_all(&root);
+}
@@ -29,6 +29,7 @@ fn verify_checks_records() {
             player_id: 0,
             street: Street::River,
             action: A::Check,
+            amount: None,
         }],
         board: vec![
             Card {
@@ -56,6 +57,7 @@ fn verify_checks_records() {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
     let mut s = String::new();
     s.push_str(&serde_json::to_string(&rec).unwrap());
@@ -75,13 +77,45 @@ fn verify_checks_records() {
     assert!(stdout.contains("hands=1"));
 }
 
+#[test]
+fn verify_accepts_hands_folded_before_the_river() {
+    let path = tmp_jsonl("verify_fold");
+    let rec = HandRecord {
+        hand_id: "19700101-000001".to_string(),
+        actions: vec![ActionRecord {
+            player_id: 0,
+            street: Street::Preflop,
+            action: A::Fold,
+            amount: None,
+        }],
+        end_reason: Some("fold".to_string()),
+        ..Default::default()
+    };
+    fs::write(&path, format!("{}\n", serde_json::to_string(&rec).unwrap())).unwrap();
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        ["axm", "verify", "--input", path.to_string_lossy().as_ref()],
+        &mut out,
+        &mut err,
+    );
+    let _ = fs::remove_file(&path);
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    assert!(String::from_utf8_lossy(&out).contains("Verify: OK"));
+}
+
 #[test]
 fn doctor_reports_ok() {
     let _guard = helpers::cli_runner::DOCTOR_LOCK
         .lock()
         .expect("doctor lock");
 
-    let base = PathBuf::from("target").join(format!("doctor_ok_{}", std::process::id()));
+    // the CLI runs inside its own temp dir, so hand it absolute paths
+    let base = std::env::current_dir()
+        .unwrap()
+        .join("target")
+        .join(format!("doctor_ok_{}", std::process::id()));
     let sqlite_dir = base.join("sqlite");
     let data_dir = base.join("data");
     fs::create_dir_all(&sqlite_dir).unwrap();
@@ -131,3 +165,87 @@ fn bench_runs_quickly() {
     let stdout = String::from_utf8_lossy(&out);
     assert!(stdout.contains("Benchmark:"));
 }
+
+#[test]
+fn bench_writes_report_and_flags_regressions() {
+    let dir = std::env::temp_dir().join(format!("axm_bench_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let report = dir.join("report.json");
+    let report_s = report.to_string_lossy().to_string();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let args = [
+        "axm",
+        "bench",
+        "--scenario",
+        "sim-hands",
+        "--iterations",
+        "3",
+        "--warmup",
+        "0",
+        "--output",
+        &report_s,
+    ];
+    assert_eq!(run(args, &mut out, &mut err), 0);
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    let sc = &json["scenarios"][0];
+    assert_eq!(sc["name"], "sim-hands");
+    assert_eq!(sc["iterations"], 3);
+    assert!(sc["median_ns"].as_u64().unwrap() <= sc["p95_ns"].as_u64().unwrap());
+
+    // a baseline that was impossibly fast counts as a regression
+    let mut fast = json.clone();
+    fast["scenarios"][0]["median_ns"] = serde_json::json!(1);
+    let baseline = dir.join("baseline.json");
+    std::fs::write(&baseline, fast.to_string()).unwrap();
+    let baseline_s = baseline.to_string_lossy().to_string();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(
+        [
+            "axm",
+            "bench",
+            "--scenario",
+            "sim-hands",
+            "--iterations",
+            "1",
+            "--baseline",
+            &baseline_s,
+        ],
+        &mut out,
+        &mut err,
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(code, 2);
+    assert!(String::from_utf8_lossy(&out).contains("Baseline: sim-hands +"));
+    assert!(String::from_utf8_lossy(&err).contains("Regression: sim-hands"));
+}
+
+#[test]
+fn bench_lists_and_rejects_unknown_scenarios() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    assert_eq!(run(["axm", "bench", "--list"], &mut out, &mut err), 0);
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("eval-all7") && s.contains("(slow)"), "{}", s);
+    assert!(s.contains("sqlite-export"), "{}", s);
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(["axm", "bench", "--scenario", "nope"], &mut out, &mut err);
+    assert_eq!(code, 2);
+    assert!(String::from_utf8_lossy(&err).contains("Unknown scenario: nope"));
+}
+
+#[test]
+fn selftest_checks_five_card_counts() {
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run(["axm", "selftest", "--threads", "2"], &mut out, &mut err);
+    assert_eq!(code, 0, "{}", String::from_utf8_lossy(&err));
+    let s = String::from_utf8_lossy(&out);
+    assert!(s.contains("Self-test: 5 cards, 2598960 hands"), "{}", s);
+    assert!(s.contains("StraightFlush 40 ok"), "{}", s);
+    assert!(s.contains("Self-test passed"), "{}", s);
+}
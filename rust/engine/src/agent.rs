@@ -0,0 +1,308 @@
+//! Bots that play a seat through the public engine API.
+//!
+//! An agent sees what a player at the table would see (its own hole cards,
+//! the board, stacks, the action so far and the legal action set) and
+//! answers with a `PlayerAction`.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::Serialize;
+
+use crate::cards::Card;
+use crate::engine::Engine;
+use crate::hand::{evaluate_hand, Category};
+use crate::logger::{ActionRecord, Street};
+use crate::player::PlayerAction;
+use crate::rules::{validate_action, AmountRange, LegalActions, MIN_CHIP_UNIT};
+
+/// One seat's view of the hand when it is asked to act.
+#[derive(Debug, Clone, Serialize)]
+pub struct Observation<'a> {
+    pub seat: usize,
+    pub hole_cards: [Card; 2],
+    pub board: &'a [Card],
+    pub street: Street,
+    pub button: usize,
+    pub blinds: (u32, u32),
+    pub stacks: [u32; 2],
+    pub pot: u32,
+    pub to_call: u32,
+    pub actions: &'a [ActionRecord],
+    pub legal: LegalActions,
+}
+
+impl Observation<'_> {
+    /// Whether `Engine::apply_action` would accept `action` from this seat;
+    /// it runs the same `validate_action` and `permits` checks, so an
+    /// oversized bet counts as an all-in.
+    pub fn allows(&self, action: &PlayerAction) -> bool {
+        // anything under the smallest legal raise fails `permits` anyway
+        let min_raise = self.legal.raise.map_or(0, |r| r.min);
+        validate_action(
+            self.stacks[self.seat],
+            self.to_call,
+            min_raise,
+            action.clone(),
+        )
+        .and_then(|v| self.legal.permits(&v))
+        .is_ok()
+    }
+}
+
+pub trait Agent {
+    fn name(&self) -> &str;
+    fn act(&mut self, obs: &Observation<'_>) -> PlayerAction;
+
+    /// Called before each hand by drivers that want every hand to replay
+    /// identically, e.g. a resumed `sim`.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Replies that had to be replaced because they were late or illegal.
+    fn fault_count(&self) -> usize {
+        0
+    }
+}
+
+impl Engine {
+    /// What `seat` sees right now, or `None` unless it is that seat's turn.
+    pub fn observation(&self, seat: usize) -> Option<Observation<'_>> {
+        if self.to_act() != Some(seat) {
+            return None;
+        }
+        let [Some(a), Some(b)] = self.players()[seat].hole_cards() else {
+            return None;
+        };
+        let players = self.players();
+        Some(Observation {
+            seat,
+            hole_cards: [a, b],
+            board: self.board(),
+            street: self.street()?,
+            button: self.button(),
+            blinds: self.blinds(),
+            stacks: [players[0].stack(), players[1].stack()],
+            pot: self.pot(),
+            to_call: self.to_call(seat),
+            actions: self.actions(),
+            legal: self.legal_actions()?,
+        })
+    }
+}
+
+/// Checks when it can and calls everything else.
+#[derive(Debug, Default)]
+pub struct AlwaysCall;
+
+impl Agent for AlwaysCall {
+    fn name(&self) -> &str {
+        "always-call"
+    }
+
+    fn act(&mut self, obs: &Observation<'_>) -> PlayerAction {
+        if obs.legal.check {
+            PlayerAction::Check
+        } else {
+            PlayerAction::Call
+        }
+    }
+}
+
+/// Picks uniformly among the legal action kinds, with a random size for
+/// bets and raises.
+#[derive(Debug)]
+pub struct RandomLegal {
+    rng: ChaCha20Rng,
+}
+
+impl RandomLegal {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+
+    fn amount(&mut self, range: AmountRange) -> u32 {
+        let lo = range.min / MIN_CHIP_UNIT;
+        let hi = (range.max / MIN_CHIP_UNIT).max(lo);
+        self.rng.gen_range(lo..=hi) * MIN_CHIP_UNIT
+    }
+}
+
+impl Agent for RandomLegal {
+    fn name(&self) -> &str {
+        "random-legal"
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = ChaCha20Rng::seed_from_u64(seed);
+    }
+
+    fn act(&mut self, obs: &Observation<'_>) -> PlayerAction {
+        let legal = &obs.legal;
+        let mut choices = Vec::with_capacity(6);
+        // folding with a free check is never better than checking
+        if legal.fold && !legal.check {
+            choices.push(PlayerAction::Fold);
+        }
+        if legal.check {
+            choices.push(PlayerAction::Check);
+        }
+        if legal.call.is_some() {
+            choices.push(PlayerAction::Call);
+        }
+        if let Some(range) = legal.bet {
+            choices.push(PlayerAction::Bet(self.amount(range)));
+        }
+        if let Some(range) = legal.raise {
+            choices.push(PlayerAction::Raise(self.amount(range)));
+        }
+        if legal.all_in.is_some() {
+            choices.push(PlayerAction::AllIn);
+        }
+        if choices.is_empty() {
+            return PlayerAction::Fold;
+        }
+        let idx = self.rng.gen_range(0..choices.len());
+        choices.swap_remove(idx)
+    }
+}
+
+/// Rates its hand from 0 to 1 and bets strong hands, calls medium ones and
+/// gives up on weak ones.
+#[derive(Debug, Default)]
+pub struct HandStrengthBot;
+
+impl HandStrengthBot {
+    /// Rough hand rating; exact on the river, rank matching before it.
+    pub fn strength(hole: [Card; 2], board: &[Card]) -> f64 {
+        let hi = hole[0].rank.max(hole[1].rank) as u8 as f64;
+        let lo = hole[0].rank.min(hole[1].rank) as u8 as f64;
+        if board.is_empty() {
+            if hole[0].rank == hole[1].rank {
+                return 0.5 + hi / 28.0;
+            }
+            let mut s = (hi + lo) / 28.0 * 0.8;
+            if hole[0].suit == hole[1].suit {
+                s += 0.05;
+            }
+            if hi - lo == 1.0 {
+                s += 0.03;
+            }
+            return s;
+        }
+        let category = if let Ok(board) = <&[Card; 5]>::try_from(board) {
+            let mut cards = [hole[0]; 7];
+            cards[1] = hole[1];
+            cards[2..].copy_from_slice(board);
+            evaluate_hand(&cards).category
+        } else {
+            let hits = |c: &Card| board.iter().filter(|b| b.rank == c.rank).count();
+            let pocket_pair = hole[0].rank == hole[1].rank;
+            match (pocket_pair, hits(&hole[0]), hits(&hole[1])) {
+                (true, 0, _) => Category::OnePair,
+                (true, _, _) => Category::ThreeOfAKind,
+                (false, a, b) if a >= 2 || b >= 2 => Category::ThreeOfAKind,
+                (false, 1, 1) => Category::TwoPair,
+                (false, 1, _) | (false, _, 1) => Category::OnePair,
+                _ => Category::HighCard,
+            }
+        };
+        match category {
+            Category::HighCard => 0.1 + hi / 140.0,
+            Category::OnePair => 0.45,
+            Category::TwoPair => 0.65,
+            Category::ThreeOfAKind => 0.75,
+            _ => 0.9,
+        }
+    }
+}
+
+impl Agent for HandStrengthBot {
+    fn name(&self) -> &str {
+        "hand-strength"
+    }
+
+    fn act(&mut self, obs: &Observation<'_>) -> PlayerAction {
+        let legal = &obs.legal;
+        let strength = Self::strength(obs.hole_cards, obs.board);
+        if strength >= 0.7 {
+            // half-pot sized, rounded to the chip unit and kept in range
+            let target = (obs.pot / 2 / MIN_CHIP_UNIT) * MIN_CHIP_UNIT;
+            let size = |range: AmountRange| {
+                let amount = target.clamp(range.min, range.max);
+                (amount < range.max).then_some(amount)
+            };
+            match (legal.bet, legal.raise) {
+                (Some(range), _) => {
+                    return size(range).map_or(PlayerAction::AllIn, PlayerAction::Bet)
+                }
+                (_, Some(range)) => {
+                    return size(range).map_or(PlayerAction::AllIn, PlayerAction::Raise)
+                }
+                _ => {}
+            }
+        }
+        if legal.check {
+            return PlayerAction::Check;
+        }
+        let cheap = obs.to_call * 4 <= obs.pot;
+        if strength >= 0.4 || (cheap && strength >= 0.25) {
+            PlayerAction::Call
+        } else {
+            PlayerAction::Fold
+        }
+    }
+}
+
+type AgentFactory = Box<dyn Fn(u64) -> Box<dyn Agent>>;
+
+/// Name → constructor table used by the CLI to build agents.
+pub struct AgentRegistry {
+    entries: Vec<(String, AgentFactory)>,
+}
+
+impl Default for AgentRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+impl AgentRegistry {
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// always-call, random-legal (alias: random, rand) and hand-strength.
+    pub fn with_builtins() -> Self {
+        let mut r = Self::empty();
+        r.register("always-call", |_| Box::new(AlwaysCall));
+        r.register("random-legal", |seed| Box::new(RandomLegal::new(seed)));
+        r.register("random", |seed| Box::new(RandomLegal::new(seed)));
+        r.register("rand", |seed| Box::new(RandomLegal::new(seed)));
+        r.register("hand-strength", |_| Box::new(HandStrengthBot));
+        r
+    }
+
+    /// Adds or replaces the constructor for `name`. The seed lets
+    /// randomised agents replay identically.
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(u64) -> Box<dyn Agent> + 'static,
+    {
+        self.entries.retain(|(n, _)| n != name);
+        self.entries.push((name.to_string(), Box::new(factory)));
+    }
+
+    pub fn create(&self, name: &str, seed: u64) -> Option<Box<dyn Agent>> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, factory)| factory(seed))
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(n, _)| n.as_str()).collect()
+    }
+}
@@ -0,0 +1,208 @@
+//! Blind level structures.
+//!
+//! A schedule is a list of levels plus a rule for when to move up. Schedules
+//! can be loaded from TOML:
+//!
+//! ```toml
+//! hands_per_level = 10      # or: minutes_per_level = 12
+//! big_blind_ante = true
+//! levels = [
+//!   { sb = 100, bb = 200, ante = 200 },
+//!   { sb = 200, bb = 400, ante = 400 },
+//! ]
+//! ```
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::rules::MIN_CHIP_UNIT;
+
+// (sb, bb) per level as listed in GAME_RULES.md; levels past 20 stay at L20.
+const GAME_RULES_LEVELS: [(u32, u32); 20] = [
+    (50, 100),
+    (75, 150),
+    (100, 200),
+    (125, 250),
+    (150, 300),
+    (200, 400),
+    (250, 500),
+    (300, 600),
+    (400, 800),
+    (500, 1000),
+    (600, 1200),
+    (800, 1600),
+    (1000, 2000),
+    (1200, 2400),
+    (1500, 3000),
+    (2000, 4000),
+    (2500, 5000),
+    (3000, 6000),
+    (3500, 7000),
+    (4000, 8000),
+];
+
+/// Hands per level in GAME_RULES.md.
+pub const GAME_RULES_HANDS_PER_LEVEL: u32 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindLevel {
+    pub sb: u32,
+    pub bb: u32,
+    #[serde(default)]
+    pub ante: u32,
+}
+
+/// When the schedule moves to the next level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelClock {
+    Hands(u32),
+    Time(Duration),
+}
+
+/// How long one hand counts for when a time clock runs against simulated
+/// play, so the level depends only on how many hands were dealt.
+pub const SIMULATED_HAND_TIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum ScheduleError {
+    #[error("Failed to read blind schedule: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid blind schedule: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Invalid blind schedule: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlindSchedule {
+    levels: Vec<BlindLevel>,
+    clock: LevelClock,
+    big_blind_ante: bool,
+}
+
+// On-disk shape; exactly one of the two clocks must be given.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleFile {
+    levels: Vec<BlindLevel>,
+    #[serde(default)]
+    hands_per_level: Option<u32>,
+    #[serde(default)]
+    minutes_per_level: Option<u32>,
+    #[serde(default)]
+    big_blind_ante: bool,
+}
+
+impl Default for BlindSchedule {
+    /// The GAME_RULES.md structure: 20 levels, 15 hands each, no antes.
+    fn default() -> Self {
+        let levels = GAME_RULES_LEVELS
+            .iter()
+            .map(|&(sb, bb)| BlindLevel { sb, bb, ante: 0 })
+            .collect();
+        Self {
+            levels,
+            clock: LevelClock::Hands(GAME_RULES_HANDS_PER_LEVEL),
+            big_blind_ante: false,
+        }
+    }
+}
+
+impl BlindSchedule {
+    pub fn new(
+        levels: Vec<BlindLevel>,
+        clock: LevelClock,
+        big_blind_ante: bool,
+    ) -> Result<Self, ScheduleError> {
+        let invalid = |msg: String| Err(ScheduleError::Invalid(msg));
+        if levels.is_empty() {
+            return invalid("at least one level is required".into());
+        }
+        if levels.len() > u8::MAX as usize {
+            return invalid(format!("at most {} levels are supported", u8::MAX));
+        }
+        match clock {
+            LevelClock::Hands(0) => return invalid("hands_per_level must be >= 1".into()),
+            LevelClock::Time(d) if d.is_zero() => {
+                return invalid("minutes_per_level must be >= 1".into())
+            }
+            _ => {}
+        }
+        for (i, l) in levels.iter().enumerate() {
+            let n = i + 1;
+            if l.sb == 0 || l.bb < l.sb {
+                return invalid(format!("level {}: need 0 < sb <= bb", n));
+            }
+            if [l.sb, l.bb, l.ante]
+                .iter()
+                .any(|x| !x.is_multiple_of(MIN_CHIP_UNIT))
+            {
+                return invalid(format!(
+                    "level {}: amounts must be multiples of {}",
+                    n, MIN_CHIP_UNIT
+                ));
+            }
+        }
+        Ok(Self {
+            levels,
+            clock,
+            big_blind_ante,
+        })
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, ScheduleError> {
+        let file: ScheduleFile = toml::from_str(s)?;
+        let clock = match (file.hands_per_level, file.minutes_per_level) {
+            (Some(h), None) => LevelClock::Hands(h),
+            (None, Some(m)) => LevelClock::Time(Duration::from_secs(u64::from(m) * 60)),
+            _ => {
+                return Err(ScheduleError::Invalid(
+                    "set exactly one of hands_per_level or minutes_per_level".into(),
+                ))
+            }
+        };
+        Self::new(file.levels, clock, file.big_blind_ante)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ScheduleError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn levels(&self) -> &[BlindLevel] {
+        &self.levels
+    }
+
+    pub fn clock(&self) -> LevelClock {
+        self.clock
+    }
+
+    /// Whether the big blind posts the whole table's ante instead of every
+    /// player posting their own.
+    pub fn big_blind_ante(&self) -> bool {
+        self.big_blind_ante
+    }
+
+    pub fn last_level(&self) -> u8 {
+        self.levels.len() as u8
+    }
+
+    /// Blinds for a 1-based level; levels past the end stay at the last one.
+    pub fn level(&self, level: u8) -> BlindLevel {
+        let idx = (level.max(1) as usize - 1).min(self.levels.len() - 1);
+        self.levels[idx]
+    }
+
+    /// Level in effect after `hands_played` hands and `elapsed` time, for a
+    /// session that started at `start_level`.
+    pub fn level_at(&self, start_level: u8, hands_played: u32, elapsed: Duration) -> u8 {
+        let steps = match self.clock {
+            LevelClock::Hands(per) => hands_played / per,
+            LevelClock::Time(per) => (elapsed.as_secs() / per.as_secs().max(1)) as u32,
+        };
+        let level = u32::from(start_level.max(1)).saturating_add(steps);
+        level.min(u32::from(self.last_level())) as u8
+    }
+}
@@ -1,4 +1,8 @@
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum Suit {
@@ -51,6 +55,177 @@ pub struct Card {
     pub rank: Rank,
 }
 
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CardParseError {
+    #[error("invalid rank {0:?} (expected one of 23456789TJQKA)")]
+    InvalidRank(char),
+    #[error("invalid suit {0:?} (expected one of cdhs)")]
+    InvalidSuit(char),
+    #[error("invalid card {0:?}")]
+    InvalidCard(String),
+}
+
+impl Rank {
+    /// `A K Q J T 9 … 2`
+    pub fn to_char(self) -> char {
+        b"23456789TJQKA"[self as usize - 2] as char
+    }
+
+    pub fn from_char(c: char) -> Result<Rank, CardParseError> {
+        match c.to_ascii_uppercase() {
+            d @ '2'..='9' => Ok(Rank::from_u8(d as u8 - b'0')),
+            'T' => Ok(Rank::Ten),
+            'J' => Ok(Rank::Jack),
+            'Q' => Ok(Rank::Queen),
+            'K' => Ok(Rank::King),
+            'A' => Ok(Rank::Ace),
+            _ => Err(CardParseError::InvalidRank(c)),
+        }
+    }
+}
+
+impl Suit {
+    /// `c d h s`
+    pub fn to_char(self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
+    pub fn from_char(c: char) -> Result<Suit, CardParseError> {
+        match c.to_ascii_lowercase() {
+            'c' => Ok(Suit::Clubs),
+            'd' => Ok(Suit::Diamonds),
+            'h' => Ok(Suit::Hearts),
+            's' => Ok(Suit::Spades),
+            _ => Err(CardParseError::InvalidSuit(c)),
+        }
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+impl FromStr for Rank {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = single_char(s).ok_or_else(|| CardParseError::InvalidCard(s.to_string()))?;
+        Rank::from_char(c)
+    }
+}
+
+impl FromStr for Suit {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = single_char(s).ok_or_else(|| CardParseError::InvalidCard(s.to_string()))?;
+        Suit::from_char(c)
+    }
+}
+
+/// `"Ah"`; the rank may be lower case and the suit upper case.
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(r), Some(su), None) => Ok(Card {
+                rank: Rank::from_char(r)?,
+                suit: Suit::from_char(su)?,
+            }),
+            _ => Err(CardParseError::InvalidCard(s.to_string())),
+        }
+    }
+}
+
+/// Parses a run of cards such as `"Td9c"`, `"Td 9c"` or `"Td,9c"`.
+/// Duplicates are not rejected here.
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, CardParseError> {
+    let chars: Vec<char> = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect();
+    if !chars.len().is_multiple_of(2) {
+        return Err(CardParseError::InvalidCard(s.trim().to_string()));
+    }
+    chars
+        .chunks(2)
+        .map(|pair| {
+            Ok(Card {
+                rank: Rank::from_char(pair[0])?,
+                suit: Suit::from_char(pair[1])?,
+            })
+        })
+        .collect()
+}
+
+/// Cards written back to back, e.g. `"Td9c"`.
+pub fn format_cards(cards: &[Card]) -> String {
+    cards.iter().map(Card::to_string).collect()
+}
+
+/// Serde helpers writing cards as `"Ah"` strings, for use with
+/// `#[serde(with = "axm_engine::cards::compact")]` on a `Vec<Card>`.
+/// Reading accepts both the string form and the derived
+/// `{"suit":"Hearts","rank":"Ace"}` form that hand logs used to be written
+/// in; files written through this module can no longer be read by builds
+/// that only know the derived form.
+pub mod compact {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Card;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AnyCard {
+        Compact(String),
+        Full(Card),
+    }
+
+    pub fn serialize<S: Serializer>(cards: &[Card], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(cards.iter().map(Card::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Card>, D::Error> {
+        Vec::<AnyCard>::deserialize(deserializer)?
+            .into_iter()
+            .map(|card| match card {
+                AnyCard::Compact(s) => s.parse().map_err(D::Error::custom),
+                AnyCard::Full(card) => Ok(card),
+            })
+            .collect()
+    }
+}
+
 pub fn all_suits() -> [Suit; 4] {
     [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades]
 }
@@ -1,14 +1,60 @@
+//! Seeded 52-card deck.
+//!
+//! Seeds: a session seed `S` gives hand `i` the seed
+//! `derive_hand_seed(S, i)`, the first `u64` of `ChaCha20Rng::seed_from_u64(S)`
+//! on stream `i`. Streams of one key never overlap, so neither do the hands
+//! of two sessions with nearby seeds. The deck for a hand is
+//! `Deck::new_with_seed(hand_seed)` shuffled once.
+//!
+//! Audit: `commitment` is the SHA-256 of the shuffled order written as
+//! cards (`"7c2dAh..."`), recorded with each hand so the order can be
+//! checked against the seed afterwards without trusting the log.
+//!
+//! Presets: `Deck::preset` pins cards to dealing positions. Every shuffle
+//! then keeps those cards in place and shuffles the rest around them, so a
+//! spot or a recorded hand can be dealt again without knowing its seed.
+
 use rand::seq::SliceRandom;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::cards::{format_cards, full_deck, Card};
+
+/// Seed for hand `hand_index` of the session seeded with `session_seed`.
+pub fn derive_hand_seed(session_seed: u64, hand_index: u64) -> u64 {
+    let mut rng = ChaCha20Rng::seed_from_u64(session_seed);
+    rng.set_stream(hand_index);
+    rng.next_u64()
+}
 
-use crate::cards::{full_deck, Card};
+/// Hex SHA-256 of `cards` in order.
+pub fn commitment(cards: &[Card]) -> String {
+    Sha256::digest(format_cards(cards).as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DeckError {
+    #[error("card {0} is used more than once")]
+    DuplicateCard(Card),
+    #[error("{0} positions given (a deck has 52)")]
+    TooManyCards(usize),
+    #[error("board has {0} cards (at most 5)")]
+    BoardSize(usize),
+}
 
 #[derive(Debug)]
 pub struct Deck {
     cards: Vec<Card>,
     position: usize,
     rng: ChaCha20Rng,
+    /// Cards pinned to the first positions; `None` slots and the positions
+    /// past the end are filled by the shuffle.
+    preset: Vec<Option<Card>>,
 }
 
 impl Deck {
@@ -18,15 +64,71 @@ impl Deck {
             cards: full_deck(),
             position: 0,
             rng,
+            preset: Vec::new(),
         };
         // Keep initial order until shuffle is called explicitly
         d
     }
 
+    /// Deck that always deals `order` first; any cards not listed follow
+    /// in seeded random order.
+    pub fn with_order(seed: u64, order: &[Card]) -> Result<Self, DeckError> {
+        let mut deck = Self::new_with_seed(seed);
+        deck.preset(order.iter().copied().map(Some).collect())?;
+        Ok(deck)
+    }
+
+    /// Pins `slots[i]` to dealing position `i` for every later shuffle.
+    pub fn preset(&mut self, slots: Vec<Option<Card>>) -> Result<(), DeckError> {
+        if slots.len() > 52 {
+            return Err(DeckError::TooManyCards(slots.len()));
+        }
+        let mut seen: Vec<Card> = Vec::new();
+        for &card in slots.iter().flatten() {
+            if seen.contains(&card) {
+                return Err(DeckError::DuplicateCard(card));
+            }
+            seen.push(card);
+        }
+        self.preset = slots;
+        Ok(())
+    }
+
+    pub fn clear_preset(&mut self) {
+        self.preset.clear();
+    }
+
+    pub fn has_preset(&self) -> bool {
+        !self.preset.is_empty()
+    }
+
+    /// Restarts the random stream from `seed`, keeping any preset.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = ChaCha20Rng::seed_from_u64(seed);
+    }
+
     pub fn shuffle(&mut self) {
         self.cards = full_deck();
-        self.cards.shuffle(&mut self.rng);
         self.position = 0;
+        if self.preset.is_empty() {
+            self.cards.shuffle(&mut self.rng);
+            return;
+        }
+        let mut rest: Vec<Card> = self
+            .cards
+            .iter()
+            .copied()
+            .filter(|c| !self.preset.contains(&Some(*c)))
+            .collect();
+        rest.shuffle(&mut self.rng);
+        let mut rest = rest.into_iter();
+        for (i, slot) in self.cards.iter_mut().enumerate() {
+            *slot = match self.preset.get(i) {
+                Some(Some(card)) => *card,
+                // as many free positions as cards left over
+                _ => rest.next().expect("preset leaves room for the rest"),
+            };
+        }
     }
 
     pub fn deal_card(&mut self) -> Option<Card> {
@@ -48,6 +150,15 @@ impl Deck {
         self.position = 0;
     }
 
+    /// All 52 cards in dealing order, dealt ones included.
+    pub fn order(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn commitment(&self) -> String {
+        commitment(&self.cards)
+    }
+
     #[allow(dead_code)]
     pub fn remaining(&self) -> usize {
         self.cards.len().saturating_sub(self.position)
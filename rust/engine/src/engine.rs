@@ -1,18 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::blinds::BlindSchedule;
 use crate::cards::Card;
-use crate::deck::Deck;
-use crate::player::{Player, Position, STARTING_STACK};
+use crate::deck::{Deck, DeckError};
+use crate::errors::GameError;
+use crate::events::{EventSink, HandEvent, ShownHand};
+use crate::hand::{compare_hands, evaluate_hand_optimized, HandStrength};
+use crate::logger::{ActionRecord, HandRecord, RecordBuilder, ShowdownInfo, Street};
+use crate::player::{Player, PlayerAction, Position, STARTING_STACK};
+use crate::pot::{split_pot, PotManager};
+use crate::rules::{legal_actions, validate_action, LegalActions, ValidatedAction};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndReason {
+    Fold,
+    Showdown,
+}
+
+impl EndReason {
+    /// Name used in hand records and JSON.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EndReason::Fold => "fold",
+            EndReason::Showdown => "showdown",
+        }
+    }
+}
+
+// Per-hand betting state; `None` on the engine between hands.
+#[derive(Debug, Clone)]
+struct HandState {
+    street: Street,
+    bb: u32,
+    stack_start: [u32; 2],
+    to_act: Option<usize>,
+    street_committed: [u32; 2],
+    total_committed: [u32; 2],
+    acted: [bool; 2],
+    folded: [bool; 2],
+    raise_closed: [bool; 2],
+    last_full_raise: u32,
+    actions: Vec<ActionRecord>,
+    end_reason: Option<EndReason>,
+    winners: Vec<usize>,
+}
+
+impl HandState {
+    fn high(&self) -> u32 {
+        self.street_committed[0].max(self.street_committed[1])
+    }
+
+    fn commit(&mut self, players: &mut [Player; 2], idx: usize, amount: u32) {
+        let amount = amount.min(players[idx].stack());
+        // amount is clamped to the stack, so this cannot fail
+        let _ = players[idx].bet(amount);
+        self.street_committed[idx] += amount;
+        self.total_committed[idx] += amount;
+    }
+
+    // Antes are dead money: in the pot, but not part of the street's betting.
+    fn post_ante(&mut self, players: &mut [Player; 2], idx: usize, amount: u32) {
+        let amount = amount.min(players[idx].stack());
+        let _ = players[idx].bet(amount);
+        self.total_committed[idx] += amount;
+    }
+
+    fn can_act(&self, players: &[Player; 2], idx: usize) -> bool {
+        !self.folded[idx] && players[idx].stack() > 0
+    }
+
+    fn needs_action(&self, players: &[Player; 2], idx: usize) -> bool {
+        if !self.can_act(players, idx) {
+            return false;
+        }
+        if self.street_committed[idx] < self.high() {
+            return true;
+        }
+        // matched: only owes an action if the opponent can still respond
+        !self.acted[idx] && self.can_act(players, 1 - idx)
+    }
+
+    fn next_after(&self, players: &[Player; 2], last: usize) -> Option<usize> {
+        let other = 1 - last;
+        if self.needs_action(players, other) {
+            Some(other)
+        } else if self.needs_action(players, last) {
+            Some(last)
+        } else {
+            None
+        }
+    }
+
+    // Hand back the part of a bet the opponent could not cover.
+    fn return_uncalled(&mut self, players: &mut [Player; 2]) {
+        let [a, b] = self.street_committed;
+        let (hi, lo) = if a >= b { (0, 1) } else { (1, 0) };
+        let excess = self.street_committed[hi] - self.street_committed[lo];
+        if excess > 0 {
+            players[hi].add_chips(excess);
+            self.street_committed[hi] -= excess;
+            self.total_committed[hi] -= excess;
+        }
+    }
+}
+
+/// Hole cards per seat and board set by `force_cards`.
+type ForcedCards = ([Option<[Card; 2]>; 2], Vec<Card>);
+
+/// Deck seed when none is given.
+pub const DEFAULT_SEED: u64 = 0xA1A2_A3A4;
 
 #[derive(Debug)]
 pub struct Engine {
     deck: Deck,
     players: [Player; 2],
-    _level: u8,
+    level: u8,
+    schedule: BlindSchedule,
     board: Vec<Card>,
+    hand: Option<HandState>,
+    forced: Option<ForcedCards>,
+    sink: EventSink,
 }
 
 impl Engine {
     pub fn new(seed: Option<u64>, level: u8) -> Self {
-        let seed = seed.unwrap_or(0xA1A2_A3A4);
+        Self::with_schedule(seed, level, BlindSchedule::default())
+    }
+
+    pub fn with_schedule(seed: Option<u64>, level: u8, schedule: BlindSchedule) -> Self {
+        let seed = seed.unwrap_or(DEFAULT_SEED);
         let deck = Deck::new_with_seed(seed);
         let players = [
             Player::new(0, STARTING_STACK, Position::Button),
@@ -21,8 +138,12 @@ impl Engine {
         Self {
             deck,
             players,
-            _level: level,
+            level,
+            schedule,
             board: Vec::with_capacity(5),
+            hand: None,
+            forced: None,
+            sink: EventSink::default(),
         }
     }
 
@@ -33,10 +154,103 @@ impl Engine {
         &mut self.players
     }
 
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Takes effect from the next `start_hand`.
+    pub fn set_level(&mut self, level: u8) {
+        self.level = level;
+    }
+
+    pub fn schedule(&self) -> &BlindSchedule {
+        &self.schedule
+    }
+
+    /// (small blind, big blind) for the engine's current level.
+    pub fn blinds(&self) -> (u32, u32) {
+        let l = self.schedule.level(self.level);
+        (l.sb, l.bb)
+    }
+
+    /// Ante each player posts at the current level; with a big-blind ante
+    /// the big blind posts it for the whole table.
+    pub fn ante(&self) -> u32 {
+        self.schedule.level(self.level).ante
+    }
+
+    /// Seat index of the button, who also posts the small blind.
+    pub fn button(&self) -> usize {
+        if self.players[1].position() == Position::Button {
+            1
+        } else {
+            0
+        }
+    }
+
     pub fn shuffle(&mut self) {
         self.deck.shuffle();
     }
 
+    /// Restarts the deck's random stream from `seed`, e.g. a seed from
+    /// `deck::derive_hand_seed`. Preset or forced cards stay in place.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.deck.reseed(seed);
+    }
+
+    /// Deals every following hand from `order`, top first (button, big
+    /// blind, button, big blind, burn, flop, ...). Cards not listed are
+    /// shuffled in after it.
+    pub fn set_deck_order(&mut self, order: &[Card]) -> Result<(), DeckError> {
+        self.deck
+            .preset(order.iter().copied().map(Some).collect())?;
+        self.forced = None;
+        Ok(())
+    }
+
+    /// Gives each seat its `hole` cards (when set) and starts the board
+    /// with `board` on every following hand; all other cards are shuffled.
+    pub fn force_cards(
+        &mut self,
+        hole: [Option<[Card; 2]>; 2],
+        board: &[Card],
+    ) -> Result<(), DeckError> {
+        if board.len() > 5 {
+            return Err(DeckError::BoardSize(board.len()));
+        }
+        self.deck
+            .preset(forced_slots(&hole, board, self.button()))?;
+        self.forced = Some((hole, board.to_vec()));
+        Ok(())
+    }
+
+    /// Back to plain seeded shuffles.
+    pub fn clear_forced_cards(&mut self) {
+        self.deck.clear_preset();
+        self.forced = None;
+    }
+
+    /// The deck in its current order; after `start_hand`, the order the
+    /// hand is dealt from.
+    pub fn deck_order(&self) -> &[Card] {
+        self.deck.order()
+    }
+
+    pub fn deck_commitment(&self) -> String {
+        self.deck.commitment()
+    }
+
+    /// Events of the current hand so far; see `events`.
+    pub fn events(&self) -> &[HandEvent] {
+        self.sink.events()
+    }
+
+    /// Receives every event from now on, across hands. See
+    /// [`crate::events`] for how the channel buffers.
+    pub fn subscribe(&mut self) -> std::sync::mpsc::Receiver<HandEvent> {
+        self.sink.subscribe()
+    }
+
     pub fn draw_n(&mut self, n: usize) -> Vec<Card> {
         (0..n).filter_map(|_| self.deck.deal_card()).collect()
     }
@@ -57,32 +271,256 @@ impl Engine {
                 p.give_card(c)?;
             }
         }
-        // flop
-        self.deck.burn_card();
-        for _ in 0..3 {
-            let c = self
-                .deck
-                .deal_card()
-                .ok_or_else(|| "deck empty".to_string())?;
-            self.board.push(c);
-        }
-        // turn
-        self.deck.burn_card();
-        self.board.push(
-            self.deck
-                .deal_card()
-                .ok_or_else(|| "deck empty".to_string())?,
-        );
-        // river
-        self.deck.burn_card();
-        self.board.push(
-            self.deck
-                .deal_card()
-                .ok_or_else(|| "deck empty".to_string())?,
-        );
+        for street in [Street::Flop, Street::Turn, Street::River] {
+            deal_street(&mut self.deck, &mut self.board, street)
+                .map_err(|_| "deck empty".to_string())?;
+        }
         Ok(())
     }
 
+    /// Shuffles, deals hole cards from the button, posts blinds for the
+    /// current level and hands the first preflop decision to the button.
+    pub fn start_hand(&mut self) -> Result<(), GameError> {
+        if self.players.iter().any(|p| p.stack() == 0) {
+            return Err(GameError::PlayerBusted);
+        }
+        let (sb, bb) = self.blinds();
+        let button = self.button();
+        let big = 1 - button;
+
+        if let Some((hole, board)) = &self.forced {
+            // the button may have moved since `force_cards`
+            self.deck
+                .preset(forced_slots(hole, board, button))
+                .expect("forced cards were checked");
+        }
+        self.deck.shuffle();
+        self.board.clear();
+        for p in &mut self.players {
+            p.clear_cards();
+        }
+        for _ in 0..2 {
+            for idx in [button, big] {
+                let c = self.deck.deal_card().ok_or(GameError::DeckEmpty)?;
+                // hole cards were just cleared, so there is always room
+                let _ = self.players[idx].give_card(c);
+            }
+        }
+
+        let mut hand = HandState {
+            street: Street::Preflop,
+            bb,
+            stack_start: [self.players[0].stack(), self.players[1].stack()],
+            to_act: None,
+            street_committed: [0; 2],
+            total_committed: [0; 2],
+            acted: [false; 2],
+            folded: [false; 2],
+            raise_closed: [false; 2],
+            last_full_raise: bb,
+            actions: Vec::new(),
+            end_reason: None,
+            winners: Vec::new(),
+        };
+        let ante = self.ante();
+        self.sink.clear();
+        self.sink.emit(HandEvent::HandStarted {
+            button,
+            level: self.level,
+            sb,
+            bb,
+            ante,
+            stacks: hand.stack_start,
+        });
+        if self.schedule.big_blind_ante() {
+            let table_ante = ante * self.players.len() as u32;
+            hand.post_ante(&mut self.players, big, table_ante);
+        } else {
+            for idx in [button, big] {
+                hand.post_ante(&mut self.players, idx, ante);
+            }
+        }
+        let antes = hand.total_committed;
+        hand.commit(&mut self.players, button, sb);
+        hand.commit(&mut self.players, big, bb);
+        self.sink.emit(HandEvent::BlindsPosted {
+            antes,
+            sb_seat: button,
+            sb: hand.street_committed[button],
+            bb_seat: big,
+            bb: hand.street_committed[big],
+        });
+        for seat in [button, big] {
+            self.sink.emit(HandEvent::CardsDealt {
+                street: Street::Preflop,
+                seat: Some(seat),
+                cards: self.players[seat]
+                    .hole_cards()
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            });
+        }
+        self.hand = Some(hand);
+        // the button acts first preflop, so resume as if the big blind just acted
+        self.advance(big)
+    }
+
+    /// Applies `action` for `player`, who must be the player to act.
+    pub fn apply_action(
+        &mut self,
+        player: usize,
+        action: PlayerAction,
+    ) -> Result<ValidatedAction, GameError> {
+        let hand = self.hand.as_ref().ok_or(GameError::HandNotInProgress)?;
+        if hand.end_reason.is_some() {
+            return Err(GameError::HandNotInProgress);
+        }
+        if hand.to_act != Some(player) {
+            return Err(GameError::OutOfTurn { player });
+        }
+        let high = hand.high();
+        let to_call = high - hand.street_committed[player];
+        let legal = self.legal_actions().ok_or(GameError::HandNotInProgress)?;
+        let validated = validate_action(
+            self.players[player].stack(),
+            to_call,
+            hand.last_full_raise,
+            action,
+        )?;
+        legal.permits(&validated)?;
+
+        let hand = self.hand.as_mut().ok_or(GameError::HandNotInProgress)?;
+        let street = hand.street;
+        let before = hand.total_committed[player];
+        let recorded = match validated {
+            ValidatedAction::Fold => {
+                hand.folded[player] = true;
+                PlayerAction::Fold
+            }
+            ValidatedAction::Check => PlayerAction::Check,
+            ValidatedAction::Call(amount) => {
+                hand.commit(&mut self.players, player, amount);
+                PlayerAction::Call
+            }
+            ValidatedAction::Bet(amount) => {
+                hand.commit(&mut self.players, player, amount);
+                hand.last_full_raise = amount;
+                hand.raise_closed[1 - player] = false;
+                PlayerAction::Bet(amount)
+            }
+            ValidatedAction::Raise(amount) => {
+                hand.commit(&mut self.players, player, to_call + amount);
+                hand.last_full_raise = amount;
+                hand.raise_closed[1 - player] = false;
+                PlayerAction::Raise(amount)
+            }
+            ValidatedAction::AllIn(amount) => {
+                hand.commit(&mut self.players, player, amount);
+                let extra = hand.street_committed[player].saturating_sub(high);
+                if extra >= hand.last_full_raise {
+                    hand.last_full_raise = extra;
+                    hand.raise_closed[1 - player] = false;
+                } else if extra > 0 {
+                    // a short all-in does not reopen betting for a player who already acted
+                    hand.raise_closed[1 - player] = hand.acted[1 - player];
+                }
+                PlayerAction::AllIn
+            }
+        };
+        if hand.street_committed[player] > high {
+            hand.acted[1 - player] = false;
+        }
+        hand.acted[player] = true;
+        let amount = hand.total_committed[player] - before;
+        hand.actions.push(ActionRecord {
+            player_id: player,
+            street,
+            action: recorded.clone(),
+            amount: Some(amount),
+        });
+        self.sink.emit(HandEvent::ActionTaken {
+            seat: player,
+            street,
+            action: recorded,
+            amount,
+            pot: hand.total_committed.iter().sum(),
+        });
+
+        if hand.folded[player] {
+            let winner = 1 - player;
+            let pot: u32 = hand.total_committed.iter().sum();
+            hand.to_act = None;
+            hand.end_reason = Some(EndReason::Fold);
+            hand.winners = vec![winner];
+            self.players[winner].add_chips(pot);
+            self.sink.emit(HandEvent::PotAwarded {
+                seat: winner,
+                amount: pot,
+                pot_index: 0,
+            });
+            self.sink.emit(hand_ended(&self.players, hand));
+            return Ok(validated);
+        }
+        self.advance(player)?;
+        Ok(validated)
+    }
+
+    // Hands the turn to whoever still owes an action. When betting closes,
+    // returns any uncalled chips and deals the next street, or runs the board
+    // out to showdown once fewer than two players can still bet.
+    fn advance(&mut self, mut last: usize) -> Result<(), GameError> {
+        let button = self.button();
+        let Engine {
+            deck,
+            players,
+            board,
+            hand,
+            sink,
+            ..
+        } = self;
+        let hand = hand.as_mut().ok_or(GameError::HandNotInProgress)?;
+        loop {
+            hand.to_act = hand.next_after(players, last);
+            if hand.to_act.is_some() {
+                return Ok(());
+            }
+            hand.return_uncalled(players);
+            let live = (0..2).filter(|&i| hand.can_act(players, i)).count();
+            let next = match hand.street {
+                Street::Preflop => Street::Flop,
+                Street::Flop => Street::Turn,
+                Street::Turn => Street::River,
+                Street::River => {
+                    hand.to_act = None;
+                    hand.end_reason = Some(EndReason::Showdown);
+                    award_showdown(players, board, hand, button, sink);
+                    sink.emit(hand_ended(players, hand));
+                    return Ok(());
+                }
+            };
+            let dealt = board.len();
+            deal_street(deck, board, next)?;
+            hand.street = next;
+            sink.emit(HandEvent::StreetAdvanced { street: next });
+            sink.emit(HandEvent::CardsDealt {
+                street: next,
+                seat: None,
+                cards: board[dealt..].to_vec(),
+            });
+            hand.street_committed = [0; 2];
+            hand.acted = [false; 2];
+            hand.raise_closed = [false; 2];
+            hand.last_full_raise = hand.bb;
+            if live < 2 {
+                // nobody left to bet: keep dealing until the river
+                continue;
+            }
+            // the big blind acts first after the flop
+            last = button;
+        }
+    }
+
     pub fn board(&self) -> &Vec<Card> {
         &self.board
     }
@@ -91,7 +529,198 @@ impl Engine {
         self.board.len() == 5
     }
 
+    pub fn is_hand_over(&self) -> bool {
+        self.hand.as_ref().is_some_and(|h| h.end_reason.is_some())
+    }
+
+    /// Current street, or `None` if no hand has been started.
+    pub fn street(&self) -> Option<Street> {
+        self.hand.as_ref().map(|h| h.street)
+    }
+
+    /// Seat index of the player who must act next, if any.
+    pub fn to_act(&self) -> Option<usize> {
+        self.hand.as_ref().and_then(|h| h.to_act)
+    }
+
+    /// Legal action set for the player to act, or `None` when nobody is.
+    pub fn legal_actions(&self) -> Option<LegalActions> {
+        let hand = self.hand.as_ref()?;
+        let player = hand.to_act?;
+        let high = hand.high();
+        Some(legal_actions(
+            self.players[player].stack(),
+            high - hand.street_committed[player],
+            high,
+            hand.last_full_raise,
+            hand.bb,
+            !hand.raise_closed[player] && hand.can_act(&self.players, 1 - player),
+        ))
+    }
+
+    pub fn to_call(&self, player: usize) -> u32 {
+        self.hand
+            .as_ref()
+            .map(|h| h.high() - h.street_committed[player])
+            .unwrap_or(0)
+    }
+
+    /// Chips committed by both players during the current hand.
+    pub fn pot(&self) -> u32 {
+        self.hand
+            .as_ref()
+            .map(|h| h.total_committed.iter().sum())
+            .unwrap_or(0)
+    }
+
+    pub fn actions(&self) -> &[ActionRecord] {
+        self.hand.as_ref().map(|h| &h.actions[..]).unwrap_or(&[])
+    }
+
+    pub fn end_reason(&self) -> Option<EndReason> {
+        self.hand.as_ref().and_then(|h| h.end_reason)
+    }
+
+    pub fn winners(&self) -> &[usize] {
+        self.hand.as_ref().map(|h| &h.winners[..]).unwrap_or(&[])
+    }
+
+    /// Showdown winners, once a hand has ended at showdown.
+    pub fn showdown(&self) -> Option<ShowdownInfo> {
+        let hand = self.hand.as_ref()?;
+        (hand.end_reason == Some(EndReason::Showdown)).then(|| ShowdownInfo {
+            winners: hand.winners.clone(),
+            notes: None,
+        })
+    }
+
+    /// Chips each seat won (positive) or lost over the finished hand.
+    pub fn net_result(&self) -> Option<[i64; 2]> {
+        let hand = self.hand.as_ref().filter(|h| h.end_reason.is_some())?;
+        Some([0, 1].map(|i| self.players[i].stack() as i64 - hand.stack_start[i] as i64))
+    }
+
+    /// The current hand as a record, with seats named `p0` and `p1`.
+    /// Result fields stay empty until the hand has ended; `None` before
+    /// the first hand.
+    pub fn hand_record(&self, hand_id: String) -> Option<HandRecord> {
+        self.hand.as_ref()?;
+        let mut builder = RecordBuilder::default();
+        let ended = self.events().iter().find_map(|e| builder.push(e));
+        let mut record = ended.or_else(|| builder.current().cloned())?;
+        record.hand_id = hand_id;
+        record.deck_commitment = Some(self.deck_commitment());
+        Some(record)
+    }
+
     pub fn deck_remaining(&self) -> usize {
         self.deck.remaining()
     }
 }
+
+// Awards each pot layer to the best hand among its eligible seats, splitting
+// ties with odd chips going to the first winner left of the button. The
+// hand's winners are those of the main pot.
+fn award_showdown(
+    players: &mut [Player; 2],
+    board: &[Card],
+    hand: &mut HandState,
+    button: usize,
+    sink: &mut EventSink,
+) {
+    let seats = players.len();
+    sink.emit(HandEvent::Showdown {
+        hands: (0..seats)
+            .filter(|&i| !hand.folded[i])
+            .map(|seat| ShownHand {
+                seat,
+                cards: players[seat].hole_cards().into_iter().flatten().collect(),
+            })
+            .collect(),
+    });
+    let strengths: Vec<Option<HandStrength>> = (0..seats)
+        .map(|i| {
+            if hand.folded[i] {
+                return None;
+            }
+            let [Some(a), Some(b)] = players[i].hole_cards() else {
+                return None;
+            };
+            let board: &[Card; 5] = board.try_into().ok()?;
+            let mut cards = [a, b, a, a, a, a, a];
+            cards[2..].copy_from_slice(board);
+            Some(evaluate_hand_optimized(&cards))
+        })
+        .collect();
+
+    let pots = PotManager::build(&hand.total_committed, &hand.folded);
+    for (layer, pot) in pots.pots().iter().enumerate() {
+        let best = pot
+            .eligible
+            .iter()
+            .filter_map(|&i| strengths[i].as_ref())
+            .max_by(|a, b| compare_hands(a, b));
+        let mut winners: Vec<usize> = pot
+            .eligible
+            .iter()
+            .copied()
+            .filter(|&i| best.is_some() && strengths[i].as_ref() == best)
+            .collect();
+        winners.sort_by_key(|&seat| (seat + seats - button - 1) % seats);
+        for (&seat, share) in winners.iter().zip(split_pot(pot.amount, winners.len())) {
+            players[seat].add_chips(share);
+            sink.emit(HandEvent::PotAwarded {
+                seat,
+                amount: share,
+                pot_index: layer,
+            });
+        }
+        if layer == 0 {
+            winners.sort_unstable();
+            hand.winners = winners;
+        }
+    }
+}
+
+pub(crate) fn seat_id(seat: usize) -> String {
+    format!("p{}", seat)
+}
+
+fn hand_ended(players: &[Player; 2], hand: &HandState) -> HandEvent {
+    let stacks = [players[0].stack(), players[1].stack()];
+    HandEvent::HandEnded {
+        reason: hand.end_reason.unwrap_or(EndReason::Showdown),
+        winners: hand.winners.clone(),
+        net: [0, 1].map(|i| stacks[i] as i64 - hand.stack_start[i] as i64),
+        stacks,
+    }
+}
+
+// Deck positions of forced cards: hole cards alternate from the button,
+// then burn, flop, burn, turn, burn, river.
+fn forced_slots(hole: &[Option<[Card; 2]>; 2], board: &[Card], button: usize) -> Vec<Option<Card>> {
+    let mut slots = vec![None; 12];
+    for (k, seat) in [button, 1 - button].into_iter().enumerate() {
+        if let Some(cards) = hole[seat] {
+            slots[k] = Some(cards[0]);
+            slots[k + 2] = Some(cards[1]);
+        }
+    }
+    for (&pos, &card) in [5, 6, 7, 9, 11].iter().zip(board) {
+        slots[pos] = Some(card);
+    }
+    slots
+}
+
+fn deal_street(deck: &mut Deck, board: &mut Vec<Card>, street: Street) -> Result<(), GameError> {
+    let n = match street {
+        Street::Preflop => return Ok(()),
+        Street::Flop => 3,
+        Street::Turn | Street::River => 1,
+    };
+    deck.burn_card();
+    for _ in 0..n {
+        board.push(deck.deal_card().ok_or(GameError::DeckEmpty)?);
+    }
+    Ok(())
+}
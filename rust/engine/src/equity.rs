@@ -0,0 +1,219 @@
+//! All-in equity of known hole cards against each other.
+//!
+//! Runouts are enumerated exactly when there are few enough of them and
+//! sampled from a seeded `Deck` otherwise. Hands are ranked with the
+//! `hand_rank` tables, which agree with `evaluate_hand` at showdown.
+
+use thiserror::Error;
+
+use crate::cards::{full_deck, Card};
+use crate::deck::Deck;
+use crate::hand_rank::{evaluate, HandRank};
+
+/// Most runouts enumerated before switching to Monte Carlo. A flop or
+/// later board is always exact; preflop heads-up (1.7M runouts) is not.
+pub const EXACT_LIMIT: u64 = 250_000;
+
+pub const DEFAULT_ITERATIONS: u64 = 100_000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EquityError {
+    #[error("at least two hands are needed")]
+    TooFewHands,
+    #[error("board has {0} cards (expected 0, 3, 4 or 5)")]
+    BoardSize(usize),
+    #[error("card {0} is used more than once")]
+    DuplicateCard(Card),
+    #[error("not enough cards left to complete the board")]
+    DeckExhausted,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HandEquity {
+    /// Runouts won outright, tied for the best hand, and lost.
+    pub wins: u64,
+    pub ties: u64,
+    pub losses: u64,
+    /// Pot share summed over runouts; a tie between k hands counts 1/k.
+    pub share: f64,
+}
+
+impl HandEquity {
+    fn pct(&self, n: u64, total: u64) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            n as f64 / total as f64 * 100.0
+        }
+    }
+
+    pub fn win_pct(&self, runouts: u64) -> f64 {
+        self.pct(self.wins, runouts)
+    }
+
+    pub fn tie_pct(&self, runouts: u64) -> f64 {
+        self.pct(self.ties, runouts)
+    }
+
+    pub fn loss_pct(&self, runouts: u64) -> f64 {
+        self.pct(self.losses, runouts)
+    }
+
+    /// Expected share of the pot, in percent.
+    pub fn equity_pct(&self, runouts: u64) -> f64 {
+        if runouts == 0 {
+            0.0
+        } else {
+            self.share / runouts as f64 * 100.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquityResult {
+    /// One entry per input hand, in input order.
+    pub hands: Vec<HandEquity>,
+    pub runouts: u64,
+    pub exact: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquityOptions {
+    pub iterations: u64,
+    pub seed: u64,
+    /// Enumerate when the number of runouts is at most this.
+    pub exact_limit: u64,
+}
+
+impl Default for EquityOptions {
+    fn default() -> Self {
+        Self {
+            iterations: DEFAULT_ITERATIONS,
+            seed: 0,
+            exact_limit: EXACT_LIMIT,
+        }
+    }
+}
+
+/// Equity of `hands` on `board` with `dead` cards removed from the deck.
+pub fn calculate(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    opts: &EquityOptions,
+) -> Result<EquityResult, EquityError> {
+    if hands.len() < 2 {
+        return Err(EquityError::TooFewHands);
+    }
+    if !matches!(board.len(), 0 | 3 | 4 | 5) {
+        return Err(EquityError::BoardSize(board.len()));
+    }
+    let mut used: Vec<Card> = Vec::new();
+    for &card in hands.iter().flatten().chain(board).chain(dead) {
+        if used.contains(&card) {
+            return Err(EquityError::DuplicateCard(card));
+        }
+        used.push(card);
+    }
+    let stub: Vec<Card> = full_deck()
+        .into_iter()
+        .filter(|c| !used.contains(c))
+        .collect();
+    let missing = 5 - board.len();
+    if stub.len() < missing {
+        return Err(EquityError::DeckExhausted);
+    }
+
+    let mut tally = Tally::new(hands, board);
+    let exact = combinations(stub.len() as u64, missing as u64) <= opts.exact_limit;
+    if exact {
+        let mut idx: Vec<usize> = (0..missing).collect();
+        loop {
+            tally.add(idx.iter().map(|&i| stub[i]));
+            if !next_combination(&mut idx, stub.len()) {
+                break;
+            }
+        }
+    } else {
+        let mut deck = Deck::new_with_seed(opts.seed);
+        for _ in 0..opts.iterations {
+            deck.shuffle();
+            let runout = std::iter::from_fn(|| deck.deal_card())
+                .filter(|c| !used.contains(c))
+                .take(missing);
+            tally.add(runout);
+        }
+    }
+    Ok(EquityResult {
+        hands: tally.equities,
+        runouts: tally.runouts,
+        exact,
+    })
+}
+
+struct Tally<'a> {
+    hands: &'a [[Card; 2]],
+    cards: [Card; 7],
+    known: usize,
+    ranks: Vec<HandRank>,
+    equities: Vec<HandEquity>,
+    runouts: u64,
+}
+
+impl<'a> Tally<'a> {
+    fn new(hands: &'a [[Card; 2]], board: &[Card]) -> Self {
+        let mut cards = [hands[0][0]; 7];
+        cards[2..2 + board.len()].copy_from_slice(board);
+        Self {
+            hands,
+            cards,
+            known: 2 + board.len(),
+            ranks: vec![HandRank(0); hands.len()],
+            equities: vec![HandEquity::default(); hands.len()],
+            runouts: 0,
+        }
+    }
+
+    fn add(&mut self, runout: impl Iterator<Item = Card>) {
+        for (slot, card) in self.cards[self.known..].iter_mut().zip(runout) {
+            *slot = card;
+        }
+        for (rank, hole) in self.ranks.iter_mut().zip(self.hands) {
+            self.cards[..2].copy_from_slice(hole);
+            *rank = evaluate(&self.cards);
+        }
+        let best = *self.ranks.iter().max().expect("at least two hands");
+        let count = self.ranks.iter().filter(|&&r| r == best).count();
+        for (eq, &rank) in self.equities.iter_mut().zip(&self.ranks) {
+            if rank != best {
+                eq.losses += 1;
+            } else if count == 1 {
+                eq.wins += 1;
+                eq.share += 1.0;
+            } else {
+                eq.ties += 1;
+                eq.share += 1.0 / count as f64;
+            }
+        }
+        self.runouts += 1;
+    }
+}
+
+fn combinations(n: u64, k: u64) -> u64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+// Advances `idx` to the next k-subset of 0..n in lexicographic order.
+fn next_combination(idx: &mut [usize], n: usize) -> bool {
+    let k = idx.len();
+    for i in (0..k).rev() {
+        if idx[i] < n - k + i {
+            idx[i] += 1;
+            for j in i + 1..k {
+                idx[j] = idx[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
@@ -6,4 +6,16 @@ pub enum GameError {
     InvalidBetAmount { amount: u32, minimum: u32 },
     #[error("Insufficient chips for action")]
     InsufficientChips,
+    #[error("Amount {amount} is not a multiple of the {unit}-chip unit")]
+    InvalidChipUnit { amount: u32, unit: u32 },
+    #[error("Player stack zero")]
+    PlayerBusted,
+    #[error("No hand in progress")]
+    HandNotInProgress,
+    #[error("Player {player} acted out of turn")]
+    OutOfTurn { player: usize },
+    #[error("Illegal action: {reason}")]
+    IllegalAction { reason: &'static str },
+    #[error("Deck empty")]
+    DeckEmpty,
 }
@@ -0,0 +1,120 @@
+//! Typed events emitted by `Engine` as a hand is played.
+//!
+//! Each hand produces, in order: `HandStarted`, `BlindsPosted`, one
+//! `CardsDealt` per seat, then `ActionTaken` for every action with a
+//! `StreetAdvanced` and a board `CardsDealt` at each new street. A hand
+//! that reaches the river ends with `Showdown`; every hand ends with one
+//! `PotAwarded` per share and finally `HandEnded`.
+//!
+//! The current hand's events are kept on the engine (`Engine::events`) and
+//! also sent to every receiver from `Engine::subscribe`;
+//! `logger::RecordBuilder` turns either into hand records, and
+//! `Engine::hand_record` is built that way. Events serialize as internally
+//! tagged JSON (`{"type":"ActionTaken",...}`) with cards as `"Ah"` strings.
+//!
+//! Subscription channels are unbounded so the engine never blocks on a slow
+//! or single-threaded reader; a receiver holds every event sent since it was
+//! last drained, roughly 20 small values per hand, so one that is kept but
+//! never read grows with every hand. Dropping the receiver unsubscribes.
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cards::Card;
+use crate::engine::EndReason;
+use crate::logger::Street;
+use crate::player::PlayerAction;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HandEvent {
+    HandStarted {
+        button: usize,
+        level: u8,
+        sb: u32,
+        bb: u32,
+        ante: u32,
+        /// Stacks before antes and blinds.
+        stacks: [u32; 2],
+    },
+    /// Chips actually posted, which may be less than the level's amounts
+    /// for a short stack.
+    BlindsPosted {
+        antes: [u32; 2],
+        sb_seat: usize,
+        sb: u32,
+        bb_seat: usize,
+        bb: u32,
+    },
+    /// Hole cards for `seat`, or board cards when `seat` is `None`.
+    CardsDealt {
+        street: Street,
+        seat: Option<usize>,
+        #[serde(with = "crate::cards::compact")]
+        cards: Vec<Card>,
+    },
+    ActionTaken {
+        seat: usize,
+        street: Street,
+        action: PlayerAction,
+        /// Chips the action put in.
+        amount: u32,
+        /// Pot after the action.
+        pot: u32,
+    },
+    StreetAdvanced {
+        street: Street,
+    },
+    Showdown {
+        hands: Vec<ShownHand>,
+    },
+    PotAwarded {
+        seat: usize,
+        amount: u32,
+        /// 0 for the main pot, then side pots in order.
+        pot_index: usize,
+    },
+    HandEnded {
+        reason: EndReason,
+        winners: Vec<usize>,
+        net: [i64; 2],
+        stacks: [u32; 2],
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShownHand {
+    pub seat: usize,
+    #[serde(with = "crate::cards::compact")]
+    pub cards: Vec<Card>,
+}
+
+/// The current hand's events plus the channels they are forwarded to.
+#[derive(Debug, Default)]
+pub(crate) struct EventSink {
+    events: Vec<HandEvent>,
+    subscribers: Vec<Sender<HandEvent>>,
+}
+
+impl EventSink {
+    pub(crate) fn subscribe(&mut self) -> Receiver<HandEvent> {
+        let (tx, rx) = channel();
+        self.subscribers.push(tx);
+        rx
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub(crate) fn events(&self) -> &[HandEvent] {
+        &self.events
+    }
+
+    pub(crate) fn emit(&mut self, event: HandEvent) {
+        // receivers that were dropped are forgotten
+        self.subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+        self.events.push(event);
+    }
+}
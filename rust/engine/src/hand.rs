@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 
 use crate::cards::{Card, Rank, Suit};
+use crate::hand_rank;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Category {
@@ -125,7 +126,9 @@ pub fn evaluate_hand(cards: &[Card; 7]) -> HandStrength {
         let high = prs[0];
         let low = prs[1];
         let mut k = [high, low, 0, 0, 0];
+        // a third pair can play as the kicker
         let mut rest = singles.clone();
+        rest.extend(prs[2..].iter().copied());
         rest.sort_unstable_by(|a, b| b.cmp(a));
         k[2] = *rest.get(0).unwrap_or(&0);
         return HandStrength {
@@ -166,35 +169,9 @@ pub fn compare_hands(a: &HandStrength, b: &HandStrength) -> Ordering {
     }
 }
 
-// Optimized variant using bitmasks for straight detection paths.
+/// Same result as `evaluate_hand`, from the lookup tables in `hand_rank`.
 pub fn evaluate_hand_optimized(cards: &[Card; 7]) -> HandStrength {
-    // leverage the same logic but use bit masks to speed straight/flush checks
-    // Build rank bitset and suit counts
-    let mut _rank_mask: u16 = 0;
-    let mut suit_counts = [0u8; 4];
-    let mut by_suit_mask: [u16; 4] = [0, 0, 0, 0];
-    for &c in cards.iter() {
-        let r = rank_val(c.rank);
-        _rank_mask |= 1u16 << r;
-        let s = suit_index(c.suit);
-        suit_counts[s] += 1;
-        by_suit_mask[s] |= 1u16 << r;
-    }
-
-    // Straight flush via mask
-    for s in 0..4 {
-        if suit_counts[s] >= 5 {
-            if let Some(high) = straight_high_from_mask(by_suit_mask[s]) {
-                return HandStrength {
-                    category: Category::StraightFlush,
-                    kickers: [high, 0, 0, 0, 0],
-                };
-            }
-        }
-    }
-
-    // Fallback to baseline for the rest (keeps equivalence simple)
-    evaluate_hand(cards)
+    hand_rank::evaluate(cards).strength()
 }
 
 fn rank_val(r: Rank) -> u8 {
@@ -242,27 +219,6 @@ fn detect_straight_high(sorted_unique_ranks: &[u8]) -> Option<u8> {
     }
 }
 
-fn straight_high_from_mask(mask: u16) -> Option<u8> {
-    // Treat Ace as 14 and optionally as 1
-    let mut m = mask;
-    // add Ace-low if Ace present
-    if (m & (1 << 14)) != 0 {
-        m |= 1 << 1;
-    }
-    // Sliding 5-bit window from Ace(14) down to 5
-    for high in (5..=14).rev() {
-        let window = (1u16 << (high - 4))
-            | (1 << (high - 3))
-            | (1 << (high - 2))
-            | (1 << (high - 1))
-            | (1 << high);
-        if (m & window) == window {
-            return Some(if high == 5 { 5 } else { high as u8 });
-        }
-    }
-    None
-}
-
 pub fn evaluate_many_optimized(cards: &[Card; 7], n: usize) -> Vec<HandStrength> {
     let mut v = Vec::with_capacity(n);
     for _ in 0..n {
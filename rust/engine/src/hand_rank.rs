@@ -0,0 +1,255 @@
+//! Table-driven evaluator for 5, 6 or 7 cards.
+//!
+//! A hand's value is a single `u32`: the category in bits 20..24 and the
+//! five tiebreak ranks (2..=14) in the nibbles below, so comparing two
+//! values orders hands exactly like `compare_hands` on their
+//! `HandStrength`.
+//!
+//! Flushes are looked up by the flush suit's 13-bit rank mask. Everything
+//! else only depends on how many cards of each rank there are, so those
+//! hands are looked up by the index of their rank-count vector among all
+//! vectors with the same card count. Both tables are built once on first
+//! use; evaluating does not allocate.
+
+use std::sync::OnceLock;
+
+use crate::cards::Card;
+use crate::hand::{Category, HandStrength};
+
+/// Comparable hand value; higher is better.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HandRank(pub u32);
+
+impl HandRank {
+    pub fn category(self) -> Category {
+        match self.0 >> 20 {
+            0 => Category::HighCard,
+            1 => Category::OnePair,
+            2 => Category::TwoPair,
+            3 => Category::ThreeOfAKind,
+            4 => Category::Straight,
+            5 => Category::Flush,
+            6 => Category::FullHouse,
+            7 => Category::FourOfAKind,
+            _ => Category::StraightFlush,
+        }
+    }
+
+    pub fn strength(self) -> HandStrength {
+        let mut kickers = [0u8; 5];
+        for (i, k) in kickers.iter_mut().enumerate() {
+            *k = ((self.0 >> (16 - 4 * i)) & 0xF) as u8;
+        }
+        HandStrength {
+            category: self.category(),
+            kickers,
+        }
+    }
+}
+
+impl From<&HandStrength> for HandRank {
+    fn from(s: &HandStrength) -> Self {
+        HandRank(pack(s.category, s.kickers))
+    }
+}
+
+impl From<HandRank> for HandStrength {
+    fn from(r: HandRank) -> Self {
+        r.strength()
+    }
+}
+
+/// Value of the best five-card hand in `cards`.
+///
+/// # Panics
+/// If `cards` does not hold 5, 6 or 7 cards.
+pub fn evaluate(cards: &[Card]) -> HandRank {
+    assert!(
+        (5..=7).contains(&cards.len()),
+        "evaluate takes 5 to 7 cards, got {}",
+        cards.len()
+    );
+    let tables = tables();
+    let mut suits = [0u16; 4];
+    let mut counts = [0u8; 13];
+    for c in cards {
+        let r = c.rank as usize - 2;
+        suits[c.suit as usize] |= 1 << r;
+        counts[r] += 1;
+    }
+    // with at most 7 cards only one suit can hold five
+    if let Some(&mask) = suits.iter().find(|m| m.count_ones() >= 5) {
+        return HandRank(tables.flush[mask as usize]);
+    }
+    let n = cards.len();
+    HandRank(tables.rest[n - 5][count_index(&counts, n)])
+}
+
+fn pack(category: Category, kickers: [u8; 5]) -> u32 {
+    kickers
+        .iter()
+        .fold(category as u32, |acc, &k| (acc << 4) | u32::from(k))
+}
+
+struct Tables {
+    /// Best flush or straight flush for every 13-bit mask with 5+ ranks.
+    flush: Vec<u32>,
+    /// Non-flush value by rank-count index, for 5, 6 and 7 cards.
+    rest: [Vec<u32>; 3],
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut flush = vec![0u32; 1 << 13];
+        for (mask, slot) in flush.iter_mut().enumerate() {
+            if (mask as u16).count_ones() >= 5 {
+                *slot = flush_value(mask as u16);
+            }
+        }
+        let rest = [5, 6, 7].map(|n| {
+            let mut table = vec![0u32; WAYS[0][n] as usize];
+            let mut counts = [0u8; 13];
+            fill(&mut counts, 0, n, &mut table);
+            table
+        });
+        Tables { flush, rest }
+    })
+}
+
+// Visits every rank-count vector with `left` cards still to place.
+fn fill(counts: &mut [u8; 13], rank: usize, left: usize, table: &mut [u32]) {
+    if rank == 13 {
+        if left == 0 {
+            let n = counts.iter().map(|&c| c as usize).sum();
+            table[count_index(counts, n)] = counts_value(counts);
+        }
+        return;
+    }
+    for c in 0..=left.min(4) {
+        counts[rank] = c as u8;
+        fill(counts, rank + 1, left - c, table);
+    }
+    counts[rank] = 0;
+}
+
+/// `WAYS[i][k]`: rank-count vectors over ranks `i..13` holding `k` cards,
+/// at most four of each rank.
+const WAYS: [[u32; 8]; 14] = {
+    let mut ways = [[0u32; 8]; 14];
+    ways[13][0] = 1;
+    let mut i = 13;
+    while i > 0 {
+        i -= 1;
+        let mut k = 0;
+        while k < 8 {
+            let mut c = 0;
+            while c <= 4 && c <= k {
+                ways[i][k] += ways[i + 1][k - c];
+                c += 1;
+            }
+            k += 1;
+        }
+    }
+    ways
+};
+
+// Position of `counts` in the lexicographic order of all vectors with `n`
+// cards.
+fn count_index(counts: &[u8; 13], n: usize) -> usize {
+    let mut index = 0;
+    let mut left = n;
+    for (i, &c) in counts.iter().enumerate() {
+        for smaller in 0..c as usize {
+            index += WAYS[i + 1][left - smaller] as usize;
+        }
+        left -= c as usize;
+    }
+    index
+}
+
+// Highest card of a five-rank run in a 13-bit mask, as a rank value.
+fn straight_high(mask: u16) -> Option<u8> {
+    for high in (4..13).rev() {
+        let run = 0b11111 << (high - 4);
+        if mask & run == run {
+            return Some(high as u8 + 2);
+        }
+    }
+    // wheel: A-2-3-4-5
+    (mask & 0b1_0000_0000_1111 == 0b1_0000_0000_1111).then_some(5)
+}
+
+// Rank values of the set bits, highest first.
+fn top_ranks(mask: u16) -> [u8; 5] {
+    let mut out = [0u8; 5];
+    let mut i = 0;
+    for r in (0..13).rev() {
+        if i < 5 && mask & (1 << r) != 0 {
+            out[i] = r as u8 + 2;
+            i += 1;
+        }
+    }
+    out
+}
+
+fn flush_value(mask: u16) -> u32 {
+    match straight_high(mask) {
+        Some(high) => pack(Category::StraightFlush, [high, 0, 0, 0, 0]),
+        None => pack(Category::Flush, top_ranks(mask)),
+    }
+}
+
+fn counts_value(counts: &[u8; 13]) -> u32 {
+    // rank masks by multiplicity, in one pass; this runs for every entry
+    // of the tables
+    let (mut any, mut pairs_up, mut pairs, mut trips, mut quads) = (0u16, 0u16, 0u16, 0u16, 0u16);
+    for (r, &c) in counts.iter().enumerate() {
+        let bit = 1u16 << r;
+        match c {
+            0 => continue,
+            2 => pairs |= bit,
+            3 => trips |= bit,
+            4 => quads |= bit,
+            _ => {}
+        }
+        any |= bit;
+        if c >= 2 {
+            pairs_up |= bit;
+        }
+    }
+    // highest rank value in a mask, skipping `skip`
+    let best = |mask: u16, skip: u16| top_ranks(mask & !skip)[0];
+    let bit = |rank: u8| if rank == 0 { 0 } else { 1u16 << (rank - 2) };
+
+    if quads != 0 {
+        let q = best(quads, 0);
+        return pack(Category::FourOfAKind, [q, best(any, bit(q)), 0, 0, 0]);
+    }
+    if trips != 0 {
+        let t = best(trips, 0);
+        let p = best(pairs_up, bit(t));
+        if p != 0 {
+            return pack(Category::FullHouse, [t, p, 0, 0, 0]);
+        }
+    }
+    if let Some(high) = straight_high(any) {
+        return pack(Category::Straight, [high, 0, 0, 0, 0]);
+    }
+    if trips != 0 {
+        let t = best(trips, 0);
+        let rest = top_ranks(any & !bit(t));
+        return pack(Category::ThreeOfAKind, [t, rest[0], rest[1], 0, 0]);
+    }
+    if pairs.count_ones() >= 2 {
+        let [hi, lo, ..] = top_ranks(pairs);
+        let kicker = best(any, bit(hi) | bit(lo));
+        return pack(Category::TwoPair, [hi, lo, kicker, 0, 0]);
+    }
+    if pairs != 0 {
+        let p = best(pairs, 0);
+        let rest = top_ranks(any & !bit(p));
+        return pack(Category::OnePair, [p, rest[0], rest[1], rest[2], 0]);
+    }
+    pack(Category::HighCard, top_ranks(any))
+}
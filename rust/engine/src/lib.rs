@@ -1,12 +1,22 @@
 //! axm-engine: Poker engine core modules
 
+pub mod agent;
+pub mod blinds;
 pub mod cards;
 pub mod deck;
 pub mod engine;
+pub mod equity;
 pub mod errors;
+pub mod events;
 pub mod game;
 pub mod hand;
+pub mod hand_rank;
 pub mod logger;
 pub mod player;
 pub mod pot;
+pub mod process_agent;
+pub mod range;
 pub mod rules;
+pub mod selftest;
+pub mod session;
+pub mod verify;
@@ -1,8 +1,25 @@
+//! Hand history records and the JSONL logger.
+//!
+//! Records are written as schema version 3 (`SCHEMA_VERSION`), which writes
+//! cards as `"Ah"` strings; versions 1 and 2 wrote `{"suit","rank"}`
+//! objects, and both forms read back. Version 1 records, which have no
+//! `schema_version` field, read into the same type: a `"blinds"` pair or
+//! `{"sb","bb"}` object fills `sb` and `bb`, bare player ids become players
+//! without a starting stack, and fields no version knows are kept in
+//! `extra` and written back unchanged.
+
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::cards::Card;
+use crate::engine::{seat_id, EndReason};
+use crate::events::HandEvent;
 use crate::player::PlayerAction;
 
+/// Version written by this build.
+pub const SCHEMA_VERSION: u32 = 3;
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Street {
     Preflop,
@@ -16,36 +33,451 @@ pub struct ActionRecord {
     pub player_id: usize,
     pub street: Street,
     pub action: PlayerAction,
+    /// Chips the action put in; absent in version 1 records.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u32>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayerRecord {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_start: Option<u32>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::cards::compact"
+    )]
+    pub hole_cards: Vec<Card>,
+    /// Chips posted as an ante, before the blinds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ante: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawHandRecord")]
 pub struct HandRecord {
+    /// Version the record was read as; 1 when the field is missing.
+    pub schema_version: u32,
     pub hand_id: String,
     pub seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sb: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bb: Option<u32>,
+    /// The level's ante, when it has one; what each player actually posted
+    /// is on `players`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ante: Option<u32>,
+    /// Id of the player on the button.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub button: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub players: Vec<PlayerRecord>,
     pub actions: Vec<ActionRecord>,
+    /// Written as `"Ah"` strings; the derived form is also read.
+    #[serde(with = "crate::cards::compact")]
     pub board: Vec<Card>,
     pub result: Option<String>,
-    #[serde(default)]
     pub ts: Option<String>,
-    #[serde(default)]
     pub meta: Option<serde_json::Value>,
-    #[serde(default)]
     pub showdown: Option<ShowdownInfo>,
+    /// Chips won (positive) or lost per player id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_result: Option<BTreeMap<String, i64>>,
+    /// `EndReason::as_str` for engine hands; older files hold other
+    /// reasons such as `"player_bust"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_reason: Option<String>,
+    /// `deck::commitment` of the hand's shuffled deck.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deck_commitment: Option<String>,
+    /// Run the hand belongs to. Consecutive hands of the same session (or
+    /// of none) carry their stacks over; a hand dealt from fresh stacks
+    /// gets a session of its own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    /// Fields this version does not know about.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Default for HandRecord {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            hand_id: String::new(),
+            seed: None,
+            level: None,
+            sb: None,
+            bb: None,
+            ante: None,
+            button: None,
+            players: Vec::new(),
+            actions: Vec::new(),
+            board: Vec::new(),
+            result: None,
+            ts: None,
+            meta: None,
+            showdown: None,
+            net_result: None,
+            end_reason: None,
+            deck_commitment: None,
+            session: None,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+// Any schema version as written; see the module docs.
+#[derive(Deserialize)]
+struct RawHandRecord {
+    #[serde(default = "first_version")]
+    schema_version: u32,
+    hand_id: String,
+    seed: Option<u64>,
+    #[serde(default)]
+    level: Option<u8>,
+    #[serde(default)]
+    sb: Option<u32>,
+    #[serde(default)]
+    bb: Option<u32>,
+    #[serde(default)]
+    ante: Option<u32>,
+    #[serde(default)]
+    button: Option<String>,
+    #[serde(default)]
+    players: Vec<RawPlayer>,
+    actions: Vec<ActionRecord>,
+    #[serde(deserialize_with = "crate::cards::compact::deserialize")]
+    board: Vec<Card>,
+    result: Option<String>,
+    #[serde(default)]
+    ts: Option<String>,
+    #[serde(default)]
+    meta: Option<serde_json::Value>,
+    #[serde(default)]
+    showdown: Option<ShowdownInfo>,
+    #[serde(default)]
+    net_result: Option<BTreeMap<String, i64>>,
+    #[serde(default)]
+    end_reason: Option<String>,
+    #[serde(default)]
+    deck_commitment: Option<String>,
+    #[serde(default)]
+    session: Option<String>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawPlayer {
+    Id(String),
+    Full(PlayerRecord),
+}
+
+impl From<RawHandRecord> for HandRecord {
+    fn from(raw: RawHandRecord) -> Self {
+        let mut extra = raw.extra;
+        // version 1 blinds: [sb, bb] or {"sb": .., "bb": ..}
+        let blinds = extra.get("blinds").map(|b| {
+            let get = |key: &str, pos: usize| {
+                b.get(key)
+                    .or_else(|| b.get(pos))
+                    .and_then(|x| x.as_u64())
+                    .and_then(|x| u32::try_from(x).ok())
+            };
+            (get("sb", 0), get("bb", 1))
+        });
+        if let Some((Some(_), Some(_))) = blinds {
+            extra.remove("blinds");
+        }
+        let (sb, bb) = blinds.unwrap_or_default();
+        Self {
+            schema_version: raw.schema_version,
+            hand_id: raw.hand_id,
+            seed: raw.seed,
+            level: raw.level,
+            sb: raw.sb.or(sb),
+            bb: raw.bb.or(bb),
+            ante: raw.ante,
+            button: raw.button,
+            players: raw
+                .players
+                .into_iter()
+                .map(|p| match p {
+                    RawPlayer::Id(id) => PlayerRecord {
+                        id,
+                        ..Default::default()
+                    },
+                    RawPlayer::Full(p) => p,
+                })
+                .collect(),
+            actions: raw.actions,
+            board: raw.board,
+            result: raw.result,
+            ts: raw.ts,
+            meta: raw.meta,
+            showdown: raw.showdown,
+            net_result: raw.net_result,
+            end_reason: raw.end_reason,
+            deck_commitment: raw.deck_commitment,
+            session: raw.session,
+            extra,
+        }
+    }
 }
 
 pub fn format_hand_id(yyyymmdd: &str, seq: u32) -> String {
     format!("{}-{:06}", yyyymmdd, seq)
 }
 
-use chrono::{SecondsFormat, Utc};
-use std::fs::{create_dir_all, File};
-use std::io::{BufWriter, Write};
-use std::path::Path;
+/// Assembles records from a stream of `HandEvent`s, such as the engine's
+/// own `Engine::events` or a receiver from `Engine::subscribe`. Records
+/// have no `hand_id`, `seed` or `deck_commitment`; those are not events.
+#[derive(Debug, Default)]
+pub struct RecordBuilder {
+    current: Option<HandRecord>,
+}
+
+impl RecordBuilder {
+    /// Adds one event and returns the record once its hand has ended.
+    /// Events before the first `HandStarted` are ignored.
+    pub fn push(&mut self, event: &HandEvent) -> Option<HandRecord> {
+        if let HandEvent::HandStarted {
+            button,
+            level,
+            sb,
+            bb,
+            ante,
+            stacks,
+        } = event
+        {
+            self.current = Some(HandRecord {
+                level: Some(*level),
+                sb: Some(*sb),
+                bb: Some(*bb),
+                ante: (*ante > 0).then_some(*ante),
+                button: Some(seat_id(*button)),
+                players: (0..stacks.len())
+                    .map(|seat| PlayerRecord {
+                        id: seat_id(seat),
+                        stack_start: Some(stacks[seat]),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            });
+            return None;
+        }
+        let record = self.current.as_mut()?;
+        match event {
+            HandEvent::BlindsPosted { antes, .. } => {
+                for (player, &posted) in record.players.iter_mut().zip(antes) {
+                    player.ante = (posted > 0).then_some(posted);
+                }
+            }
+            HandEvent::CardsDealt {
+                seat: Some(seat),
+                cards,
+                ..
+            } => {
+                if let Some(player) = record.players.get_mut(*seat) {
+                    player.hole_cards = cards.clone();
+                }
+            }
+            HandEvent::CardsDealt {
+                seat: None, cards, ..
+            } => record.board.extend_from_slice(cards),
+            HandEvent::ActionTaken {
+                seat,
+                street,
+                action,
+                amount,
+                ..
+            } => record.actions.push(ActionRecord {
+                player_id: *seat,
+                street: *street,
+                action: action.clone(),
+                amount: Some(*amount),
+            }),
+            HandEvent::HandEnded {
+                reason,
+                winners,
+                net,
+                ..
+            } => {
+                // a split pot has no single winner
+                if let [w] = winners[..] {
+                    record.result = Some(seat_id(w));
+                }
+                if *reason == EndReason::Showdown {
+                    record.showdown = Some(ShowdownInfo {
+                        winners: winners.clone(),
+                        notes: None,
+                    });
+                }
+                record.net_result = Some(
+                    net.iter()
+                        .enumerate()
+                        .map(|(seat, n)| (seat_id(seat), *n))
+                        .collect(),
+                );
+                record.end_reason = Some(reason.as_str().to_string());
+                return self.current.take();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// The hand in progress, if one has started and not yet ended.
+    pub fn current(&self) -> Option<&HandRecord> {
+        self.current.as_ref()
+    }
+}
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Source of the current time for dates and timestamps. Any
+/// `Fn() -> DateTime<Utc>` is a clock, so tests can pin or advance it.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<F: Fn() -> DateTime<Utc>> Clock for F {
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+pub struct SystemClock;
 
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// When a daily logger starts its next file; unset limits never rotate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rotation {
+    /// Bytes after which the current file is closed.
+    pub max_bytes: Option<u64>,
+    /// Hands after which the current file is closed.
+    pub max_hands: Option<u32>,
+}
+
+/// Writes hand records as JSONL, either to one file (`create`) or to
+/// `<root>/YYYYMMDD/hands-NNNN.jsonl` (`daily`).
+///
+/// A daily logger takes the date from its clock, moves to a new directory
+/// when the date changes and to a new numbered file when `Rotation` says
+/// so. Opening a day that already has files appends to the last one and
+/// continues the sequence after the highest `hand_id` found, so ids from
+/// `next_id` never repeat within a root.
+///
+/// A file opened for appending first loses any torn final line left by an
+/// interrupted write (see `truncate_torn_tail`).
+///
+/// A path ending in `.zst` is written as a series of independent zstd
+/// frames, each holding whole lines. A frame is finished once it holds
+/// `ZSTD_FRAME_BYTES` of records, on `flush`, `sync` and when the logger is
+/// dropped, so an interrupted run loses at most the frame in progress and
+/// appending just adds frames after the last complete one.
 pub struct HandLogger {
-    writer: Option<BufWriter<File>>,
+    writer: Option<Sink>,
     date: String,
     seq: u32,
+    clock: Box<dyn Clock>,
+    daily: Option<Daily>,
+    sync_every: Option<u32>,
+    unsynced: u32,
+    truncated: u64,
+    timestamps: bool,
+}
+
+/// Uncompressed bytes collected before a zstd frame is finished.
+pub const ZSTD_FRAME_BYTES: usize = 1 << 20;
+const ZSTD_LEVEL: i32 = 3;
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Zstd { file: File, pending: Vec<u8> },
+}
+
+impl Sink {
+    fn open(file: File, path: &Path) -> Self {
+        if is_zstd(path) {
+            Sink::Zstd {
+                file,
+                pending: Vec::new(),
+            }
+        } else {
+            Sink::Plain(BufWriter::new(file))
+        }
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        match self {
+            Sink::Plain(w) => {
+                w.write_all(line)?;
+                w.write_all(b"\n")?;
+                w.flush()
+            }
+            Sink::Zstd { pending, .. } => {
+                pending.extend_from_slice(line);
+                pending.push(b'\n');
+                if pending.len() >= ZSTD_FRAME_BYTES {
+                    self.flush()?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // For zstd, ends the current frame.
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Sink::Plain(w) => w.flush(),
+            Sink::Zstd { file, pending } => {
+                if pending.is_empty() {
+                    return Ok(());
+                }
+                let frame = zstd::bulk::compress(pending, ZSTD_LEVEL)?;
+                file.write_all(&frame)?;
+                pending.clear();
+                Ok(())
+            }
+        }
+    }
+
+    fn sync(&mut self) -> std::io::Result<()> {
+        self.flush()?;
+        match self {
+            Sink::Plain(w) => w.get_ref().sync_data(),
+            Sink::Zstd { file, .. } => file.sync_data(),
+        }
+    }
+}
+
+struct Daily {
+    root: PathBuf,
+    rotation: Rotation,
+    part: u32,
+    path: PathBuf,
+    bytes: u64,
+    hands: u32,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -62,11 +494,43 @@ impl HandLogger {
                 let _ = create_dir_all(parent);
             }
         }
-        let f = File::create(path)?;
+        let f = File::create(path.as_ref())?;
         Ok(Self {
-            writer: Some(BufWriter::new(f)),
-            date: "19700101".to_string(),
+            writer: Some(Sink::open(f, path.as_ref())),
+            date: date_of(&SystemClock),
             seq: 0,
+            clock: Box::new(SystemClock),
+            daily: None,
+            sync_every: None,
+            unsynced: 0,
+            truncated: 0,
+            timestamps: true,
+        })
+    }
+
+    /// Appends to `path`, creating it if needed. Ids continue after the
+    /// highest of today's ids already in the file.
+    pub fn append<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                create_dir_all(parent)?;
+            }
+        }
+        let truncated = truncate_torn_tail(path)?;
+        let f = OpenOptions::new().create(true).append(true).open(path)?;
+        let date = date_of(&SystemClock);
+        let seq = last_seq(path, &date)?;
+        Ok(Self {
+            writer: Some(Sink::open(f, path)),
+            date,
+            seq,
+            clock: Box::new(SystemClock),
+            daily: None,
+            sync_every: None,
+            unsynced: 0,
+            truncated,
+            timestamps: true,
         })
     }
 
@@ -75,26 +539,420 @@ impl HandLogger {
             writer: None,
             date: date.to_string(),
             seq: 0,
+            clock: Box::new(SystemClock),
+            daily: None,
+            sync_every: None,
+            unsynced: 0,
+            truncated: 0,
+            timestamps: true,
+        }
+    }
+
+    /// Daily files under `root` (e.g. `data/hands`), dated by the system
+    /// clock.
+    pub fn daily<P: AsRef<Path>>(root: P, rotation: Rotation) -> std::io::Result<Self> {
+        Self::daily_with_clock(root, rotation, SystemClock)
+    }
+
+    pub fn daily_with_clock<P: AsRef<Path>>(
+        root: P,
+        rotation: Rotation,
+        clock: impl Clock + 'static,
+    ) -> std::io::Result<Self> {
+        let date = date_of(&clock);
+        let mut logger = Self {
+            writer: None,
+            date: date.clone(),
+            seq: 0,
+            clock: Box::new(clock),
+            daily: Some(Daily {
+                root: root.as_ref().to_path_buf(),
+                rotation,
+                part: 0,
+                path: PathBuf::new(),
+                bytes: 0,
+                hands: 0,
+            }),
+            sync_every: None,
+            unsynced: 0,
+            truncated: 0,
+            timestamps: true,
+        };
+        logger.open_day(date)?;
+        Ok(logger)
+    }
+
+    /// Syncs the file to disk after every `hands` records; `None` (the
+    /// default) leaves it to the OS.
+    pub fn set_sync_every(&mut self, hands: Option<u32>) {
+        self.sync_every = hands.filter(|&n| n > 0);
+    }
+
+    /// Whether records without a `ts` get the clock's time (the default).
+    pub fn set_timestamps(&mut self, on: bool) {
+        self.timestamps = on;
+    }
+
+    /// Writes out buffered records, finishing the current zstd frame.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.writer {
+            Some(w) => w.flush(),
+            None => Ok(()),
         }
     }
 
+    /// Flushes and syncs whatever has been written so far.
+    pub fn sync(&mut self) -> std::io::Result<()> {
+        if let Some(w) = &mut self.writer {
+            w.sync()?;
+        }
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Bytes of torn final lines dropped when files were opened.
+    pub fn truncated_bytes(&self) -> u64 {
+        self.truncated
+    }
+
+    /// File currently written to by a daily logger.
+    pub fn path(&self) -> Option<&Path> {
+        self.daily.as_ref().map(|d| d.path.as_path())
+    }
+
     pub fn next_id(&mut self) -> String {
+        // a failed roll-over surfaces on the next write
+        let _ = self.roll_date();
         self.seq += 1;
         format_hand_id(&self.date, self.seq)
     }
 
+    /// Gives `record` the next id, writes it and returns the id.
+    pub fn log(&mut self, mut record: HandRecord) -> std::io::Result<String> {
+        record.hand_id = self.next_id();
+        self.write(&record)?;
+        Ok(record.hand_id)
+    }
+
     pub fn write(&mut self, record: &HandRecord) -> std::io::Result<()> {
+        self.roll_date()?;
+        self.rotate_if_full()?;
         // inject timestamp if missing
         let mut rec = record.clone();
-        if rec.ts.is_none() {
-            rec.ts = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
+        rec.schema_version = SCHEMA_VERSION;
+        if rec.ts.is_none() && self.timestamps {
+            rec.ts = Some(self.clock.now().to_rfc3339_opts(SecondsFormat::Secs, true));
         }
         let line = serde_json::to_string(&rec).expect("serialize");
         if let Some(w) = &mut self.writer {
-            w.write_all(line.as_bytes())?;
-            w.write_all(b"\n")?;
-            w.flush()?;
+            w.write_line(line.as_bytes())?;
+        }
+        if let Some(d) = &mut self.daily {
+            d.bytes += line.len() as u64 + 1;
+            d.hands += 1;
+        }
+        self.unsynced += 1;
+        if self.sync_every.is_some_and(|n| self.unsynced >= n) {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    fn roll_date(&mut self) -> std::io::Result<()> {
+        if self.daily.is_none() {
+            return Ok(());
+        }
+        let today = date_of(self.clock.as_ref());
+        if today != self.date {
+            self.open_day(today)?;
+        }
+        Ok(())
+    }
+
+    fn rotate_if_full(&mut self) -> std::io::Result<()> {
+        let Some(d) = &self.daily else {
+            return Ok(());
+        };
+        let full = d.rotation.max_hands.is_some_and(|m| d.hands >= m)
+            || d.rotation.max_bytes.is_some_and(|m| d.bytes >= m);
+        if full {
+            let part = d.part + 1;
+            self.open_part(part)?;
         }
         Ok(())
     }
+
+    // Switches to `date`'s directory, resuming its last file and sequence.
+    fn open_day(&mut self, date: String) -> std::io::Result<()> {
+        let Some(d) = &self.daily else {
+            return Ok(());
+        };
+        let dir = d.root.join(&date);
+        create_dir_all(&dir)?;
+        let mut parts: Vec<(u32, PathBuf)> = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if let Some(n) = part_number(&path) {
+                parts.push((n, path));
+            }
+        }
+        parts.sort();
+        let mut seq = 0;
+        for (_, path) in &parts {
+            seq = seq.max(last_seq(path, &date)?);
+        }
+        self.date = date;
+        self.seq = seq;
+        self.open_part(parts.last().map_or(1, |(n, _)| *n))?;
+        // the resumed file may already be over the limits
+        self.rotate_if_full()
+    }
+
+    fn open_part(&mut self, part: u32) -> std::io::Result<()> {
+        let Some(d) = &mut self.daily else {
+            return Ok(());
+        };
+        if let Some(w) = &mut self.writer {
+            if self.unsynced > 0 && self.sync_every.is_some() {
+                w.sync()?;
+                self.unsynced = 0;
+            } else {
+                w.flush()?;
+            }
+        }
+        let path = d
+            .root
+            .join(&self.date)
+            .join(format!("hands-{:04}.jsonl", part));
+        self.truncated += truncate_torn_tail(&path)?;
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        d.bytes = file.metadata()?.len();
+        d.hands = if d.bytes == 0 { 0 } else { count_lines(&path)? };
+        d.part = part;
+        d.path = path;
+        self.writer = Some(Sink::Plain(BufWriter::new(file)));
+        Ok(())
+    }
+}
+
+impl Drop for HandLogger {
+    fn drop(&mut self) {
+        // finish the open zstd frame; errors surface through `flush`
+        let _ = self.flush();
+    }
+}
+
+/// Cuts `path` back to its last newline, dropping a final line that an
+/// interrupted write left without one. A `.zst` file is cut back to the
+/// end of its last complete frame instead. Returns the bytes removed; a
+/// missing file is left alone.
+pub fn truncate_torn_tail<P: AsRef<Path>>(path: P) -> std::io::Result<u64> {
+    const CHUNK: u64 = 64 * 1024;
+
+    let path = path.as_ref();
+    let mut file = match OpenOptions::new().read(true).write(true).open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    if is_zstd(path) {
+        return truncate_torn_frame(&mut file);
+    }
+    let len = file.metadata()?.len();
+    let mut end = len;
+    let mut buf = Vec::new();
+    // scan backwards for the last newline
+    let keep = loop {
+        if end == 0 {
+            break 0;
+        }
+        let start = end.saturating_sub(CHUNK);
+        buf.resize((end - start) as usize, 0);
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut buf)?;
+        if let Some(i) = buf.iter().rposition(|&b| b == b'\n') {
+            break start + i as u64 + 1;
+        }
+        end = start;
+    };
+    if keep < len {
+        file.set_len(keep)?;
+        file.sync_data()?;
+    }
+    Ok(len - keep)
+}
+
+// Walks the frames from the start and cuts at the first one that is
+// incomplete or unreadable.
+fn truncate_torn_frame(file: &mut File) -> std::io::Result<u64> {
+    let len = file.metadata()?.len();
+    let mut keep = 0u64;
+    while let Some(end) = frame_end(file, keep, len)? {
+        keep = end;
+    }
+    if keep < len {
+        file.set_len(keep)?;
+        file.sync_data()?;
+    }
+    Ok(len - keep)
+}
+
+// End of the zstd frame starting at `start`, or `None` if the file ends
+// inside it or no frame starts there. Only the frame and block headers are
+// read; block contents are stepped over by their declared sizes.
+fn frame_end(file: &mut File, start: u64, len: u64) -> std::io::Result<Option<u64>> {
+    const FRAME_MAGIC: u32 = 0xFD2F_B528;
+    const SKIPPABLE_MAGIC: u32 = 0x184D_2A50;
+
+    let mut read = |pos: u64, buf: &mut [u8]| -> std::io::Result<bool> {
+        if pos + buf.len() as u64 > len {
+            return Ok(false);
+        }
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(buf)?;
+        Ok(true)
+    };
+    let mut word = [0; 4];
+    if !read(start, &mut word)? {
+        return Ok(None);
+    }
+    let magic = u32::from_le_bytes(word);
+    if magic & 0xFFFF_FFF0 == SKIPPABLE_MAGIC {
+        if !read(start + 4, &mut word)? {
+            return Ok(None);
+        }
+        let end = start + 8 + u64::from(u32::from_le_bytes(word));
+        return Ok((end <= len).then_some(end));
+    }
+    if magic != FRAME_MAGIC {
+        return Ok(None);
+    }
+    let mut descriptor = [0; 1];
+    if !read(start + 4, &mut descriptor)? {
+        return Ok(None);
+    }
+    let descriptor = descriptor[0];
+    if descriptor & 0x08 != 0 {
+        // reserved bit
+        return Ok(None);
+    }
+    let single_segment = descriptor & 0x20 != 0;
+    let window = u64::from(!single_segment);
+    let dictionary = [0, 1, 2, 4][usize::from(descriptor & 0x03)];
+    let content_size = match descriptor >> 6 {
+        0 => u64::from(single_segment),
+        1 => 2,
+        2 => 4,
+        _ => 8,
+    };
+    let mut pos = start + 5 + window + dictionary + content_size;
+    loop {
+        let mut header = [0; 3];
+        if !read(pos, &mut header)? {
+            return Ok(None);
+        }
+        let header = u32::from_le_bytes([header[0], header[1], header[2], 0]);
+        let size = u64::from(header >> 3);
+        pos += 3 + match (header >> 1) & 0x03 {
+            // raw and compressed blocks store `size` bytes, RLE just one
+            0 | 2 => size,
+            1 => 1,
+            _ => return Ok(None),
+        };
+        if header & 0x01 != 0 {
+            break;
+        }
+    }
+    if descriptor & 0x04 != 0 {
+        // content checksum
+        pos += 4;
+    }
+    Ok((pos <= len).then_some(pos))
+}
+
+/// Whether `path` is written and read as zstd (`.zst`).
+pub fn is_zstd(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "zst")
+}
+
+fn date_of(clock: &dyn Clock) -> String {
+    clock.now().format("%Y%m%d").to_string()
+}
+
+// `hands-0003.jsonl` -> 3
+fn part_number(path: &Path) -> Option<u32> {
+    let name = path.file_name()?.to_str()?;
+    name.strip_prefix("hands-")?
+        .strip_suffix(".jsonl")?
+        .parse()
+        .ok()
+}
+
+fn count_lines(path: &Path) -> std::io::Result<u32> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut lines = 0;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(lines);
+        }
+        lines += buf.iter().filter(|&&b| b == b'\n').count() as u32;
+        let n = buf.len();
+        reader.consume(n);
+    }
+}
+
+// Highest sequence number among `date`'s ids in `path`. Records are
+// appended in order, so for a plain file the tail decides: once it holds
+// any id, earlier lines cannot add to today's sequence. A `.zst` file, or
+// a tail without a single whole record, is streamed line by line.
+fn last_seq(path: &Path, date: &str) -> std::io::Result<u32> {
+    const TAIL: u64 = 64 * 1024;
+
+    #[derive(Deserialize)]
+    struct Id {
+        hand_id: String,
+    }
+    // `None` for a line without an id, `Some(0)` for another day's id
+    let seq_of = |line: &str| {
+        let id = serde_json::from_str::<Id>(line).ok()?;
+        Some(
+            id.hand_id
+                .strip_prefix(date)
+                .and_then(|rest| rest.strip_prefix('-'))
+                .and_then(|n| n.parse::<u32>().ok())
+                .unwrap_or(0),
+        )
+    };
+    let stream = |reader: &mut dyn BufRead| -> std::io::Result<u32> {
+        let mut seq = 0;
+        for line in reader.split(b'\n') {
+            seq = seq.max(seq_of(&String::from_utf8_lossy(&line?)).unwrap_or(0));
+        }
+        Ok(seq)
+    };
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok(0);
+    }
+    if is_zstd(path) {
+        return stream(&mut BufReader::new(zstd::stream::read::Decoder::new(file)?));
+    }
+    let start = len.saturating_sub(TAIL);
+    file.seek(SeekFrom::Start(start))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+    if let Some(seq) = String::from_utf8_lossy(&tail)
+        .lines()
+        .filter_map(seq_of)
+        .max()
+    {
+        return Ok(seq);
+    }
+    if start == 0 {
+        return Ok(0);
+    }
+    file.seek(SeekFrom::Start(0))?;
+    stream(&mut BufReader::new(file))
 }
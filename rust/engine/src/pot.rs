@@ -1,23 +1,71 @@
+use crate::rules::MIN_CHIP_UNIT;
+
+/// One layer of the pot and the seats that can win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u32,
+    /// Seat indices, ascending.
+    pub eligible: Vec<usize>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct PotManager {
     main: u32,
     sides: Vec<u32>,
+    pots: Vec<Pot>,
 }
 
 impl PotManager {
     pub fn from_contributions(contrib: [u32; 2]) -> Self {
-        let a = contrib[0];
-        let b = contrib[1];
-        let shared = a.min(b);
-        let main = shared * 2;
-        let mut sides = Vec::new();
-        if a != b {
-            let side = a.max(b) - shared;
-            if side > 0 {
-                sides.push(side);
+        Self::build(&contrib, &[false, false])
+    }
+
+    /// Layers per-seat contributions into a main pot and side pots.
+    ///
+    /// Each all-in level of a live player caps a layer; folded seats pay into
+    /// the layers but are never eligible. Chips above the deepest live level
+    /// go to the last pot.
+    pub fn build(contributions: &[u32], folded: &[bool]) -> Self {
+        let live = |seat: usize| !folded.get(seat).copied().unwrap_or(false);
+        let mut levels: Vec<u32> = contributions
+            .iter()
+            .enumerate()
+            .filter(|&(seat, &c)| live(seat) && c > 0)
+            .map(|(_, &c)| c)
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut pots: Vec<Pot> = Vec::new();
+        let mut prev = 0;
+        for &level in &levels {
+            let amount = contributions
+                .iter()
+                .map(|&c| c.min(level) - c.min(prev))
+                .sum();
+            let eligible: Vec<usize> = (0..contributions.len())
+                .filter(|&seat| live(seat) && contributions[seat] >= level)
+                .collect();
+            match pots.last_mut() {
+                Some(last) if last.eligible == eligible => last.amount += amount,
+                _ => pots.push(Pot { amount, eligible }),
+            }
+            prev = level;
+        }
+        let leftover: u32 = contributions.iter().map(|&c| c.saturating_sub(prev)).sum();
+        if leftover > 0 {
+            match pots.last_mut() {
+                Some(last) => last.amount += leftover,
+                None => pots.push(Pot {
+                    amount: leftover,
+                    eligible: Vec::new(),
+                }),
             }
         }
-        Self { main, sides }
+
+        let main = pots.first().map(|p| p.amount).unwrap_or(0);
+        let sides = pots.iter().skip(1).map(|p| p.amount).collect();
+        Self { main, sides, pots }
     }
 
     pub fn main_pot(&self) -> u32 {
@@ -26,4 +74,36 @@ impl PotManager {
     pub fn side_pots(&self) -> &[u32] {
         &self.sides
     }
+    /// Main pot first, then side pots in order of increasing depth.
+    pub fn pots(&self) -> &[Pot] {
+        &self.pots
+    }
+    pub fn total(&self) -> u32 {
+        self.pots.iter().map(|p| p.amount).sum()
+    }
+}
+
+/// Splits `amount` into `ways` shares in whole chip units. Odd units go to
+/// the first shares, so callers list winners starting left of the button.
+pub fn split_pot(amount: u32, ways: usize) -> Vec<u32> {
+    if ways == 0 {
+        return Vec::new();
+    }
+    let ways = ways as u32;
+    let units = amount / MIN_CHIP_UNIT;
+    let base = units / ways * MIN_CHIP_UNIT;
+    let odd_units = units % ways;
+    (0..ways)
+        .map(|i| {
+            let mut share = base;
+            if i < odd_units {
+                share += MIN_CHIP_UNIT;
+            }
+            if i == 0 {
+                // anything below one unit stays with the first winner
+                share += amount % MIN_CHIP_UNIT;
+            }
+            share
+        })
+        .collect()
 }
@@ -0,0 +1,184 @@
+//! Agents running in a separate process, talking line-delimited JSON.
+//!
+//! For every decision the engine writes one request line to the bot's stdin:
+//!
+//! ```json
+//! {"type":"act","seq":7,"seat":0,"hole_cards":[...],"board":[...],
+//!  "street":"Flop","button":0,"blinds":[50,100],"stacks":[19900,19900],
+//!  "pot":200,"to_call":0,"actions":[...],"legal":{"fold":true,"check":true,...}}
+//! ```
+//!
+//! and expects one reply line on stdout echoing `seq` with a `PlayerAction`:
+//! `{"seq":7,"action":"Call"}` or `{"seq":7,"action":{"Raise":200}}`. A reply
+//! that is late, unreadable or illegal is replaced by check (or fold when
+//! facing a bet) and counted as a fault; a late reply that turns up during a
+//! later request is recognised by its `seq` and dropped. Anything the bot
+//! writes to stderr is passed through.
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::{Agent, Observation};
+use crate::player::PlayerAction;
+
+/// How long a bot may think before its turn is played for it.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+struct Request<'a, 'b> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    seq: u64,
+    #[serde(flatten)]
+    obs: &'a Observation<'b>,
+}
+
+#[derive(Deserialize)]
+struct Reply {
+    seq: u64,
+    action: PlayerAction,
+}
+
+/// Why a reply was replaced by the fallback action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    Timeout,
+    Exited,
+    BadReply(String),
+    Illegal(PlayerAction),
+}
+
+pub struct ProcessAgent {
+    name: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<String>,
+    timeout: Duration,
+    /// Number of the last request sent.
+    seq: u64,
+    faults: Vec<Fault>,
+}
+
+impl ProcessAgent {
+    /// Starts `command` (program and whitespace-separated arguments, no
+    /// shell) with piped stdin/stdout.
+    pub fn spawn(command: &str, timeout: Duration) -> std::io::Result<Self> {
+        let mut parts = command.split_whitespace();
+        let program = parts.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "empty bot command")
+        })?;
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| std::io::Error::other("bot stdout unavailable"))?;
+        // a reader thread lets us wait on replies with a timeout
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            name: format!("cmd:{}", command),
+            child,
+            stdin,
+            lines,
+            timeout,
+            seq: 0,
+            faults: Vec::new(),
+        })
+    }
+
+    /// Faults so far, oldest first.
+    pub fn faults(&self) -> &[Fault] {
+        &self.faults
+    }
+
+    fn ask(&mut self, obs: &Observation<'_>) -> Result<PlayerAction, Fault> {
+        self.seq += 1;
+        let request = Request {
+            kind: "act",
+            seq: self.seq,
+            obs,
+        };
+        let line = serde_json::to_string(&request).map_err(|e| Fault::BadReply(e.to_string()))?;
+        let stdin = self.stdin.as_mut().ok_or(Fault::Exited)?;
+        writeln!(stdin, "{}", line)
+            .and_then(|_| stdin.flush())
+            .map_err(|_| Fault::Exited)?;
+        let deadline = Instant::now() + self.timeout;
+        let reply = loop {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(wait) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Err(Fault::Timeout),
+                Err(RecvTimeoutError::Disconnected) => return Err(Fault::Exited),
+            };
+            let Ok(reply) = serde_json::from_str::<Reply>(&line) else {
+                return Err(Fault::BadReply(line));
+            };
+            // the answer to a request that already timed out
+            if reply.seq < self.seq {
+                continue;
+            }
+            if reply.seq > self.seq {
+                return Err(Fault::BadReply(line));
+            }
+            break reply;
+        };
+        if !obs.allows(&reply.action) {
+            return Err(Fault::Illegal(reply.action));
+        }
+        Ok(reply.action)
+    }
+}
+
+impl Agent for ProcessAgent {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fault_count(&self) -> usize {
+        self.faults.len()
+    }
+
+    fn act(&mut self, obs: &Observation<'_>) -> PlayerAction {
+        match self.ask(obs) {
+            Ok(action) => action,
+            Err(fault) => {
+                if fault == Fault::Exited {
+                    self.stdin = None;
+                }
+                self.faults.push(fault);
+                if obs.legal.check {
+                    PlayerAction::Check
+                } else {
+                    PlayerAction::Fold
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ProcessAgent {
+    fn drop(&mut self) {
+        self.stdin = None;
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,488 @@
+//! Hand ranges in the usual shorthand, and range-vs-range equity.
+//!
+//! A range is a comma-separated list of:
+//!
+//! - hand classes: `AA`, `AKs`, `AKo`, `AK` (suited and offsuit)
+//! - open ends: `22+` (pairs up to AA), `A2s+` / `KTo+` (kicker up to one
+//!   below the top card)
+//! - spans: `22-55`, `A2s-A5s`
+//! - exact combos: `AhKh`
+//! - a share of all hands by preflop rank: `50%`
+//!
+//! Any item may carry a weight, `AKo:0.5`, the fraction of its combos that
+//! are in the range. Later items override earlier ones.
+
+use std::str::FromStr;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use thiserror::Error;
+
+use crate::cards::{all_ranks, all_suits, full_deck, Card, CardParseError, Rank};
+use crate::equity::{self, EquityError, EquityOptions};
+
+pub type Combo = [Card; 2];
+
+/// Total number of two-card combos.
+pub const ALL_COMBOS: usize = 1326;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RangeError {
+    #[error("invalid range item {0:?}")]
+    InvalidItem(String),
+    #[error("invalid weight {0:?} (expected 0 to 1)")]
+    InvalidWeight(String),
+    #[error(transparent)]
+    Card(#[from] CardParseError),
+    #[error("range {0} has no combos left after card removal")]
+    Empty(usize),
+    #[error("no non-conflicting matchups: the ranges always share a card")]
+    NoMatchups,
+    #[error(transparent)]
+    Equity(#[from] EquityError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Suitedness {
+    Pair,
+    Suited,
+    Offsuit,
+    Any,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    combos: Vec<(Combo, f64)>,
+}
+
+impl Range {
+    /// Combos and their weights, in insertion order.
+    pub fn combos(&self) -> &[(Combo, f64)] {
+        &self.combos
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Sum of weights, i.e. the number of combos counting partial ones.
+    pub fn weighted_len(&self) -> f64 {
+        self.combos.iter().map(|(_, w)| w).sum()
+    }
+
+    /// Adds a combo or replaces its weight. A zero weight removes it.
+    pub fn insert(&mut self, combo: Combo, weight: f64) {
+        let combo = normalize(combo);
+        self.combos.retain(|(c, _)| *c != combo);
+        if weight > 0.0 {
+            self.combos.push((combo, weight));
+        }
+    }
+
+    /// The range with every combo that uses one of `cards` removed.
+    pub fn without(&self, cards: &[Card]) -> Range {
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter(|(c, _)| !cards.contains(&c[0]) && !cards.contains(&c[1]))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// The best `pct` percent of all combos by preflop rank.
+    pub fn top_percent(pct: f64) -> Range {
+        let target = (pct.clamp(0.0, 100.0) / 100.0 * ALL_COMBOS as f64).round() as usize;
+        let mut range = Range::default();
+        for (hi, lo, kind) in preflop_order() {
+            if range.len() >= target {
+                break;
+            }
+            for combo in class_combos(hi, lo, kind) {
+                range.insert(combo, 1.0);
+            }
+        }
+        range
+    }
+
+    fn add_item(&mut self, item: &str) -> Result<(), RangeError> {
+        let (body, weight) = match item.split_once(':') {
+            Some((body, w)) => {
+                let weight: f64 = w
+                    .trim()
+                    .parse()
+                    .map_err(|_| RangeError::InvalidWeight(w.trim().to_string()))?;
+                if !(0.0..=1.0).contains(&weight) {
+                    return Err(RangeError::InvalidWeight(w.trim().to_string()));
+                }
+                (body.trim(), weight)
+            }
+            None => (item, 1.0),
+        };
+        for combo in expand(body)? {
+            self.insert(combo, weight);
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::default();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            range.add_item(item)?;
+        }
+        Ok(range)
+    }
+}
+
+fn normalize(combo: Combo) -> Combo {
+    if combo[0] >= combo[1] {
+        combo
+    } else {
+        [combo[1], combo[0]]
+    }
+}
+
+fn expand(body: &str) -> Result<Vec<Combo>, RangeError> {
+    let bad = || RangeError::InvalidItem(body.to_string());
+    if let Some(pct) = body.strip_suffix('%') {
+        let pct: f64 = pct.trim().parse().map_err(|_| bad())?;
+        return Ok(Range::top_percent(pct)
+            .combos
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect());
+    }
+    // exact combo, e.g. AhKh
+    if body.len() == 4 {
+        if let Ok(cards) = crate::cards::parse_cards(body) {
+            if cards[0] == cards[1] {
+                return Err(bad());
+            }
+            return Ok(vec![[cards[0], cards[1]]]);
+        }
+    }
+    if let Some(open) = body.strip_suffix('+') {
+        let (hi, lo, kind) = parse_class(open).ok_or_else(bad)?;
+        return Ok(match kind {
+            Suitedness::Pair => span(lo as u8..=Rank::Ace as u8, |r| (r, r), kind),
+            _ => span(lo as u8..=hi as u8 - 1, |r| (hi, r), kind),
+        });
+    }
+    if let Some((from, to)) = body.split_once('-') {
+        let (h1, l1, k1) = parse_class(from).ok_or_else(bad)?;
+        let (h2, l2, k2) = parse_class(to).ok_or_else(bad)?;
+        if k1 != k2 {
+            return Err(bad());
+        }
+        let (a, b) = (l1.min(l2) as u8, l1.max(l2) as u8);
+        return match k1 {
+            Suitedness::Pair => Ok(span(a..=b, |r| (r, r), k1)),
+            _ if h1 == h2 => Ok(span(a..=b, |r| (h1, r), k1)),
+            _ => Err(bad()),
+        };
+    }
+    let (hi, lo, kind) = parse_class(body).ok_or_else(bad)?;
+    Ok(class_combos(hi, lo, kind))
+}
+
+fn span(
+    ranks: std::ops::RangeInclusive<u8>,
+    class: impl Fn(Rank) -> (Rank, Rank),
+    kind: Suitedness,
+) -> Vec<Combo> {
+    ranks
+        .flat_map(|r| {
+            let (hi, lo) = class(Rank::from_u8(r));
+            class_combos(hi, lo, kind)
+        })
+        .collect()
+}
+
+// "AKs" → (Ace, King, Suited); the higher rank always comes first.
+fn parse_class(s: &str) -> Option<(Rank, Rank, Suitedness)> {
+    let mut chars = s.chars();
+    let a = Rank::from_char(chars.next()?).ok()?;
+    let b = Rank::from_char(chars.next()?).ok()?;
+    let suffix = chars.next();
+    if chars.next().is_some() {
+        return None;
+    }
+    let (hi, lo) = (a.max(b), a.min(b));
+    let kind = match (hi == lo, suffix) {
+        (true, None) => Suitedness::Pair,
+        (false, None) => Suitedness::Any,
+        (false, Some('s' | 'S')) => Suitedness::Suited,
+        (false, Some('o' | 'O')) => Suitedness::Offsuit,
+        _ => return None,
+    };
+    Some((hi, lo, kind))
+}
+
+fn class_combos(hi: Rank, lo: Rank, kind: Suitedness) -> Vec<Combo> {
+    let mut out = Vec::new();
+    for s1 in all_suits() {
+        for s2 in all_suits() {
+            let keep = match kind {
+                Suitedness::Pair => s1 < s2,
+                Suitedness::Suited => s1 == s2,
+                Suitedness::Offsuit => s1 != s2,
+                Suitedness::Any => true,
+            };
+            if keep {
+                out.push(normalize([
+                    Card { suit: s1, rank: hi },
+                    Card { suit: s2, rank: lo },
+                ]));
+            }
+        }
+    }
+    out
+}
+
+/// All 169 starting hand classes, best first, ranked by the Chen formula
+/// with ties broken by the higher cards and suitedness.
+fn preflop_order() -> Vec<(Rank, Rank, Suitedness)> {
+    let mut classes = Vec::with_capacity(169);
+    for hi in all_ranks() {
+        for lo in all_ranks().into_iter().filter(|&lo| lo <= hi) {
+            if hi == lo {
+                classes.push((hi, lo, Suitedness::Pair));
+            } else {
+                classes.push((hi, lo, Suitedness::Suited));
+                classes.push((hi, lo, Suitedness::Offsuit));
+            }
+        }
+    }
+    classes.sort_by(|a, b| {
+        chen_score(*b)
+            .total_cmp(&chen_score(*a))
+            .then(b.0.cmp(&a.0))
+            .then(b.1.cmp(&a.1))
+            .then((b.2 == Suitedness::Suited).cmp(&(a.2 == Suitedness::Suited)))
+    });
+    classes
+}
+
+fn chen_score((hi, lo, kind): (Rank, Rank, Suitedness)) -> f64 {
+    let points = |r: Rank| match r {
+        Rank::Ace => 10.0,
+        Rank::King => 8.0,
+        Rank::Queen => 7.0,
+        Rank::Jack => 6.0,
+        r => r as u8 as f64 / 2.0,
+    };
+    if kind == Suitedness::Pair {
+        return (points(hi) * 2.0).max(5.0);
+    }
+    let mut score = points(hi);
+    if kind == Suitedness::Suited {
+        score += 2.0;
+    }
+    let gap = hi as u8 - lo as u8 - 1;
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    if gap <= 1 && hi < Rank::Queen {
+        score += 1.0;
+    }
+    score
+}
+
+/// One range's result, in percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RangeEquity {
+    pub win: f64,
+    pub tie: f64,
+    pub loss: f64,
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeEquityResult {
+    /// One entry per input range, in input order.
+    pub ranges: Vec<RangeEquity>,
+    /// Combo matchups enumerated, or Monte Carlo trials.
+    pub samples: u64,
+    pub exact: bool,
+}
+
+/// Equity of `ranges` against each other. Every matchup is enumerated with
+/// all runouts when matchups × runouts fits in `opts.exact_limit`;
+/// otherwise combos and runouts are sampled together, `opts.iterations`
+/// times.
+pub fn range_equity(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+    opts: &EquityOptions,
+) -> Result<RangeEquityResult, RangeError> {
+    if ranges.len() < 2 {
+        return Err(EquityError::TooFewHands.into());
+    }
+    if !matches!(board.len(), 0 | 3 | 4 | 5) {
+        return Err(EquityError::BoardSize(board.len()).into());
+    }
+    let mut known = board.to_vec();
+    known.extend_from_slice(dead);
+    let live: Vec<Range> = ranges.iter().map(|r| r.without(&known)).collect();
+    if let Some(i) = live.iter().position(Range::is_empty) {
+        return Err(RangeError::Empty(i));
+    }
+    let matchups: u64 = live.iter().map(|r| r.len() as u64).product();
+    let left = 52u64.saturating_sub(known.len() as u64 + 2 * ranges.len() as u64);
+    let runouts = (0..5u64.saturating_sub(board.len() as u64))
+        .fold(1u64, |acc, i| acc * (left - i) / (i + 1));
+    if matchups.saturating_mul(runouts) <= opts.exact_limit {
+        exact(&live, board, dead)
+    } else {
+        sampled(&live, board, dead, opts)
+    }
+}
+
+#[derive(Default)]
+struct Totals {
+    sums: Vec<RangeEquity>,
+    weight: f64,
+}
+
+impl Totals {
+    fn add(&mut self, res: &equity::EquityResult, weight: f64) {
+        if self.sums.is_empty() {
+            self.sums = vec![RangeEquity::default(); res.hands.len()];
+        }
+        for (sum, eq) in self.sums.iter_mut().zip(&res.hands) {
+            sum.win += eq.win_pct(res.runouts) * weight;
+            sum.tie += eq.tie_pct(res.runouts) * weight;
+            sum.loss += eq.loss_pct(res.runouts) * weight;
+            sum.equity += eq.equity_pct(res.runouts) * weight;
+        }
+        self.weight += weight;
+    }
+
+    fn finish(mut self, samples: u64, exact: bool) -> RangeEquityResult {
+        if self.weight > 0.0 {
+            for sum in &mut self.sums {
+                sum.win /= self.weight;
+                sum.tie /= self.weight;
+                sum.loss /= self.weight;
+                sum.equity /= self.weight;
+            }
+        }
+        RangeEquityResult {
+            ranges: self.sums,
+            samples,
+            exact,
+        }
+    }
+}
+
+fn conflicts(hands: &[Combo]) -> bool {
+    hands.iter().enumerate().any(|(i, a)| {
+        hands[i + 1..]
+            .iter()
+            .any(|b| a.iter().any(|c| b.contains(c)))
+    })
+}
+
+fn exact(ranges: &[Range], board: &[Card], dead: &[Card]) -> Result<RangeEquityResult, RangeError> {
+    let opts = EquityOptions {
+        exact_limit: u64::MAX,
+        ..EquityOptions::default()
+    };
+    let mut totals = Totals::default();
+    let mut samples = 0;
+    let mut idx = vec![0usize; ranges.len()];
+    loop {
+        let hands: Vec<Combo> = idx
+            .iter()
+            .zip(ranges)
+            .map(|(&i, r)| r.combos[i].0)
+            .collect();
+        if !conflicts(&hands) {
+            let weight: f64 = idx
+                .iter()
+                .zip(ranges)
+                .map(|(&i, r)| r.combos[i].1)
+                .product();
+            totals.add(&equity::calculate(&hands, board, dead, &opts)?, weight);
+            samples += 1;
+        }
+        // odometer over the ranges' combo lists
+        let mut pos = ranges.len();
+        loop {
+            if pos == 0 {
+                if samples == 0 {
+                    return Err(RangeError::NoMatchups);
+                }
+                return Ok(totals.finish(samples, true));
+            }
+            pos -= 1;
+            idx[pos] += 1;
+            if idx[pos] < ranges[pos].len() {
+                break;
+            }
+            idx[pos] = 0;
+        }
+    }
+}
+
+// Tries per trial to draw combos that do not share cards.
+const MAX_DRAWS: usize = 1000;
+
+fn sampled(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+    opts: &EquityOptions,
+) -> Result<RangeEquityResult, RangeError> {
+    let mut rng = ChaCha20Rng::seed_from_u64(opts.seed);
+    let mut totals = Totals::default();
+    let mut runout: Vec<Card> = board.to_vec();
+    let stub: Vec<Card> = full_deck()
+        .into_iter()
+        .filter(|c| !board.contains(c) && !dead.contains(c))
+        .collect();
+    let once = EquityOptions {
+        exact_limit: u64::MAX,
+        ..EquityOptions::default()
+    };
+    for _ in 0..opts.iterations {
+        let hands = (0..MAX_DRAWS)
+            .map(|_| {
+                ranges
+                    .iter()
+                    .map(|r| {
+                        r.combos
+                            .choose_weighted(&mut rng, |(_, w)| *w)
+                            .map(|(c, _)| *c)
+                            .expect("ranges are not empty")
+                    })
+                    .collect::<Vec<Combo>>()
+            })
+            .find(|hands| !conflicts(hands))
+            .ok_or(RangeError::NoMatchups)?;
+        runout.truncate(board.len());
+        while runout.len() < 5 {
+            let card = stub[rng.gen_range(0..stub.len())];
+            if !runout.contains(&card) && !hands.iter().any(|h| h.contains(&card)) {
+                runout.push(card);
+            }
+        }
+        totals.add(&equity::calculate(&hands, &runout, dead, &once)?, 1.0);
+    }
+    Ok(totals.finish(opts.iterations, false))
+}
@@ -1,6 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::errors::GameError;
 use crate::player::PlayerAction as A;
 
+/// Smallest chip denomination; every bet and raise is a multiple of this.
+pub const MIN_CHIP_UNIT: u32 = 25;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ValidatedAction {
     Fold,
@@ -58,3 +63,124 @@ pub fn validate_action(
         A::AllIn => Ok(ValidatedAction::AllIn(stack)),
     }
 }
+
+/// Inclusive chip range for a bet or raise; `max` always means all-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmountRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl AmountRange {
+    pub fn contains(&self, amount: u32) -> bool {
+        self.min <= amount && amount <= self.max
+    }
+}
+
+/// Everything the player to act may do. `bet` is a total amount, `raise` is
+/// the increment on top of `call`, matching `PlayerAction`. Folding is
+/// always legal, even when a check is free; skipping that dominated choice is
+/// up to the agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalActions {
+    pub fold: bool,
+    pub check: bool,
+    pub call: Option<u32>,
+    pub bet: Option<AmountRange>,
+    pub raise: Option<AmountRange>,
+    pub all_in: Option<u32>,
+}
+
+impl LegalActions {
+    /// Confirms that an action normalized by `validate_action` is in this set.
+    pub fn permits(&self, action: &ValidatedAction) -> Result<(), GameError> {
+        let illegal = |reason| Err(GameError::IllegalAction { reason });
+        match *action {
+            ValidatedAction::Fold if !self.fold => illegal("no decision to make"),
+            ValidatedAction::Check if !self.check => illegal("cannot check facing a bet"),
+            ValidatedAction::Call(_) if self.call.is_none() => illegal("nothing to call"),
+            ValidatedAction::Bet(amount) => match self.bet {
+                Some(range) => check_amount(range, amount),
+                None if self.call.is_some() || self.raise.is_some() => {
+                    illegal("cannot bet facing a bet")
+                }
+                None => illegal("betting is closed"),
+            },
+            ValidatedAction::Raise(amount) => match self.raise {
+                Some(range) => check_amount(range, amount),
+                None if self.bet.is_some() => illegal("nothing to raise"),
+                None => illegal("raising is closed"),
+            },
+            ValidatedAction::AllIn(amount) if self.all_in != Some(amount) => {
+                illegal("raising is closed")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn check_amount(range: AmountRange, amount: u32) -> Result<(), GameError> {
+    if amount < range.min {
+        return Err(GameError::InvalidBetAmount {
+            amount,
+            minimum: range.min,
+        });
+    }
+    if !amount.is_multiple_of(MIN_CHIP_UNIT) {
+        return Err(GameError::InvalidChipUnit {
+            amount,
+            unit: MIN_CHIP_UNIT,
+        });
+    }
+    Ok(())
+}
+
+fn round_up_to_unit(amount: u32) -> u32 {
+    amount.div_ceil(MIN_CHIP_UNIT) * MIN_CHIP_UNIT
+}
+
+/// Enumerates the legal actions at a decision point.
+///
+/// `current_bet` is the highest amount committed on this street and
+/// `min_raise` the last full raise increment. `can_raise` is false when a
+/// short all-in left betting closed for this player or nobody could respond.
+pub fn legal_actions(
+    stack: u32,
+    to_call: u32,
+    current_bet: u32,
+    min_raise: u32,
+    big_blind: u32,
+    can_raise: bool,
+) -> LegalActions {
+    let mut legal = LegalActions::default();
+    if stack == 0 {
+        return legal;
+    }
+    legal.fold = true;
+    legal.check = to_call == 0;
+    if to_call > 0 {
+        legal.call = Some(to_call.min(stack));
+    }
+    if stack <= to_call {
+        // calling already puts the player all-in
+        legal.all_in = Some(stack);
+        return legal;
+    }
+    if !can_raise {
+        return legal;
+    }
+    legal.all_in = Some(stack);
+    let room = stack - to_call;
+    if current_bet == 0 {
+        let min = round_up_to_unit(big_blind.max(MIN_CHIP_UNIT));
+        if room >= min {
+            legal.bet = Some(AmountRange { min, max: room });
+        }
+    } else {
+        let min = round_up_to_unit(min_raise.max(big_blind).max(MIN_CHIP_UNIT));
+        if room >= min {
+            legal.raise = Some(AmountRange { min, max: room });
+        }
+    }
+    legal
+}
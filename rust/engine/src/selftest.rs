@@ -0,0 +1,157 @@
+//! Exhaustive evaluator checks against the known category frequencies.
+//!
+//! Every 5-card hand is ranked with the `hand_rank` tables (`evaluate_hand`
+//! only takes 7 cards). Every 7-card hand is ranked with `evaluate_hand`
+//! and `evaluate_hand_optimized`, which must agree card for card.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::cards::{full_deck, Card};
+use crate::hand::{evaluate_hand, evaluate_hand_optimized, Category};
+use crate::hand_rank;
+
+/// Hands per `Category`, high card first, over all C(52, 5) hands.
+pub const FIVE_CARD_COUNTS: [u64; 9] = [
+    1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 40,
+];
+
+/// Hands per `Category`, high card first, over all C(52, 7) hands.
+pub const SEVEN_CARD_COUNTS: [u64; 9] = [
+    23_294_460, 58_627_800, 31_433_400, 6_461_620, 6_180_020, 4_047_644, 3_473_184, 224_848, 41_584,
+];
+
+pub const CATEGORIES: [Category; 9] = [
+    Category::HighCard,
+    Category::OnePair,
+    Category::TwoPair,
+    Category::ThreeOfAKind,
+    Category::Straight,
+    Category::Flush,
+    Category::FullHouse,
+    Category::FourOfAKind,
+    Category::StraightFlush,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfTestReport {
+    pub cards: usize,
+    pub hands: u64,
+    pub counts: [u64; 9],
+    /// Known counts; only set for a full 52-card deck.
+    pub expected: Option<[u64; 9]>,
+    /// 7-card hands where `evaluate_hand_optimized` disagreed.
+    pub mismatches: u64,
+    pub first_mismatch: Option<Vec<Card>>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0 && self.expected.is_none_or(|e| e == self.counts)
+    }
+}
+
+/// All 2,598,960 five-card hands.
+pub fn check_five(threads: usize) -> SelfTestReport {
+    check(&full_deck(), 5, threads)
+}
+
+/// All 133,784,560 seven-card hands; takes a while.
+pub fn check_seven(threads: usize) -> SelfTestReport {
+    check(&full_deck(), 7, threads)
+}
+
+/// Every `cards`-card subset of `deck` (5 or 7 cards), split over
+/// `threads` workers by first card.
+pub fn check(deck: &[Card], cards: usize, threads: usize) -> SelfTestReport {
+    assert!(cards == 5 || cards == 7, "self-test covers 5 or 7 cards");
+    let next_first = AtomicUsize::new(0);
+    let total = Mutex::new(SelfTestReport {
+        cards,
+        hands: 0,
+        counts: [0; 9],
+        expected: None,
+        mismatches: 0,
+        first_mismatch: None,
+    });
+    let last_first = deck.len().saturating_sub(cards);
+    thread::scope(|s| {
+        for _ in 0..threads.max(1) {
+            s.spawn(|| {
+                let mut local = Tally::default();
+                loop {
+                    let first = next_first.fetch_add(1, Ordering::Relaxed);
+                    if first > last_first || deck.len() < cards {
+                        break;
+                    }
+                    local.run_from(deck, cards, first);
+                }
+                let mut total = total.lock().expect("self-test lock");
+                total.hands += local.hands;
+                for (t, c) in total.counts.iter_mut().zip(local.counts) {
+                    *t += c;
+                }
+                total.mismatches += local.mismatches;
+                if total.first_mismatch.is_none() {
+                    total.first_mismatch = local.first_mismatch;
+                }
+            });
+        }
+    });
+    let mut report = total.into_inner().expect("self-test lock");
+    if deck.len() == 52 {
+        report.expected = Some(if cards == 5 {
+            FIVE_CARD_COUNTS
+        } else {
+            SEVEN_CARD_COUNTS
+        });
+    }
+    report
+}
+
+#[derive(Default)]
+struct Tally {
+    hands: u64,
+    counts: [u64; 9],
+    mismatches: u64,
+    first_mismatch: Option<Vec<Card>>,
+}
+
+impl Tally {
+    // every subset whose lowest index is `first`
+    fn run_from(&mut self, deck: &[Card], cards: usize, first: usize) {
+        let n = deck.len();
+        let mut idx: Vec<usize> = (first..first + cards).collect();
+        let mut hand = [deck[0]; 7];
+        loop {
+            for (slot, &i) in hand.iter_mut().zip(&idx) {
+                *slot = deck[i];
+            }
+            self.add(&hand[..cards]);
+            // advance positions 1.. only; position 0 stays at `first`
+            let Some(i) = (1..cards).rev().find(|&i| idx[i] < n - cards + i) else {
+                return;
+            };
+            idx[i] += 1;
+            for j in i + 1..cards {
+                idx[j] = idx[j - 1] + 1;
+            }
+        }
+    }
+
+    fn add(&mut self, hand: &[Card]) {
+        let category = if let Ok(seven) = <&[Card; 7]>::try_from(hand) {
+            let base = evaluate_hand(seven);
+            if evaluate_hand_optimized(seven) != base {
+                self.mismatches += 1;
+                self.first_mismatch.get_or_insert_with(|| hand.to_vec());
+            }
+            base.category
+        } else {
+            hand_rank::evaluate(hand).category()
+        };
+        self.counts[category as usize] += 1;
+        self.hands += 1;
+    }
+}
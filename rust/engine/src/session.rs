@@ -0,0 +1,160 @@
+//! Heads-up tournament sessions: hands are played back to back on one
+//! engine until a player is eliminated.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::Agent;
+use crate::blinds::BlindSchedule;
+use crate::deck::derive_hand_seed;
+use crate::engine::{Engine, DEFAULT_SEED};
+use crate::errors::GameError;
+use crate::game::GameState;
+use crate::player::PlayerAction;
+use crate::rules::LegalActions;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub hands_played: u32,
+    /// Seat holding every chip, once the other player is eliminated.
+    pub winner: Option<usize>,
+    pub level_reached: u8,
+    pub final_stacks: [u32; 2],
+}
+
+#[derive(Debug)]
+pub struct Session {
+    engine: Engine,
+    seed: u64,
+    start_level: u8,
+    hands_played: u32,
+    rejected: [usize; 2],
+    started: Instant,
+}
+
+impl Session {
+    pub fn new(seed: Option<u64>, level: u8, schedule: BlindSchedule) -> Self {
+        Self {
+            engine: Engine::with_schedule(seed, level.max(1), schedule),
+            seed: seed.unwrap_or(DEFAULT_SEED),
+            start_level: level.max(1),
+            hands_played: 0,
+            rejected: [0; 2],
+            started: Instant::now(),
+        }
+    }
+
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    pub fn hands_played(&self) -> u32 {
+        self.hands_played
+    }
+
+    /// Actions the engine refused for each seat, played as check/fold.
+    pub fn rejected(&self) -> [usize; 2] {
+        self.rejected
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Seed of the next hand's deck, see `deck::derive_hand_seed`.
+    pub fn next_hand_seed(&self) -> u64 {
+        derive_hand_seed(self.seed, u64::from(self.hands_played))
+    }
+
+    /// Blind level the next hand will be played at.
+    pub fn next_level(&self) -> u8 {
+        self.engine
+            .schedule()
+            .level_at(self.start_level, self.hands_played, self.elapsed())
+    }
+
+    /// True once a player has no chips left.
+    pub fn is_over(&self) -> bool {
+        self.engine.players().iter().any(|p| p.stack() == 0)
+    }
+
+    /// Seat holding every chip, if the session is over.
+    pub fn winner(&self) -> Option<usize> {
+        if !self.is_over() {
+            return None;
+        }
+        self.engine.players().iter().position(|p| p.stack() > 0)
+    }
+
+    /// Plays one hand to the end, asking `decide` for every action. Blinds
+    /// follow the schedule and the button moves to the other seat afterwards.
+    /// An action the engine rejects is played as a check, or a fold facing
+    /// a bet, so a hand that starts always finishes.
+    pub fn play_hand<F>(&mut self, mut decide: F) -> Result<(), GameError>
+    where
+        F: FnMut(&Engine, usize, &LegalActions) -> PlayerAction,
+    {
+        if self.is_over() {
+            return Err(GameError::PlayerBusted);
+        }
+        self.engine.set_level(self.next_level());
+        self.engine.set_seed(self.next_hand_seed());
+        self.engine.start_hand()?;
+        while let (Some(player), Some(legal)) = (self.engine.to_act(), self.engine.legal_actions())
+        {
+            let action = decide(&self.engine, player, &legal);
+            if self.engine.apply_action(player, action).is_err() {
+                self.rejected[player] += 1;
+                let fallback = if legal.check {
+                    PlayerAction::Check
+                } else {
+                    PlayerAction::Fold
+                };
+                self.engine.apply_action(player, fallback)?;
+            }
+        }
+        self.hands_played += 1;
+        self.rotate_button();
+        Ok(())
+    }
+
+    /// Plays one hand with `agents[seat]` choosing for each seat.
+    pub fn play_hand_with(&mut self, agents: &mut [Box<dyn Agent>; 2]) -> Result<(), GameError> {
+        self.play_hand(|engine, seat, _| match engine.observation(seat) {
+            Some(obs) => agents[seat].act(&obs),
+            None => PlayerAction::Fold,
+        })
+    }
+
+    /// Plays hands until someone is eliminated or `max_hands` is reached.
+    pub fn run<F>(
+        &mut self,
+        max_hands: Option<u32>,
+        mut decide: F,
+    ) -> Result<SessionSummary, GameError>
+    where
+        F: FnMut(&Engine, usize, &LegalActions) -> PlayerAction,
+    {
+        while !self.is_over() && max_hands.is_none_or(|max| self.hands_played < max) {
+            self.play_hand(&mut decide)?;
+        }
+        Ok(self.summary())
+    }
+
+    pub fn summary(&self) -> SessionSummary {
+        let players = self.engine.players();
+        SessionSummary {
+            hands_played: self.hands_played,
+            winner: self.winner(),
+            level_reached: self.engine.level(),
+            final_stacks: [players[0].stack(), players[1].stack()],
+        }
+    }
+
+    fn rotate_button(&mut self) {
+        let mut state = GameState::new(self.engine.players().clone(), self.engine.level());
+        state.rotate_button();
+        *self.engine.players_mut() = state.players().clone();
+    }
+}
@@ -0,0 +1,551 @@
+//! Rule checks over recorded hands.
+//!
+//! Hands are replayed through `rules::legal_actions` and `validate_action`,
+//! so a recorded action is accepted exactly when the engine would accept it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::GameError;
+use crate::logger::{ActionRecord, HandRecord, Street};
+use crate::player::PlayerAction;
+use crate::rules::{legal_actions, validate_action, ValidatedAction, MIN_CHIP_UNIT};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    UnknownPlayer,
+    MinimumBet,
+    MinimumRaise,
+    ChipUnit,
+    IllegalAction,
+    ReopenAfterShortAllIn,
+    DealingOrder,
+    BurnPositions,
+    StackMismatch,
+    RejoinAfterElimination,
+    UnexpectedPlayer,
+    MissingPlayer,
+    NonPositiveStack,
+    BoardLength,
+    ActionAmount,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub hand_id: String,
+    /// 1-based position of the hand in its file.
+    pub hand_index: u64,
+    /// 0-based index into the hand's `actions`, for betting violations.
+    pub action_index: Option<usize>,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Dealing details some records carry in `meta`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DealingMeta {
+    #[serde(default)]
+    pub small_blind: Option<String>,
+    #[serde(default)]
+    pub big_blind: Option<String>,
+    #[serde(default)]
+    pub deal_sequence: Option<Vec<String>>,
+    #[serde(default)]
+    pub burn_positions: Option<Vec<i64>>,
+}
+
+/// What the checks need to know about a hand beyond its actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HandContext {
+    pub hand_id: String,
+    pub hand_index: u64,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub button: Option<String>,
+    pub starting_stacks: BTreeMap<String, i64>,
+    /// Antes posted, by player; taken from the stacks before the blinds.
+    pub antes: BTreeMap<String, u32>,
+    pub dealing: Option<DealingMeta>,
+}
+
+impl HandContext {
+    /// Reads the context of the `hand_index`-th record of a file. Missing
+    /// blinds fall back to the smallest legal big blind and no small blind;
+    /// players without a `stack_start` (version 1 rosters) are left out. A
+    /// `meta` object that does not describe the deal is a violation.
+    pub fn from_record(rec: &HandRecord, hand_index: u64) -> Result<Self, Violation> {
+        let mut ctx = HandContext {
+            hand_id: rec.hand_id.clone(),
+            hand_index,
+            small_blind: rec.sb.unwrap_or(0),
+            big_blind: rec.bb.unwrap_or(MIN_CHIP_UNIT).max(MIN_CHIP_UNIT),
+            button: rec.button.clone(),
+            ..Default::default()
+        };
+        for player in &rec.players {
+            if let Some(stack) = player.stack_start {
+                ctx.starting_stacks.insert(player.id.clone(), stack as i64);
+            }
+            if let Some(ante) = player.ante {
+                ctx.antes.insert(player.id.clone(), ante);
+            }
+        }
+        if let Some(meta) = rec.meta.as_ref().filter(|m| m.is_object()) {
+            ctx.dealing = Some(serde_json::from_value(meta.clone()).map_err(|e| {
+                ctx.violation(
+                    None,
+                    Rule::DealingOrder,
+                    format!("Invalid dealing order at hand {}: {}", hand_index, e),
+                )
+            })?);
+        }
+        Ok(ctx)
+    }
+
+    fn violation(&self, action_index: Option<usize>, rule: Rule, message: String) -> Violation {
+        Violation {
+            hand_id: self.hand_id.clone(),
+            hand_index: self.hand_index,
+            action_index,
+            rule,
+            message,
+        }
+    }
+
+    // Recorded player ids are seat numbers; rosters usually name them "p<seat>".
+    fn player_key(&self, seat: usize) -> String {
+        let candidate = format!("p{}", seat);
+        if self.starting_stacks.contains_key(&candidate) {
+            candidate
+        } else {
+            seat.to_string()
+        }
+    }
+
+    fn blind_posters(&self) -> Option<(String, String)> {
+        let known = |id: &str| self.starting_stacks.contains_key(id);
+        if let Some(meta) = &self.dealing {
+            if let (Some(sb), Some(bb)) = (&meta.small_blind, &meta.big_blind) {
+                if known(sb) && known(bb) && sb != bb {
+                    return Some((sb.clone(), bb.clone()));
+                }
+            }
+        }
+        // heads-up: the button posts the small blind
+        let button = self.button.as_deref().filter(|b| known(b))?;
+        if self.starting_stacks.len() != 2 {
+            return None;
+        }
+        let other = self.starting_stacks.keys().find(|id| *id != button)?;
+        Some((button.to_string(), other.clone()))
+    }
+}
+
+/// Runs the per-hand checks (dealing order and betting).
+pub fn verify_hand(ctx: &HandContext, actions: &[ActionRecord]) -> Vec<Violation> {
+    let mut out = check_dealing(ctx);
+    out.extend(check_betting(ctx, actions));
+    out
+}
+
+#[derive(Debug, Clone, Default)]
+struct SeatState {
+    remaining: u32,
+    committed: u32,
+    folded: bool,
+    acted: bool,
+    raise_closed: bool,
+}
+
+/// Replays `actions` and reports the first betting rule they break.
+pub fn check_betting(ctx: &HandContext, actions: &[ActionRecord]) -> Vec<Violation> {
+    if ctx.starting_stacks.is_empty() {
+        return Vec::new();
+    }
+    let big_blind = ctx.big_blind;
+    let mut seats: BTreeMap<String, SeatState> = ctx
+        .starting_stacks
+        .iter()
+        .map(|(id, stack)| {
+            let ante = ctx.antes.get(id).copied().unwrap_or(0);
+            let state = SeatState {
+                remaining: ((*stack).max(0) as u32).saturating_sub(ante),
+                ..SeatState::default()
+            };
+            (id.clone(), state)
+        })
+        .collect();
+    let mut street: Option<Street> = None;
+    let mut high = 0u32;
+    let mut min_raise = big_blind;
+
+    if let Some((sb, bb)) = ctx.blind_posters() {
+        for (id, blind) in [(sb, ctx.small_blind), (bb, big_blind)] {
+            if let Some(seat) = seats.get_mut(&id) {
+                let posted = blind.min(seat.remaining);
+                seat.remaining -= posted;
+                seat.committed = posted;
+                high = high.max(posted);
+            }
+        }
+        street = Some(Street::Preflop);
+    }
+
+    for (idx, act) in actions.iter().enumerate() {
+        let at = |msg: String| format!("{} at hand {} (action #{})", msg, ctx.hand_index, idx + 1);
+        let id = ctx.player_key(act.player_id);
+        if !seats.contains_key(&id) {
+            return vec![ctx.violation(
+                Some(idx),
+                Rule::UnknownPlayer,
+                at(format!("Unknown player {}", id)),
+            )];
+        }
+        if street != Some(act.street) {
+            street = Some(act.street);
+            for seat in seats.values_mut() {
+                seat.committed = 0;
+                seat.acted = false;
+                seat.raise_closed = false;
+            }
+            high = 0;
+            min_raise = big_blind;
+        }
+
+        let someone_can_respond = seats
+            .iter()
+            .any(|(other, s)| *other != id && !s.folded && s.remaining > 0);
+        let seat = &seats[&id];
+        let to_call = high.saturating_sub(seat.committed);
+        let legal = legal_actions(
+            seat.remaining,
+            to_call,
+            high,
+            min_raise,
+            big_blind,
+            !seat.raise_closed && someone_can_respond,
+        );
+        let kind = action_name(&act.action);
+        let rejected = |err: GameError| match err {
+            GameError::InvalidBetAmount { amount, minimum } => match act.action {
+                PlayerAction::Raise(_) => ctx.violation(
+                    Some(idx),
+                    Rule::MinimumRaise,
+                    at(format!("Raise delta {} below minimum {}", amount, minimum)),
+                ),
+                _ => ctx.violation(
+                    Some(idx),
+                    Rule::MinimumBet,
+                    at(format!("Bet below minimum {}", minimum)),
+                ),
+            },
+            GameError::InvalidChipUnit { amount, .. } => ctx.violation(
+                Some(idx),
+                Rule::ChipUnit,
+                at(format!("Invalid {} amount {}", kind, amount)),
+            ),
+            other => ctx.violation(
+                Some(idx),
+                Rule::IllegalAction,
+                at(format!("Illegal {} ({})", kind, other)),
+            ),
+        };
+
+        let validated =
+            match validate_action(seat.remaining, to_call, min_raise, act.action.clone()) {
+                Ok(v) => v,
+                Err(e) => return vec![rejected(e)],
+            };
+        let adds = match validated {
+            ValidatedAction::Fold | ValidatedAction::Check => 0,
+            ValidatedAction::Call(amount)
+            | ValidatedAction::Bet(amount)
+            | ValidatedAction::AllIn(amount) => amount,
+            ValidatedAction::Raise(amount) => to_call + amount,
+        };
+        let new_commit = seat.committed + adds;
+        if seat.raise_closed && new_commit > high {
+            return vec![ctx.violation(
+                Some(idx),
+                Rule::ReopenAfterShortAllIn,
+                at("Betting illegally reopened after short all-in".to_string()),
+            )];
+        }
+        if let Err(e) = legal.permits(&validated) {
+            return vec![rejected(e)];
+        }
+        if let Some(recorded) = act.amount.filter(|&a| a != adds) {
+            return vec![ctx.violation(
+                Some(idx),
+                Rule::ActionAmount,
+                at(format!(
+                    "Recorded {} amount {} but the action puts in {}",
+                    kind, recorded, adds
+                )),
+            )];
+        }
+
+        let extra = new_commit.saturating_sub(high);
+        let full_raise = extra >= min_raise;
+        let acted_before: Vec<String> = seats
+            .iter()
+            .filter(|(other, s)| **other != id && s.acted)
+            .map(|(other, _)| other.clone())
+            .collect();
+        if extra > 0 {
+            for (other, s) in seats.iter_mut() {
+                if *other == id {
+                    continue;
+                }
+                if full_raise {
+                    s.raise_closed = false;
+                } else if acted_before.contains(other) {
+                    s.raise_closed = true;
+                }
+                s.acted = false;
+            }
+            if full_raise {
+                min_raise = extra;
+            }
+            high = new_commit;
+        }
+        let seat = seats.get_mut(&id).expect("seat exists");
+        seat.remaining -= adds;
+        seat.committed = new_commit;
+        seat.acted = true;
+        if validated == ValidatedAction::Fold {
+            seat.folded = true;
+        }
+    }
+    Vec::new()
+}
+
+fn action_name(action: &PlayerAction) -> &'static str {
+    match action {
+        PlayerAction::Fold => "fold",
+        PlayerAction::Check => "check",
+        PlayerAction::Call => "call",
+        PlayerAction::Bet(_) => "bet",
+        PlayerAction::Raise(_) => "raise",
+        PlayerAction::AllIn => "all-in",
+    }
+}
+
+/// Checks that the board was dealt to the river, or, for a hand that ended
+/// in a fold, up to the street of the last action.
+pub fn check_board(ctx: &HandContext, rec: &HandRecord) -> Vec<Violation> {
+    let folded = match rec.end_reason.as_deref() {
+        Some(reason) => reason == "fold",
+        None => rec
+            .actions
+            .last()
+            .is_some_and(|a| a.action == PlayerAction::Fold),
+    };
+    let found = rec.board.len();
+    let (valid, expected) = match (folded, rec.actions.last()) {
+        (false, _) => (found == 5, "5".to_string()),
+        (true, Some(last)) => {
+            let n = street_cards(last.street);
+            (found == n, n.to_string())
+        }
+        (true, None) => (matches!(found, 0 | 3 | 4 | 5), "0, 3, 4 or 5".to_string()),
+    };
+    if valid {
+        return Vec::new();
+    }
+    vec![ctx.violation(
+        None,
+        Rule::BoardLength,
+        format!(
+            "Invalid board length at hand {}: expected {} cards but found {}",
+            ctx.hand_index, expected, found
+        ),
+    )]
+}
+
+fn street_cards(street: Street) -> usize {
+    match street {
+        Street::Preflop => 0,
+        Street::Flop => 3,
+        Street::Turn => 4,
+        Street::River => 5,
+    }
+}
+
+/// Checks blind seats, hole-card distribution order and burn positions.
+pub fn check_dealing(ctx: &HandContext) -> Vec<Violation> {
+    let (Some(meta), false) = (&ctx.dealing, ctx.starting_stacks.is_empty()) else {
+        return Vec::new();
+    };
+    let order = |msg: String| {
+        ctx.violation(
+            None,
+            Rule::DealingOrder,
+            format!("Invalid dealing order at hand {}: {}", ctx.hand_index, msg),
+        )
+    };
+    let mut out = Vec::new();
+    let stacks = &ctx.starting_stacks;
+    let player_count = stacks.len();
+    let rounds = 2; // Texas Hold'em: two hole cards per player
+    let sb = meta.small_blind.as_deref();
+    let bb = meta.big_blind.as_deref();
+    if let Some(sb_id) = sb.filter(|id| !stacks.contains_key(*id)) {
+        return vec![order(format!("unknown small blind {}", sb_id))];
+    }
+    if let Some(bb_id) = bb.filter(|id| !stacks.contains_key(*id)) {
+        return vec![order(format!("unknown big blind {}", bb_id))];
+    }
+    if let (Some(btn), Some(sb_id)) = (ctx.button.as_deref(), sb) {
+        if sb_id != btn {
+            return vec![order(format!(
+                "button {} must match small blind {}",
+                btn, sb_id
+            ))];
+        }
+    }
+    if let (Some(sb_id), Some(bb_id)) = (sb, bb) {
+        if sb_id == bb_id {
+            return vec![order("small blind and big blind must differ".to_string())];
+        }
+        if player_count == 2 {
+            if let Some(expected_bb) = stacks.keys().find(|id| id.as_str() != sb_id) {
+                if bb_id != expected_bb {
+                    return vec![order(format!(
+                        "expected big blind {} but found {}",
+                        expected_bb, bb_id
+                    ))];
+                }
+            }
+        }
+    }
+    if let Some(seq) = &meta.deal_sequence {
+        let expected_len = player_count * rounds;
+        if seq.len() != expected_len {
+            return vec![order(format!(
+                "expected {} entries in deal_sequence but found {}",
+                expected_len,
+                seq.len()
+            ))];
+        }
+        if seq.iter().any(|id| !stacks.contains_key(id)) {
+            return vec![order("deal_sequence references unknown player".to_string())];
+        }
+        let first_round = &seq[..player_count];
+        if let Some(sb_id) = sb {
+            if first_round.first().map(String::as_str) != Some(sb_id) {
+                return vec![order(format!(
+                    "expected {} to receive the first card",
+                    sb_id
+                ))];
+            }
+        }
+        if let Some(bb_id) = bb {
+            if player_count >= 2 && first_round.get(1).map(String::as_str) != Some(bb_id) {
+                return vec![order(format!(
+                    "expected {} to receive the second card",
+                    bb_id
+                ))];
+            }
+        }
+        let unique: HashSet<&String> = first_round.iter().collect();
+        if unique.len() != player_count {
+            return vec![order("duplicate players in first deal round".to_string())];
+        }
+        for round_idx in 1..rounds {
+            let chunk = &seq[round_idx * player_count..(round_idx + 1) * player_count];
+            if chunk != first_round {
+                return vec![order("inconsistent card distribution order".to_string())];
+            }
+        }
+    }
+    if let Some(burns) = &meta.burn_positions {
+        let burn = |msg: String| {
+            ctx.violation(
+                None,
+                Rule::BurnPositions,
+                format!("Invalid dealing order at hand {}: {}", ctx.hand_index, msg),
+            )
+        };
+        if burns.len() != 3 {
+            out.push(burn("expected 3 burn positions".to_string()));
+        } else if player_count >= 2 {
+            let hole_cards = player_count as i64 * 2;
+            let expected = vec![
+                hole_cards + 1,
+                hole_cards + 1 + 3 + 1,
+                hole_cards + 1 + 3 + 1 + 1 + 1,
+            ];
+            if *burns != expected {
+                out.push(burn(format!(
+                    "expected burn positions {:?} but found {:?}",
+                    expected, burns
+                )));
+            }
+        }
+    }
+    out
+}
+
+/// Compares a hand's starting stacks with the stacks left after the previous hand.
+pub fn check_roster(ctx: &HandContext, prev: Option<&BTreeMap<String, i64>>) -> Vec<Violation> {
+    let hands = ctx.hand_index;
+    let mut out = Vec::new();
+    let mut push = |rule, message| out.push(ctx.violation(None, rule, message));
+    if let Some(prev_map) = prev {
+        for (id, stack_start) in &ctx.starting_stacks {
+            match prev_map.get(id) {
+                Some(prev_stack) => {
+                    if prev_stack != stack_start {
+                        push(
+                            Rule::StackMismatch,
+                            format!("Stack mismatch for {} at hand {}", id, hands),
+                        );
+                    }
+                    if *prev_stack <= 0 {
+                        push(
+                            Rule::RejoinAfterElimination,
+                            format!(
+                                "Player {} reappeared after elimination at hand {}",
+                                id, hands
+                            ),
+                        );
+                    }
+                }
+                None => push(
+                    Rule::UnexpectedPlayer,
+                    format!("Unexpected player {} at hand {}", id, hands),
+                ),
+            }
+        }
+        for (id, prev_stack) in prev_map {
+            if !ctx.starting_stacks.contains_key(id) && *prev_stack > 0 {
+                push(
+                    Rule::MissingPlayer,
+                    format!("Missing player {} at hand {}", id, hands),
+                );
+            }
+        }
+    }
+    for (id, stack_start) in &ctx.starting_stacks {
+        if *stack_start <= 0 {
+            push(
+                Rule::NonPositiveStack,
+                format!(
+                    "Player {} has non-positive starting stack at hand {}",
+                    id, hands
+                ),
+            );
+        }
+    }
+    out
+}
@@ -0,0 +1,72 @@
+use axm_engine::agent::{Agent, AgentRegistry, HandStrengthBot};
+use axm_engine::blinds::BlindSchedule;
+use axm_engine::cards::{Card, Rank, Suit};
+use axm_engine::engine::Engine;
+use axm_engine::session::Session;
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card { rank, suit }
+}
+
+#[test]
+fn registry_builds_builtins_by_name() {
+    let reg = AgentRegistry::with_builtins();
+    for name in ["always-call", "random-legal", "hand-strength"] {
+        let agent = reg.create(name, 1).expect(name);
+        assert_eq!(agent.name(), name);
+    }
+    assert_eq!(reg.create("random", 1).unwrap().name(), "random-legal");
+    assert!(reg.create("nope", 1).is_none());
+}
+
+#[test]
+fn observation_only_for_seat_to_act() {
+    let mut eng = Engine::new(Some(1), 1);
+    assert!(eng.observation(0).is_none());
+    eng.start_hand().unwrap();
+    assert!(eng.observation(1).is_none());
+    let obs = eng.observation(0).unwrap();
+    assert_eq!(obs.to_call, 50);
+    assert_eq!(obs.pot, 150);
+    assert_eq!(obs.stacks, [19_950, 19_900]);
+    assert!(obs.board.is_empty());
+}
+
+#[test]
+fn builtin_agents_only_make_legal_moves() {
+    let reg = AgentRegistry::with_builtins();
+    for (a, b) in [
+        ("random-legal", "hand-strength"),
+        ("hand-strength", "always-call"),
+        ("random-legal", "random-legal"),
+    ] {
+        let mut agents: [Box<dyn Agent>; 2] =
+            [reg.create(a, 7).unwrap(), reg.create(b, 8).unwrap()];
+        let mut session = Session::new(Some(11), 1, BlindSchedule::default());
+        for _ in 0..200 {
+            if session.is_over() {
+                break;
+            }
+            session.play_hand_with(&mut agents).expect("legal action");
+        }
+        let total: u32 = session.engine().players().iter().map(|p| p.stack()).sum();
+        assert_eq!(total, 40_000);
+    }
+}
+
+#[test]
+fn hand_strength_ranks_made_hands_above_air() {
+    let aces = [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)];
+    let junk = [
+        card(Rank::Seven, Suit::Clubs),
+        card(Rank::Two, Suit::Diamonds),
+    ];
+    assert!(HandStrengthBot::strength(aces, &[]) > HandStrengthBot::strength(junk, &[]));
+    let flop = [
+        card(Rank::Ace, Suit::Clubs),
+        card(Rank::Nine, Suit::Diamonds),
+        card(Rank::Four, Suit::Spades),
+    ];
+    assert!(HandStrengthBot::strength(aces, &flop) >= 0.7);
+    assert!(HandStrengthBot::strength(junk, &flop) < 0.4);
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use axm_engine::blinds::{BlindLevel, BlindSchedule, LevelClock};
+use axm_engine::engine::Engine;
+
+#[test]
+fn default_matches_game_rules() {
+    let s = BlindSchedule::default();
+    assert_eq!(s.levels().len(), 20);
+    assert_eq!(s.clock(), LevelClock::Hands(15));
+    assert_eq!(
+        s.level(1),
+        BlindLevel {
+            sb: 50,
+            bb: 100,
+            ante: 0
+        }
+    );
+    assert_eq!(s.level(20).bb, 8000);
+    // past the table the last level continues
+    assert_eq!(s.level(25).bb, 8000);
+}
+
+#[test]
+fn levels_advance_by_hand_count() {
+    let s = BlindSchedule::default();
+    let zero = Duration::ZERO;
+    assert_eq!(s.level_at(1, 0, zero), 1);
+    assert_eq!(s.level_at(1, 14, zero), 1);
+    assert_eq!(s.level_at(1, 15, zero), 2);
+    assert_eq!(s.level_at(3, 30, zero), 5);
+    assert_eq!(s.level_at(1, 10_000, zero), 20);
+}
+
+#[test]
+fn loads_time_based_schedule_with_antes_from_toml() {
+    let s = BlindSchedule::from_toml_str(
+        r#"
+minutes_per_level = 10
+big_blind_ante = true
+
+[[levels]]
+sb = 100
+bb = 200
+ante = 200
+
+[[levels]]
+sb = 200
+bb = 400
+ante = 400
+"#,
+    )
+    .unwrap();
+    assert!(s.big_blind_ante());
+    assert_eq!(s.clock(), LevelClock::Time(Duration::from_secs(600)));
+    assert_eq!(s.level_at(1, 500, Duration::from_secs(599)), 1);
+    assert_eq!(s.level_at(1, 0, Duration::from_secs(600)), 2);
+}
+
+#[test]
+fn rejects_bad_schedules() {
+    for bad in [
+        "hands_per_level = 5\nlevels = []",
+        "levels = [ { sb = 50, bb = 100 } ]",
+        "hands_per_level = 5\nminutes_per_level = 5\nlevels = [ { sb = 50, bb = 100 } ]",
+        "hands_per_level = 0\nlevels = [ { sb = 50, bb = 100 } ]",
+        "hands_per_level = 5\nlevels = [ { sb = 60, bb = 100 } ]",
+        "hands_per_level = 5\nlevels = [ { sb = 200, bb = 100 } ]",
+    ] {
+        assert!(BlindSchedule::from_toml_str(bad).is_err(), "{}", bad);
+    }
+}
+
+#[test]
+fn engine_posts_antes_as_dead_money() {
+    let levels = vec![BlindLevel {
+        sb: 100,
+        bb: 200,
+        ante: 25,
+    }];
+    let s = BlindSchedule::new(levels.clone(), LevelClock::Hands(10), false).unwrap();
+    let mut eng = Engine::with_schedule(Some(1), 1, s);
+    eng.start_hand().unwrap();
+    assert_eq!(eng.pot(), 350);
+    // antes do not count toward the amount to call
+    assert_eq!(eng.to_call(0), 100);
+
+    let s = BlindSchedule::new(levels, LevelClock::Hands(10), true).unwrap();
+    let mut eng = Engine::with_schedule(Some(1), 1, s);
+    eng.start_hand().unwrap();
+    assert_eq!(eng.pot(), 350);
+    assert_eq!(eng.players()[0].stack(), 19_900);
+    assert_eq!(eng.players()[1].stack(), 19_750);
+}
@@ -0,0 +1,75 @@
+use axm_engine::cards::{
+    compact, format_cards, full_deck, parse_cards, Card, CardParseError, Rank as R, Suit as S,
+};
+use axm_engine::logger::HandRecord;
+use serde::{Deserialize, Serialize};
+
+fn c(rank: R, suit: S) -> Card {
+    Card { suit, rank }
+}
+
+#[test]
+fn card_display_round_trips_for_full_deck() {
+    for card in full_deck() {
+        let s = card.to_string();
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.parse::<Card>(), Ok(card));
+    }
+    assert_eq!(c(R::Ace, S::Hearts).to_string(), "Ah");
+    assert_eq!(c(R::Ten, S::Diamonds).to_string(), "Td");
+    assert_eq!(R::Two.to_string(), "2");
+    assert_eq!(S::Spades.to_string(), "s");
+    assert_eq!("q".parse::<R>(), Ok(R::Queen));
+    assert_eq!("C".parse::<S>(), Ok(S::Clubs));
+}
+
+#[test]
+fn parses_multi_card_strings() {
+    let expected = vec![c(R::Ten, S::Diamonds), c(R::Nine, S::Clubs)];
+    assert_eq!(parse_cards("Td9c").unwrap(), expected);
+    assert_eq!(parse_cards("Td 9c").unwrap(), expected);
+    assert_eq!(parse_cards("td,9C").unwrap(), expected);
+    assert_eq!(parse_cards("").unwrap(), vec![]);
+    assert_eq!(format_cards(&expected), "Td9c");
+}
+
+#[test]
+fn rejects_bad_cards() {
+    assert_eq!("1h".parse::<Card>(), Err(CardParseError::InvalidRank('1')));
+    assert_eq!("Ax".parse::<Card>(), Err(CardParseError::InvalidSuit('x')));
+    assert!(matches!(
+        "Ahh".parse::<Card>(),
+        Err(CardParseError::InvalidCard(_))
+    ));
+    assert!(parse_cards("AhK").is_err());
+    assert!(parse_cards("10h").is_err());
+}
+
+#[test]
+fn hand_record_board_reads_both_forms() {
+    let full = r#"{"hand_id":"x","seed":null,"actions":[],"board":[{"suit":"Hearts","rank":"Ace"}],"result":null}"#;
+    let short = r#"{"hand_id":"x","seed":null,"actions":[],"board":["Ah"],"result":null}"#;
+    let a: HandRecord = serde_json::from_str(full).unwrap();
+    let b: HandRecord = serde_json::from_str(short).unwrap();
+    assert_eq!(a.board, vec![c(R::Ace, S::Hearts)]);
+    assert_eq!(a, b);
+    let bad = r#"{"hand_id":"x","seed":null,"actions":[],"board":["Zz"],"result":null}"#;
+    assert!(serde_json::from_str::<HandRecord>(bad).is_err());
+}
+
+#[derive(Serialize, Deserialize)]
+struct Compact {
+    #[serde(with = "compact")]
+    board: Vec<Card>,
+}
+
+#[test]
+fn compact_helper_writes_strings() {
+    let v = Compact {
+        board: parse_cards("Ah Kd 7c").unwrap(),
+    };
+    let s = serde_json::to_string(&v).unwrap();
+    assert_eq!(s, r#"{"board":["Ah","Kd","7c"]}"#);
+    let back: Compact = serde_json::from_str(&s).unwrap();
+    assert_eq!(back.board, v.board);
+}
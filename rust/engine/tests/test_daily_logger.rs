@@ -0,0 +1,146 @@
+use std::cell::Cell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use axm_engine::logger::{HandLogger, HandRecord, Rotation};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+fn tmp_root(name: &str) -> PathBuf {
+    let p = std::env::temp_dir().join(format!("axm_daily_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&p);
+    p
+}
+
+fn at(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap()
+}
+
+fn ids(path: &std::path::Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<HandRecord>(l).unwrap().hand_id)
+        .collect()
+}
+
+#[test]
+fn writes_into_dated_directory_and_resumes_sequence() {
+    let root = tmp_root("resume");
+    let mut logger = HandLogger::daily_with_clock(&root, Rotation::default(), || at(4)).unwrap();
+    for _ in 0..3 {
+        logger.log(HandRecord::default()).unwrap();
+    }
+    let file = root.join("20250304").join("hands-0001.jsonl");
+    assert_eq!(logger.path(), Some(file.as_path()));
+    drop(logger);
+
+    let mut logger = HandLogger::daily_with_clock(&root, Rotation::default(), || at(4)).unwrap();
+    assert_eq!(
+        logger.log(HandRecord::default()).unwrap(),
+        "20250304-000004"
+    );
+    assert_eq!(
+        ids(&file),
+        [
+            "20250304-000001",
+            "20250304-000002",
+            "20250304-000003",
+            "20250304-000004"
+        ]
+    );
+    let first: HandRecord =
+        serde_json::from_str(fs::read_to_string(&file).unwrap().lines().next().unwrap()).unwrap();
+    assert_eq!(first.ts.as_deref(), Some("2025-03-04T12:00:00Z"));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn new_day_starts_a_new_directory() {
+    let root = tmp_root("rollover");
+    let now = Rc::new(Cell::new(at(4)));
+    let clock = {
+        let now = now.clone();
+        move || now.get()
+    };
+    let mut logger = HandLogger::daily_with_clock(&root, Rotation::default(), clock).unwrap();
+    logger.log(HandRecord::default()).unwrap();
+    now.set(now.get() + Duration::days(1));
+    assert_eq!(
+        logger.log(HandRecord::default()).unwrap(),
+        "20250305-000001"
+    );
+    assert_eq!(
+        logger.path(),
+        Some(root.join("20250305").join("hands-0001.jsonl").as_path())
+    );
+    assert_eq!(
+        ids(&root.join("20250304").join("hands-0001.jsonl")).len(),
+        1
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn rotates_by_hand_count_and_size() {
+    let root = tmp_root("rotate");
+    let rotation = Rotation {
+        max_hands: Some(2),
+        ..Default::default()
+    };
+    let mut logger = HandLogger::daily_with_clock(&root, rotation, || at(6)).unwrap();
+    for _ in 0..5 {
+        logger.log(HandRecord::default()).unwrap();
+    }
+    drop(logger);
+    let day = root.join("20250306");
+    assert_eq!(ids(&day.join("hands-0001.jsonl")).len(), 2);
+    assert_eq!(ids(&day.join("hands-0002.jsonl")).len(), 2);
+    assert_eq!(ids(&day.join("hands-0003.jsonl")), ["20250306-000005"]);
+
+    // resuming fills the last file before starting another
+    let mut logger = HandLogger::daily_with_clock(&root, rotation, || at(6)).unwrap();
+    assert_eq!(
+        logger.log(HandRecord::default()).unwrap(),
+        "20250306-000006"
+    );
+    assert_eq!(ids(&day.join("hands-0003.jsonl")).len(), 2);
+    logger.log(HandRecord::default()).unwrap();
+    assert_eq!(ids(&day.join("hands-0004.jsonl")), ["20250306-000007"]);
+    drop(logger);
+
+    let rotation = Rotation {
+        max_bytes: Some(1),
+        ..Default::default()
+    };
+    let mut logger = HandLogger::daily_with_clock(&root, rotation, || at(7)).unwrap();
+    for _ in 0..3 {
+        logger.log(HandRecord::default()).unwrap();
+    }
+    let parts = fs::read_dir(root.join("20250307")).unwrap().count();
+    assert_eq!(parts, 3);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn reopening_a_torn_file_drops_the_partial_record() {
+    let root = tmp_root("torn");
+    let mut logger = HandLogger::daily_with_clock(&root, Rotation::default(), || at(8)).unwrap();
+    logger.log(HandRecord::default()).unwrap();
+    logger.log(HandRecord::default()).unwrap();
+    drop(logger);
+    let file = root.join("20250308").join("hands-0001.jsonl");
+    let mut content = fs::read_to_string(&file).unwrap();
+    // the second record was cut off mid-write
+    content.truncate(content.len() - 10);
+    fs::write(&file, &content).unwrap();
+
+    let mut logger = HandLogger::daily_with_clock(&root, Rotation::default(), || at(8)).unwrap();
+    assert!(logger.truncated_bytes() > 0);
+    assert_eq!(
+        logger.log(HandRecord::default()).unwrap(),
+        "20250308-000002"
+    );
+    assert_eq!(ids(&file), ["20250308-000001", "20250308-000002"]);
+    let _ = fs::remove_dir_all(&root);
+}
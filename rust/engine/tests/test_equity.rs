@@ -0,0 +1,101 @@
+use axm_engine::cards::{parse_cards, Card};
+use axm_engine::equity::{calculate, EquityError, EquityOptions};
+
+fn hole(s: &str) -> [Card; 2] {
+    let v = parse_cards(s).unwrap();
+    [v[0], v[1]]
+}
+
+fn cards(s: &str) -> Vec<Card> {
+    parse_cards(s).unwrap()
+}
+
+#[test]
+fn flop_is_enumerated_exactly() {
+    let hands = [hole("AhKh"), hole("QsQd")];
+    let res = calculate(&hands, &cards("7h8h2c"), &[], &EquityOptions::default()).unwrap();
+    assert!(res.exact);
+    assert_eq!(res.runouts, 990);
+    let [a, b] = [res.hands[0], res.hands[1]];
+    assert_eq!(a.wins + a.ties + a.losses, 990);
+    assert_eq!(a.wins, b.losses);
+    assert_eq!(a.ties, b.ties);
+    assert!((a.equity_pct(990) + b.equity_pct(990) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn river_decides_outright_and_ties_split() {
+    // the board plays for both
+    let res = calculate(
+        &[hole("2c3d"), hole("4h5s")],
+        &cards("AhKhQhJhTh"),
+        &[],
+        &EquityOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(res.runouts, 1);
+    assert_eq!(res.hands[0].ties, 1);
+    assert_eq!(res.hands[0].equity_pct(1), 50.0);
+
+    // a wheel on the river beats a pair of kings
+    let res = calculate(
+        &[hole("Ah2d"), hole("KsKd")],
+        &cards("3c4h5s9dTc"),
+        &[],
+        &EquityOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(res.hands[0].wins, 1);
+    assert_eq!(res.hands[1].losses, 1);
+}
+
+#[test]
+fn dead_cards_are_removed_from_runouts() {
+    let hands = [hole("AhKh"), hole("QsQd")];
+    let board = cards("7h8h2c");
+    let live = calculate(&hands, &board, &[], &EquityOptions::default()).unwrap();
+    let dead = calculate(
+        &hands,
+        &board,
+        &cards("3h4h5h6h"),
+        &EquityOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(dead.runouts, 820); // C(41, 2)
+    assert!(dead.hands[0].equity_pct(dead.runouts) < live.hands[0].equity_pct(live.runouts));
+}
+
+#[test]
+fn monte_carlo_is_seeded_and_close_to_exact() {
+    let hands = [hole("AhKh"), hole("QsQd")];
+    let board = cards("7h8h2c");
+    let exact = calculate(&hands, &board, &[], &EquityOptions::default()).unwrap();
+    let opts = EquityOptions {
+        iterations: 4000,
+        seed: 9,
+        exact_limit: 0,
+    };
+    let mc = calculate(&hands, &board, &[], &opts).unwrap();
+    assert!(!mc.exact);
+    assert_eq!(mc.runouts, 4000);
+    assert_eq!(mc, calculate(&hands, &board, &[], &opts).unwrap());
+    let diff = mc.hands[0].equity_pct(mc.runouts) - exact.hands[0].equity_pct(exact.runouts);
+    assert!(diff.abs() < 3.0, "diff {}", diff);
+}
+
+#[test]
+fn rejects_bad_input() {
+    let opts = EquityOptions::default();
+    assert_eq!(
+        calculate(&[hole("AhKh")], &[], &[], &opts),
+        Err(EquityError::TooFewHands)
+    );
+    assert_eq!(
+        calculate(&[hole("AhKh"), hole("QsQd")], &cards("7h8h"), &[], &opts),
+        Err(EquityError::BoardSize(2))
+    );
+    assert_eq!(
+        calculate(&[hole("AhKh"), hole("QsQd")], &cards("Ah8h2c"), &[], &opts),
+        Err(EquityError::DuplicateCard(cards("Ah")[0]))
+    );
+}
@@ -0,0 +1,115 @@
+use axm_engine::cards::{full_deck, parse_cards, Card};
+use axm_engine::deck::{Deck, DeckError};
+use axm_engine::engine::{EndReason, Engine};
+use axm_engine::player::{PlayerAction as A, Position};
+
+fn cards(s: &str) -> Vec<Card> {
+    parse_cards(s).unwrap()
+}
+
+fn pair(s: &str) -> Option<[Card; 2]> {
+    Some(cards(s).try_into().unwrap())
+}
+
+fn opt(c: &[Card]) -> [Option<Card>; 2] {
+    [Some(c[0]), Some(c[1])]
+}
+
+#[test]
+fn preset_order_is_dealt_first_and_rest_is_shuffled() {
+    let top = cards("AsKsQsJsTs");
+    let mut deck = Deck::with_order(3, &top).unwrap();
+    deck.shuffle();
+    let first: Vec<Card> = (0..5).filter_map(|_| deck.deal_card()).collect();
+    assert_eq!(first, top);
+    let mut all = deck.order().to_vec();
+    all.sort_by_key(|c| (c.suit as u8, c.rank as u8));
+    let mut expected = full_deck();
+    expected.sort_by_key(|c| (c.suit as u8, c.rank as u8));
+    assert_eq!(all, expected);
+
+    // the free part still depends on the seed
+    let mut other = Deck::with_order(4, &top).unwrap();
+    other.shuffle();
+    assert_eq!(other.order()[..5], top[..]);
+    assert_ne!(other.order(), deck.order());
+}
+
+#[test]
+fn full_preset_reproduces_a_recorded_order() {
+    let mut recorded = Deck::new_with_seed(77);
+    recorded.shuffle();
+    let mut deck = Deck::with_order(0, recorded.order()).unwrap();
+    deck.shuffle();
+    assert_eq!(deck.commitment(), recorded.commitment());
+}
+
+#[test]
+fn preset_rejects_duplicates_and_long_orders() {
+    assert_eq!(
+        Deck::with_order(0, &cards("AsKsAs")).unwrap_err(),
+        DeckError::DuplicateCard(cards("As")[0])
+    );
+    let mut long = full_deck();
+    long.push(long[0]);
+    assert_eq!(
+        Deck::with_order(0, &long).unwrap_err(),
+        DeckError::TooManyCards(53)
+    );
+    let mut eng = Engine::new(Some(1), 1);
+    assert_eq!(
+        eng.force_cards([pair("AhAd"), pair("AhKd")], &[]),
+        Err(DeckError::DuplicateCard(cards("Ah")[0]))
+    );
+    assert_eq!(
+        eng.force_cards([None, None], &cards("2c3c4c5c6c7c")),
+        Err(DeckError::BoardSize(6))
+    );
+}
+
+#[test]
+fn aces_against_kings_on_a_king_high_board() {
+    for seed in 0..20 {
+        let mut eng = Engine::new(Some(seed), 1);
+        eng.force_cards([pair("AhAd"), pair("KhKd")], &cards("Kc7s2d"))
+            .unwrap();
+        eng.start_hand().unwrap();
+        assert_eq!(eng.players()[0].hole_cards(), opt(&cards("AhAd")));
+        assert_eq!(eng.players()[1].hole_cards(), opt(&cards("KhKd")));
+        while let Some(p) = eng.to_act() {
+            let legal = eng.legal_actions().unwrap();
+            let action = if legal.check { A::Check } else { A::Call };
+            eng.apply_action(p, action).unwrap();
+        }
+        assert_eq!(&eng.board()[..3], &cards("Kc7s2d")[..]);
+        assert_eq!(eng.end_reason(), Some(EndReason::Showdown));
+        // only the last ace can save the aces
+        let expected =
+            if eng.board().contains(&cards("Ac")[0]) || eng.board().contains(&cards("As")[0]) {
+                vec![0]
+            } else {
+                vec![1]
+            };
+        assert_eq!(eng.winners(), &expected[..], "seed {}", seed);
+    }
+}
+
+#[test]
+fn forced_cards_follow_the_seat_when_the_button_moves() {
+    let mut eng = Engine::new(Some(5), 1);
+    eng.force_cards([pair("2c2d"), None], &[]).unwrap();
+    for _ in 0..2 {
+        eng.start_hand().unwrap();
+        let [a, b] = eng.players()[0].hole_cards();
+        assert_eq!([a.unwrap(), b.unwrap()], pair("2c2d").unwrap());
+        let p = eng.to_act().unwrap();
+        eng.apply_action(p, A::Fold).unwrap();
+        let button = eng.button();
+        eng.players_mut()[button].set_position(Position::BigBlind);
+        eng.players_mut()[1 - button].set_position(Position::Button);
+    }
+    eng.clear_forced_cards();
+    eng.start_hand().unwrap();
+    let [a, b] = eng.players()[0].hole_cards();
+    assert_ne!([a.unwrap(), b.unwrap()], pair("2c2d").unwrap());
+}
@@ -0,0 +1,155 @@
+use axm_engine::cards::Card;
+use axm_engine::engine::{EndReason, Engine};
+use axm_engine::events::HandEvent;
+use axm_engine::logger::Street;
+use axm_engine::player::PlayerAction as A;
+
+fn kind(e: &HandEvent) -> &'static str {
+    match e {
+        HandEvent::HandStarted { .. } => "start",
+        HandEvent::BlindsPosted { .. } => "blinds",
+        HandEvent::CardsDealt { .. } => "cards",
+        HandEvent::ActionTaken { .. } => "action",
+        HandEvent::StreetAdvanced { .. } => "street",
+        HandEvent::Showdown { .. } => "showdown",
+        HandEvent::PotAwarded { .. } => "award",
+        HandEvent::HandEnded { .. } => "end",
+    }
+}
+
+fn check_down(engine: &mut Engine) {
+    engine.start_hand().unwrap();
+    while let Some(p) = engine.to_act() {
+        let legal = engine.legal_actions().unwrap();
+        let action = if legal.check { A::Check } else { A::Call };
+        engine.apply_action(p, action).unwrap();
+    }
+}
+
+#[test]
+fn fold_hand_emits_events_in_order() {
+    let mut eng = Engine::new(Some(4), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Fold).unwrap();
+    let kinds: Vec<_> = eng.events().iter().map(kind).collect();
+    assert_eq!(
+        kinds,
+        ["start", "blinds", "cards", "cards", "action", "award", "end"]
+    );
+    assert_eq!(
+        eng.events()[1],
+        HandEvent::BlindsPosted {
+            antes: [0, 0],
+            sb_seat: 0,
+            sb: 50,
+            bb_seat: 1,
+            bb: 100,
+        }
+    );
+    assert_eq!(
+        eng.events()[4],
+        HandEvent::ActionTaken {
+            seat: 0,
+            street: Street::Preflop,
+            action: A::Fold,
+            amount: 0,
+            pot: 150,
+        }
+    );
+    assert_eq!(
+        eng.events()[5],
+        HandEvent::PotAwarded {
+            seat: 1,
+            amount: 150,
+            pot_index: 0,
+        }
+    );
+    assert_eq!(
+        eng.events()[6],
+        HandEvent::HandEnded {
+            reason: EndReason::Fold,
+            winners: vec![1],
+            net: [-50, 50],
+            stacks: [19_950, 20_050],
+        }
+    );
+}
+
+#[test]
+fn events_rebuild_the_hand() {
+    for seed in 0..50 {
+        let mut eng = Engine::new(Some(seed), 1);
+        check_down(&mut eng);
+        let events = eng.events();
+        let mut board: Vec<Card> = Vec::new();
+        let mut streets = Vec::new();
+        let mut awarded = 0;
+        let mut pot = 0;
+        for e in events {
+            match e {
+                HandEvent::CardsDealt {
+                    seat: Some(seat),
+                    cards,
+                    ..
+                } => {
+                    let hole: Vec<Card> = eng.players()[*seat]
+                        .hole_cards()
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    assert_eq!(cards, &hole);
+                }
+                HandEvent::CardsDealt {
+                    seat: None, cards, ..
+                } => board.extend(cards),
+                HandEvent::StreetAdvanced { street } => streets.push(*street),
+                HandEvent::ActionTaken { pot: p, .. } => pot = *p,
+                HandEvent::PotAwarded { amount, .. } => awarded += amount,
+                _ => {}
+            }
+        }
+        assert_eq!(&board, eng.board());
+        assert_eq!(streets, [Street::Flop, Street::Turn, Street::River]);
+        assert_eq!(awarded, pot);
+        assert!(
+            matches!(events.last(), Some(HandEvent::HandEnded { net, .. }) if Some(*net) == eng.net_result())
+        );
+        let shown = events.iter().find_map(|e| match e {
+            HandEvent::Showdown { hands } => Some(hands.len()),
+            _ => None,
+        });
+        assert_eq!(shown, Some(2));
+    }
+}
+
+#[test]
+fn subscribers_receive_every_hand() {
+    let mut eng = Engine::new(Some(9), 1);
+    let rx = eng.subscribe();
+    check_down(&mut eng);
+    let first = eng.events().to_vec();
+    check_down(&mut eng);
+    // only the latest hand is kept on the engine
+    assert_eq!(kind(&eng.events()[0]), "start");
+    let received: Vec<HandEvent> = rx.try_iter().collect();
+    assert_eq!(received.len(), first.len() + eng.events().len());
+    assert_eq!(received[..first.len()], first[..]);
+
+    drop(rx);
+    check_down(&mut eng);
+}
+
+#[test]
+fn events_serialize_with_type_tags_and_card_strings() {
+    let mut eng = Engine::new(Some(2), 1);
+    check_down(&mut eng);
+    for e in eng.events() {
+        let json = serde_json::to_value(e).unwrap();
+        assert!(json["type"].is_string());
+        let back: HandEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(&back, e);
+    }
+    let dealt = serde_json::to_value(&eng.events()[2]).unwrap();
+    assert_eq!(dealt["type"], "CardsDealt");
+    assert_eq!(dealt["cards"][0].as_str().unwrap().len(), 2);
+}
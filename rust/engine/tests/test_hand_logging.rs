@@ -22,6 +22,7 @@ fn writes_jsonl_with_lf_only() {
             player_id: 0,
             street: Street::Preflop,
             action: PlayerAction::Check,
+            amount: None,
         }],
         board: vec![Card {
             suit: S::Clubs,
@@ -31,6 +32,7 @@ fn writes_jsonl_with_lf_only() {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
     logger.write(&rec).expect("write");
     let bytes = fs::read(&path).expect("read file");
@@ -62,6 +64,7 @@ fn ts_is_generated_when_missing_and_preserved_when_present() {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
     logger.write(&rec).expect("write");
     let line = String::from_utf8(fs::read(&path).unwrap()).unwrap();
@@ -77,3 +80,217 @@ fn ts_is_generated_when_missing_and_preserved_when_present() {
     let content = String::from_utf8(fs::read(&path).unwrap()).unwrap();
     assert!(content.contains(&preset), "preset ts must be kept");
 }
+
+#[test]
+fn logger_writes_current_schema_version() {
+    let path = tmp_path("schema_version");
+    let mut logger = HandLogger::create(&path).unwrap();
+    let rec = HandRecord {
+        hand_id: "20250101-000001".into(),
+        schema_version: 1,
+        ..Default::default()
+    };
+    logger.write(&rec).unwrap();
+    drop(logger);
+    let line = std::fs::read_to_string(&path).unwrap();
+    let back: HandRecord = serde_json::from_str(line.trim()).unwrap();
+    assert_eq!(back.schema_version, axm_engine::logger::SCHEMA_VERSION);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn torn_final_line_is_truncated() {
+    use axm_engine::logger::truncate_torn_tail;
+
+    let path = tmp_path("torn_tail");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "a\nb\n{\"hand_id\":\"2025").unwrap();
+    assert_eq!(truncate_torn_tail(&path).unwrap(), 16);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+    assert_eq!(truncate_torn_tail(&path).unwrap(), 0);
+    fs::write(&path, "no newline at all").unwrap();
+    truncate_torn_tail(&path).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"");
+    let _ = fs::remove_file(&path);
+    assert_eq!(truncate_torn_tail(&path).unwrap(), 0);
+}
+
+#[test]
+fn appending_logger_repairs_before_writing() {
+    let path = tmp_path("append_torn");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let rec = HandRecord {
+        hand_id: "20250101-000001".into(),
+        ..Default::default()
+    };
+    let good = serde_json::to_string(&rec).unwrap();
+    fs::write(&path, format!("{}\n{}", good, &good[..good.len() / 2])).unwrap();
+
+    let mut logger = HandLogger::append(&path).unwrap();
+    logger.set_sync_every(Some(1));
+    assert_eq!(logger.truncated_bytes(), (good.len() / 2) as u64);
+    logger
+        .write(&HandRecord {
+            hand_id: "20250101-000002".into(),
+            ..Default::default()
+        })
+        .unwrap();
+    drop(logger);
+    let content = fs::read_to_string(&path).unwrap();
+    let ids: Vec<String> = content
+        .lines()
+        .map(|l| serde_json::from_str::<HandRecord>(l).unwrap().hand_id)
+        .collect();
+    assert_eq!(ids, ["20250101-000001", "20250101-000002"]);
+    let _ = fs::remove_file(&path);
+}
+
+fn record(id: &str) -> HandRecord {
+    HandRecord {
+        hand_id: id.into(),
+        ..Default::default()
+    }
+}
+
+fn zstd_ids(path: &PathBuf) -> Vec<String> {
+    let text = zstd::stream::decode_all(fs::File::open(path).unwrap()).unwrap();
+    String::from_utf8(text)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<HandRecord>(l).unwrap().hand_id)
+        .collect()
+}
+
+#[test]
+fn zst_path_writes_compressed_frames() {
+    let path = tmp_path("frames").with_extension("jsonl.zst");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut logger = HandLogger::create(&path).unwrap();
+    logger.write(&record("20250101-000001")).unwrap();
+    logger.flush().unwrap();
+    logger.write(&record("20250101-000002")).unwrap();
+    // dropping finishes the second frame
+    drop(logger);
+    let bytes = fs::read(&path).unwrap();
+    let first = zstd::zstd_safe::find_frame_compressed_size(&bytes).unwrap();
+    assert!(first < bytes.len());
+    assert_eq!(zstd_ids(&path), ["20250101-000001", "20250101-000002"]);
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn appending_to_zst_drops_a_torn_frame() {
+    let path = tmp_path("torn_frame").with_extension("jsonl.zst");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut logger = HandLogger::create(&path).unwrap();
+    logger.write(&record("20250101-000001")).unwrap();
+    logger.flush().unwrap();
+    logger.write(&record("20250101-000002")).unwrap();
+    drop(logger);
+    let len = fs::metadata(&path).unwrap().len();
+    // the second frame was cut off mid-write
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_len(len - 5)
+        .unwrap();
+
+    let mut logger = HandLogger::append(&path).unwrap();
+    assert!(logger.truncated_bytes() > 0);
+    logger.write(&record("20250101-000002")).unwrap();
+    logger.write(&record("20250101-000003")).unwrap();
+    drop(logger);
+    assert_eq!(
+        zstd_ids(&path),
+        ["20250101-000001", "20250101-000002", "20250101-000003"]
+    );
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn appending_logger_continues_the_sequence() {
+    for ext in ["jsonl", "jsonl.zst"] {
+        let path = tmp_path("append_seq").with_extension(ext);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let _ = fs::remove_file(&path);
+        let mut logger = HandLogger::append(&path).unwrap();
+        let first = logger.log(HandRecord::default()).unwrap();
+        logger.log(HandRecord::default()).unwrap();
+        drop(logger);
+
+        let mut logger = HandLogger::append(&path).unwrap();
+        let third = logger.log(HandRecord::default()).unwrap();
+        drop(logger);
+        assert!(first.ends_with("-000001"), "{}", first);
+        assert_eq!(third, format!("{}-000003", &first[..8]));
+        let _ = fs::remove_file(&path);
+    }
+}
+
+#[test]
+fn torn_frame_is_found_from_headers_alone() {
+    use axm_engine::logger::truncate_torn_tail;
+    use std::io::Write;
+
+    let path = tmp_path("frame_walk").with_extension("jsonl.zst");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    // compressed, raw (incompressible) and RLE blocks, the last with a checksum
+    let text = "{\"hand_id\":\"20250101-000001\"}\n".repeat(5000);
+    let mut x = 1u64;
+    let noise: Vec<u8> = (0..300_000)
+        .map(|_| {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (x >> 56) as u8
+        })
+        .collect();
+    let mut head = zstd::stream::encode_all(text.as_bytes(), 3).unwrap();
+    head.extend(zstd::stream::encode_all(&noise[..], 3).unwrap());
+    let mut enc = zstd::stream::write::Encoder::new(Vec::new(), 3).unwrap();
+    enc.include_checksum(true).unwrap();
+    enc.write_all(&[0u8; 200_000]).unwrap();
+    enc.write_all(text.as_bytes()).unwrap();
+    let last = enc.finish().unwrap();
+
+    let mut whole = head.clone();
+    whole.extend(&last);
+    fs::write(&path, &whole).unwrap();
+    assert_eq!(truncate_torn_tail(&path).unwrap(), 0);
+    for cut in (1..last.len()).step_by(7).chain([last.len() - 1]) {
+        fs::write(&path, &whole[..head.len() + cut]).unwrap();
+        assert_eq!(
+            truncate_torn_tail(&path).unwrap(),
+            cut as u64,
+            "cut {}",
+            cut
+        );
+        assert_eq!(fs::read(&path).unwrap(), head);
+    }
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn appending_on_a_new_day_ignores_older_ids() {
+    let path = tmp_path("append_new_day");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    // well over the tail the logger looks at, all from an earlier day
+    let old: String = (1..=2000)
+        .map(|n| {
+            format!(
+                "{}\n",
+                serde_json::to_string(&record(&format!("20000101-{:06}", n))).unwrap()
+            )
+        })
+        .collect();
+    fs::write(&path, old).unwrap();
+    let mut logger = HandLogger::append(&path).unwrap();
+    let id = logger.log(HandRecord::default()).unwrap();
+    assert!(
+        id.ends_with("-000001") && !id.starts_with("20000101"),
+        "{}",
+        id
+    );
+    let _ = fs::remove_file(&path);
+}
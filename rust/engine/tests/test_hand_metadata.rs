@@ -15,6 +15,7 @@ fn hand_record_supports_timestamp_and_metadata() {
         ts: Some("2025-01-02T03:04:05Z".to_string()),
         meta: Some(serde_json::json!({"note":"test"})),
         showdown: None,
+        ..Default::default()
     };
     let s = serde_json::to_string(&rec).unwrap();
     assert!(s.contains("\"ts\":"));
@@ -0,0 +1,111 @@
+use axm_engine::cards::{full_deck, parse_cards, Card};
+use axm_engine::hand::{compare_hands, evaluate_hand, Category, HandStrength};
+use axm_engine::hand_rank::{evaluate, HandRank};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+fn cards(s: &str) -> Vec<Card> {
+    parse_cards(s).unwrap()
+}
+
+fn rank(s: &str) -> HandRank {
+    evaluate(&cards(s))
+}
+
+#[test]
+fn matches_evaluate_hand_on_random_seven_card_hands() {
+    let mut rng = ChaCha20Rng::seed_from_u64(14);
+    let mut deck = full_deck();
+    for _ in 0..20_000 {
+        deck.shuffle(&mut rng);
+        let hand: [Card; 7] = deck[..7].try_into().unwrap();
+        let lut = evaluate(&hand);
+        let base = evaluate_hand(&hand);
+        assert_eq!(lut.strength(), base, "{:?}", hand);
+        assert_eq!(HandRank::from(&base), lut);
+    }
+}
+
+#[test]
+fn order_matches_compare_hands() {
+    let mut rng = ChaCha20Rng::seed_from_u64(15);
+    let mut deck = full_deck();
+    for _ in 0..5_000 {
+        deck.shuffle(&mut rng);
+        let a: [Card; 7] = deck[..7].try_into().unwrap();
+        let b: [Card; 7] = deck[7..14].try_into().unwrap();
+        let (ra, rb) = (evaluate(&a), evaluate(&b));
+        assert_eq!(
+            ra.cmp(&rb),
+            compare_hands(&evaluate_hand(&a), &evaluate_hand(&b))
+        );
+    }
+}
+
+#[test]
+fn six_cards_take_the_best_five() {
+    let mut rng = ChaCha20Rng::seed_from_u64(16);
+    let mut deck = full_deck();
+    for _ in 0..2_000 {
+        deck.shuffle(&mut rng);
+        let six = &deck[..6];
+        let best = (0..6)
+            .map(|skip| {
+                let five: Vec<Card> = (0..6).filter(|&i| i != skip).map(|i| six[i]).collect();
+                evaluate(&five)
+            })
+            .max()
+            .unwrap();
+        assert_eq!(evaluate(six), best);
+    }
+}
+
+#[test]
+fn five_card_categories_and_kickers() {
+    let cases = [
+        ("AhKhQhJhTh", Category::StraightFlush, [14, 0, 0, 0, 0]),
+        ("5d4d3d2dAd", Category::StraightFlush, [5, 0, 0, 0, 0]),
+        ("9c9d9h9sKd", Category::FourOfAKind, [9, 13, 0, 0, 0]),
+        ("3c3d3hJsJd", Category::FullHouse, [3, 11, 0, 0, 0]),
+        ("Kh9h7h4h2h", Category::Flush, [13, 9, 7, 4, 2]),
+        ("As2d3c4h5s", Category::Straight, [5, 0, 0, 0, 0]),
+        ("7c7d7hAs2d", Category::ThreeOfAKind, [7, 14, 2, 0, 0]),
+        ("8c8dQhQsJd", Category::TwoPair, [12, 8, 11, 0, 0]),
+        ("TcTd4h3s2d", Category::OnePair, [10, 4, 3, 2, 0]),
+        ("Ac9d7h4s2d", Category::HighCard, [14, 9, 7, 4, 2]),
+    ];
+    for (hand, category, kickers) in cases {
+        assert_eq!(
+            rank(hand).strength(),
+            HandStrength { category, kickers },
+            "{}",
+            hand
+        );
+    }
+}
+
+#[test]
+fn third_pair_plays_as_kicker() {
+    let seven: [Card; 7] = cards("AcAdKcKdQcQd2h").try_into().unwrap();
+    let expected = HandStrength {
+        category: Category::TwoPair,
+        kickers: [14, 13, 12, 0, 0],
+    };
+    assert_eq!(evaluate(&seven).strength(), expected);
+    assert_eq!(evaluate_hand(&seven), expected);
+}
+
+#[test]
+fn ranks_compare_across_categories() {
+    assert!(rank("2c3d4h5s7c") < rank("2c2d4h5s7c"));
+    assert!(rank("AsKsQsJs9s") > rank("AsKdQcJhTs"));
+    assert!(rank("As2d3c4h5s") < rank("2d3c4h5s6c"));
+    assert_eq!(rank("AsKsQsJs9s8d7c"), rank("AsKsQsJs9s"));
+}
+
+#[test]
+#[should_panic]
+fn rejects_four_cards() {
+    evaluate(&cards("AsKsQsJs"));
+}
@@ -12,11 +12,13 @@ fn hand_record_serializes_and_deserializes() {
                 player_id: 0,
                 street: Street::Preflop,
                 action: PlayerAction::Bet(50),
+                amount: None,
             },
             ActionRecord {
                 player_id: 1,
                 street: Street::Preflop,
                 action: PlayerAction::Call,
+                amount: None,
             },
         ],
         board: vec![
@@ -37,6 +39,7 @@ fn hand_record_serializes_and_deserializes() {
         ts: None,
         meta: None,
         showdown: None,
+        ..Default::default()
     };
 
     let s = serde_json::to_string(&rec).expect("serialize");
@@ -49,3 +52,99 @@ fn id_format_matches_spec() {
     let id = format_hand_id("20251231", 42);
     assert_eq!(id, "20251231-000042");
 }
+
+#[test]
+fn version_1_records_read_without_loss() {
+    let line = r#"{"hand_id":"20250101-000001","seed":7,"level":2,"blinds":[50,100],"button":"p0","players":["p0","p1"],"actions":[{"player_id":0,"street":"Preflop","action":"Call"}],"board":[{"suit":"Hearts","rank":"Ace"}],"result":"p1","net_result":{"p0":-100,"p1":100},"end_reason":"player_bust","timestamp":"2025-01-01T00:00:00Z"}"#;
+    let rec: HandRecord = serde_json::from_str(line).unwrap();
+    assert_eq!(rec.schema_version, 1);
+    assert_eq!((rec.level, rec.sb, rec.bb), (Some(2), Some(50), Some(100)));
+    assert_eq!(rec.players.len(), 2);
+    assert_eq!(rec.players[1].id, "p1");
+    assert_eq!(rec.players[1].stack_start, None);
+    assert_eq!(rec.actions[0].amount, None);
+    assert_eq!(rec.net_result.as_ref().unwrap()["p1"], 100);
+    assert_eq!(rec.end_reason.as_deref(), Some("player_bust"));
+    assert_eq!(rec.extra["timestamp"], "2025-01-01T00:00:00Z");
+    assert!(!rec.extra.contains_key("blinds"));
+
+    // writing it back keeps every field
+    let again: HandRecord = serde_json::from_str(&serde_json::to_string(&rec).unwrap()).unwrap();
+    assert_eq!(again, rec);
+
+    let named = line.replace("[50,100]", r#"{"sb":25,"bb":50}"#);
+    let rec: HandRecord = serde_json::from_str(&named).unwrap();
+    assert_eq!((rec.sb, rec.bb), (Some(25), Some(50)));
+    // unreadable blinds stay as they were
+    let odd = line.replace("[50,100]", r#""50/100""#);
+    let rec: HandRecord = serde_json::from_str(&odd).unwrap();
+    assert_eq!(rec.sb, None);
+    assert_eq!(rec.extra["blinds"], "50/100");
+}
+
+#[test]
+fn version_2_records_keep_their_version_and_object_cards_read() {
+    let line = r#"{"schema_version":2,"hand_id":"20250101-000002","seed":7,"players":[],"actions":[],"board":[{"suit":"Spades","rank":"Two"},{"suit":"Hearts","rank":"Ace"}],"result":null}"#;
+    let rec: HandRecord = serde_json::from_str(line).unwrap();
+    // readers tell the card format apart by the version
+    assert_eq!(rec.schema_version, 2);
+    assert_eq!(
+        rec.board.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+        ["2s", "Ah"]
+    );
+}
+
+#[test]
+fn engine_hands_become_version_3_records() {
+    use axm_engine::engine::Engine;
+
+    let mut eng = Engine::new(Some(3), 1);
+    assert!(eng.hand_record("x".into()).is_none());
+    eng.start_hand().unwrap();
+    eng.apply_action(0, PlayerAction::Raise(200)).unwrap();
+    eng.apply_action(1, PlayerAction::Fold).unwrap();
+    let rec = eng.hand_record("20250101-000001".into()).unwrap();
+    assert_eq!(rec.schema_version, axm_engine::logger::SCHEMA_VERSION);
+    assert_eq!((rec.sb, rec.bb), (Some(50), Some(100)));
+    assert_eq!(rec.button.as_deref(), Some("p0"));
+    assert_eq!(rec.players[0].stack_start, Some(20_000));
+    assert_eq!(rec.players[1].hole_cards.len(), 2);
+    // a raise of 200 on top of the 50 to call
+    assert_eq!(rec.actions[0].amount, Some(250));
+    assert_eq!(rec.actions[1].amount, Some(0));
+    assert_eq!(rec.result.as_deref(), Some("p0"));
+    assert_eq!(rec.net_result.as_ref().unwrap()["p0"], 100);
+    assert_eq!(rec.end_reason.as_deref(), Some("fold"));
+
+    let json: serde_json::Value = serde_json::to_value(&rec).unwrap();
+    assert_eq!(json["schema_version"], 3);
+    assert!(json["players"][0]["hole_cards"][0].is_string());
+    let back: HandRecord = serde_json::from_value(json).unwrap();
+    assert_eq!(back, rec);
+}
+
+#[test]
+fn subscribed_events_rebuild_the_engine_records() {
+    use axm_engine::agent::{Agent, RandomLegal};
+    use axm_engine::engine::Engine;
+    use axm_engine::logger::RecordBuilder;
+
+    let mut eng = Engine::new(Some(11), 1);
+    let rx = eng.subscribe();
+    let mut bots = [RandomLegal::new(1), RandomLegal::new(2)];
+    let mut builder = RecordBuilder::default();
+    for _ in 0..50 {
+        if eng.start_hand().is_err() {
+            break;
+        }
+        while let Some(seat) = eng.to_act() {
+            let action = bots[seat].act(&eng.observation(seat).unwrap());
+            eng.apply_action(seat, action).unwrap();
+        }
+        let mut built: Vec<HandRecord> = rx.try_iter().filter_map(|e| builder.push(&e)).collect();
+        assert_eq!(built.len(), 1);
+        let mut expected = eng.hand_record(String::new()).unwrap();
+        expected.deck_commitment = None;
+        assert_eq!(built.pop().unwrap(), expected);
+    }
+}
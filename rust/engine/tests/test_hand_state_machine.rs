@@ -0,0 +1,118 @@
+use axm_engine::engine::{EndReason, Engine};
+use axm_engine::errors::GameError;
+use axm_engine::logger::Street;
+use axm_engine::player::PlayerAction as A;
+
+#[test]
+fn start_hand_posts_blinds_and_button_acts_first() {
+    let mut eng = Engine::new(Some(3), 1);
+    eng.start_hand().expect("start");
+    assert_eq!(eng.street(), Some(Street::Preflop));
+    assert_eq!(eng.button(), 0);
+    assert_eq!(eng.to_act(), Some(0));
+    assert_eq!(eng.to_call(0), 50);
+    assert_eq!(eng.pot(), 150);
+    assert_eq!(eng.players()[0].stack(), 19_950);
+    assert_eq!(eng.players()[1].stack(), 19_900);
+    assert!(eng.board().is_empty());
+}
+
+#[test]
+fn check_down_reaches_showdown_with_full_board() {
+    let mut eng = Engine::new(Some(5), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Call).unwrap();
+    eng.apply_action(1, A::Check).unwrap();
+    assert_eq!(eng.street(), Some(Street::Flop));
+    assert_eq!(eng.board().len(), 3);
+    // big blind acts first after the flop
+    for street_len in [4, 5] {
+        eng.apply_action(1, A::Check).unwrap();
+        eng.apply_action(0, A::Check).unwrap();
+        assert_eq!(eng.board().len(), street_len);
+    }
+    eng.apply_action(1, A::Check).unwrap();
+    eng.apply_action(0, A::Check).unwrap();
+    assert!(eng.is_hand_over());
+    assert_eq!(eng.end_reason(), Some(EndReason::Showdown));
+    assert_eq!(eng.actions().len(), 8);
+    assert_eq!(eng.pot(), 200);
+}
+
+#[test]
+fn fold_ends_hand_and_awards_pot() {
+    let mut eng = Engine::new(Some(5), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Raise(200)).unwrap();
+    eng.apply_action(1, A::Fold).unwrap();
+    assert_eq!(eng.end_reason(), Some(EndReason::Fold));
+    assert_eq!(eng.winners(), &[0]);
+    assert_eq!(eng.players()[0].stack(), 20_100);
+    assert_eq!(eng.players()[1].stack(), 19_900);
+    assert_eq!(eng.to_act(), None);
+}
+
+#[test]
+fn raise_reopens_action_for_opponent() {
+    let mut eng = Engine::new(Some(5), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Call).unwrap();
+    eng.apply_action(1, A::Raise(100)).unwrap();
+    assert_eq!(eng.street(), Some(Street::Preflop));
+    assert_eq!(eng.to_act(), Some(0));
+    assert_eq!(eng.to_call(0), 100);
+}
+
+#[test]
+fn out_of_turn_and_illegal_actions_are_rejected() {
+    let mut eng = Engine::new(Some(5), 1);
+    assert_eq!(
+        eng.apply_action(0, A::Check),
+        Err(GameError::HandNotInProgress)
+    );
+    eng.start_hand().unwrap();
+    assert_eq!(
+        eng.apply_action(1, A::Check),
+        Err(GameError::OutOfTurn { player: 1 })
+    );
+    assert!(matches!(
+        eng.apply_action(0, A::Bet(200)),
+        Err(GameError::IllegalAction { .. })
+    ));
+    assert!(matches!(
+        eng.apply_action(0, A::Raise(50)),
+        Err(GameError::InvalidBetAmount { .. })
+    ));
+    assert_eq!(
+        eng.apply_action(0, A::Raise(110)),
+        Err(GameError::InvalidChipUnit {
+            amount: 110,
+            unit: 25
+        })
+    );
+}
+
+#[test]
+fn all_in_and_call_runs_out_board() {
+    let mut eng = Engine::new(Some(11), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::AllIn).unwrap();
+    eng.apply_action(1, A::Call).unwrap();
+    assert_eq!(eng.board().len(), 5);
+    assert_eq!(eng.end_reason(), Some(EndReason::Showdown));
+    assert_eq!(eng.pot(), 40_000);
+}
+
+#[test]
+fn uncalled_excess_is_returned() {
+    let mut eng = Engine::new(Some(11), 1);
+    {
+        let players = eng.players_mut();
+        players[1].bet(19_000).unwrap();
+    }
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::AllIn).unwrap();
+    eng.apply_action(1, A::Call).unwrap();
+    assert_eq!(eng.pot(), 2_000);
+    assert_eq!(eng.players()[0].stack(), 19_000);
+}
@@ -0,0 +1,121 @@
+use axm_engine::engine::Engine;
+use axm_engine::errors::GameError;
+use axm_engine::player::PlayerAction as A;
+use axm_engine::rules::{legal_actions, AmountRange, LegalActions, ValidatedAction};
+
+#[test]
+fn small_blind_preflop_can_fold_call_or_raise() {
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    let legal = eng.legal_actions().expect("button to act");
+    assert_eq!(
+        legal,
+        LegalActions {
+            fold: true,
+            check: false,
+            call: Some(50),
+            bet: None,
+            raise: Some(AmountRange {
+                min: 100,
+                max: 19_900
+            }),
+            all_in: Some(19_950),
+        }
+    );
+}
+
+#[test]
+fn big_blind_option_allows_check_or_raise() {
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Call).unwrap();
+    let legal = eng.legal_actions().unwrap();
+    assert!(legal.check);
+    assert!(legal.fold);
+    assert_eq!(legal.call, None);
+    assert_eq!(legal.bet, None);
+    assert!(legal.raise.is_some());
+}
+
+#[test]
+fn folding_with_a_free_check_is_still_legal() {
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Call).unwrap();
+    eng.apply_action(1, A::Fold).unwrap();
+    assert_eq!(eng.to_act(), None);
+}
+
+#[test]
+fn unopened_street_offers_bet_from_big_blind() {
+    let legal = legal_actions(5_000, 0, 0, 200, 200, true);
+    assert!(legal.check);
+    assert_eq!(
+        legal.bet,
+        Some(AmountRange {
+            min: 200,
+            max: 5_000
+        })
+    );
+    assert_eq!(legal.raise, None);
+}
+
+#[test]
+fn min_raise_tracks_last_full_raise() {
+    // facing a 300 raise on top of a 100 bet: next raise must add at least 300
+    let legal = legal_actions(5_000, 300, 400, 300, 100, true);
+    assert_eq!(legal.raise.map(|r| r.min), Some(300));
+}
+
+#[test]
+fn short_stack_can_only_call_all_in() {
+    let legal = legal_actions(60, 100, 100, 100, 100, true);
+    assert_eq!(legal.call, Some(60));
+    assert_eq!(legal.all_in, Some(60));
+    assert_eq!(legal.raise, None);
+}
+
+#[test]
+fn closed_raising_leaves_call_and_fold() {
+    let legal = legal_actions(1_000, 50, 250, 200, 100, false);
+    assert!(legal.fold);
+    assert_eq!(legal.call, Some(50));
+    assert_eq!(legal.raise, None);
+    assert_eq!(legal.all_in, None);
+    assert!(legal.permits(&ValidatedAction::AllIn(1_000)).is_err());
+}
+
+#[test]
+fn permits_rejects_off_unit_and_undersized_amounts() {
+    let legal = legal_actions(5_000, 0, 0, 100, 100, true);
+    assert_eq!(
+        legal.permits(&ValidatedAction::Bet(75)),
+        Err(GameError::InvalidBetAmount {
+            amount: 75,
+            minimum: 100
+        })
+    );
+    assert_eq!(
+        legal.permits(&ValidatedAction::Bet(130)),
+        Err(GameError::InvalidChipUnit {
+            amount: 130,
+            unit: 25
+        })
+    );
+    assert!(legal.permits(&ValidatedAction::Bet(125)).is_ok());
+}
+
+#[test]
+fn opponent_all_in_closes_raising() {
+    let mut eng = Engine::new(Some(2), 1);
+    {
+        let players = eng.players_mut();
+        players[0].bet(19_000).unwrap();
+    }
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::AllIn).unwrap();
+    let legal = eng.legal_actions().unwrap();
+    assert_eq!(legal.call, Some(900));
+    assert_eq!(legal.raise, None);
+    assert_eq!(legal.all_in, None);
+}
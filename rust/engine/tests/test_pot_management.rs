@@ -1,4 +1,4 @@
-use axm_engine::pot::PotManager;
+use axm_engine::pot::{Pot, PotManager};
 
 #[test]
 fn heads_up_simple_side_pot() {
@@ -13,3 +13,67 @@ fn equal_stacks_no_side_pot() {
     assert_eq!(pm.main_pot(), 2000);
     assert!(pm.side_pots().is_empty());
 }
+
+#[test]
+fn multiway_all_ins_layer_side_pots() {
+    // seat 0 all-in 100, seat 1 all-in 300, seats 2 and 3 put in 500
+    let pm = PotManager::build(&[100, 300, 500, 500], &[false; 4]);
+    assert_eq!(
+        pm.pots(),
+        &[
+            Pot {
+                amount: 400,
+                eligible: vec![0, 1, 2, 3]
+            },
+            Pot {
+                amount: 600,
+                eligible: vec![1, 2, 3]
+            },
+            Pot {
+                amount: 400,
+                eligible: vec![2, 3]
+            },
+        ]
+    );
+    assert_eq!(pm.main_pot(), 400);
+    assert_eq!(pm.side_pots(), &[600, 400]);
+    assert_eq!(pm.total(), 1_400);
+}
+
+#[test]
+fn folded_chips_fund_pots_without_eligibility() {
+    // seat 2 folded after putting in 200
+    let pm = PotManager::build(&[100, 400, 200], &[false, false, true]);
+    assert_eq!(
+        pm.pots(),
+        &[
+            Pot {
+                amount: 300,
+                eligible: vec![0, 1]
+            },
+            Pot {
+                amount: 400,
+                eligible: vec![1]
+            },
+        ]
+    );
+}
+
+#[test]
+fn folded_overbet_joins_last_pot() {
+    let pm = PotManager::build(&[300, 300, 600], &[false, false, true]);
+    assert_eq!(
+        pm.pots(),
+        &[Pot {
+            amount: 1_200,
+            eligible: vec![0, 1]
+        }]
+    );
+}
+
+#[test]
+fn equal_levels_merge_into_one_pot() {
+    let pm = PotManager::build(&[250, 250, 250], &[false, true, false]);
+    assert_eq!(pm.pots().len(), 1);
+    assert_eq!(pm.main_pot(), 750);
+}
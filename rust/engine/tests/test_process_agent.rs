@@ -0,0 +1,157 @@
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use axm_engine::agent::{Agent, AlwaysCall};
+use axm_engine::engine::Engine;
+use axm_engine::player::PlayerAction as A;
+use axm_engine::process_agent::{Fault, ProcessAgent};
+
+// Reads one request into $line and its number into $seq.
+const READ: &str = r#"read line; seq=${line#*\"seq\":}; seq=${seq%%,*}"#;
+
+// A bot running a temporary shell script, deleted along with it.
+struct Bot {
+    agent: ProcessAgent,
+    script: PathBuf,
+}
+
+impl Deref for Bot {
+    type Target = ProcessAgent;
+
+    fn deref(&self) -> &ProcessAgent {
+        &self.agent
+    }
+}
+
+impl DerefMut for Bot {
+    fn deref_mut(&mut self) -> &mut ProcessAgent {
+        &mut self.agent
+    }
+}
+
+impl Drop for Bot {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.script);
+    }
+}
+
+fn bot(name: &str, body: &str, timeout: Duration) -> Bot {
+    let script = std::env::temp_dir().join(format!("axm_bot_{}_{}.sh", name, std::process::id()));
+    std::fs::write(&script, body).unwrap();
+    let agent =
+        ProcessAgent::spawn(&format!("sh {}", script.display()), timeout).expect("spawn sh");
+    Bot { agent, script }
+}
+
+fn reply(action: &str) -> String {
+    format!(r#"echo "{{\"seq\":$seq,\"action\":{}}}""#, action)
+}
+
+#[test]
+fn bot_reply_is_used_and_sees_game_state() {
+    // replies Call and echoes the request to a file for inspection
+    let log = std::env::temp_dir().join(format!("axm_bot_log_{}.jsonl", std::process::id()));
+    let body = format!(
+        "while {}; do echo \"$line\" >> {}; {}; done\n",
+        READ,
+        log.display(),
+        reply(r#"\"Call\""#)
+    );
+    let mut agent = bot("caller", &body, Duration::from_secs(5));
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    let obs = eng.observation(0).unwrap();
+    assert_eq!(agent.act(&obs), A::Call);
+    assert_eq!(agent.fault_count(), 0);
+    drop(agent);
+    let sent = std::fs::read_to_string(&log).unwrap();
+    let _ = std::fs::remove_file(&log);
+    let req: serde_json::Value = serde_json::from_str(sent.lines().next().unwrap()).unwrap();
+    assert_eq!(req["type"], "act");
+    assert_eq!(req["seq"], 1);
+    assert_eq!(req["to_call"], 50);
+    assert_eq!(req["legal"]["call"], 50);
+    assert_eq!(req["hole_cards"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn illegal_and_garbled_replies_fall_back() {
+    let body = format!(
+        "{}; {}\n{}; echo 'nonsense'\n",
+        READ,
+        reply(r#"\"Check\""#),
+        READ
+    );
+    let mut agent = bot("illegal", &body, Duration::from_secs(5));
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    let obs = eng.observation(0).unwrap();
+    // facing the big blind: check is illegal, so the seat folds
+    assert_eq!(agent.act(&obs), A::Fold);
+    assert_eq!(agent.act(&obs), A::Fold);
+    assert_eq!(
+        agent.faults(),
+        &[Fault::Illegal(A::Check), Fault::BadReply("nonsense".into())]
+    );
+    // the script has exited by now
+    assert_eq!(agent.act(&obs), A::Fold);
+    assert_eq!(agent.faults().last(), Some(&Fault::Exited));
+}
+
+#[test]
+fn slow_bot_times_out() {
+    let body = format!(
+        "while {}; do sleep 2; {}; done\n",
+        READ,
+        reply(r#"\"Call\""#)
+    );
+    let mut agent = bot("slow", &body, Duration::from_millis(100));
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    eng.apply_action(0, A::Call).unwrap();
+    let obs = eng.observation(1).unwrap();
+    assert_eq!(agent.act(&obs), A::Check);
+    assert_eq!(agent.faults(), &[Fault::Timeout]);
+    // a local agent for comparison answers instantly
+    assert_eq!(AlwaysCall.act(&obs), A::Check);
+}
+
+#[test]
+fn missing_program_fails_to_spawn() {
+    assert!(ProcessAgent::spawn("/nonexistent/bot", Duration::from_secs(1)).is_err());
+    assert!(ProcessAgent::spawn("  ", Duration::from_secs(1)).is_err());
+}
+
+#[test]
+fn late_reply_to_an_earlier_request_is_dropped() {
+    // answers the first request after it timed out, then the second at once
+    let body = format!(
+        "{}; sleep 1.5; {}\n{}; {}\n",
+        READ,
+        reply(r#"\"Fold\""#),
+        READ,
+        reply(r#"\"Call\""#)
+    );
+    let mut agent = bot("late", &body, Duration::from_secs(1));
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    let obs = eng.observation(0).unwrap();
+    assert_eq!(agent.act(&obs), A::Fold);
+    assert_eq!(agent.faults(), &[Fault::Timeout]);
+    assert_eq!(agent.act(&obs), A::Call);
+    assert_eq!(agent.fault_count(), 1);
+}
+
+#[test]
+fn oversized_bet_is_accepted_as_all_in() {
+    let body = format!("{}; {}\n", READ, reply(r#"{\"Raise\":1000000}"#));
+    let mut agent = bot("shove", &body, Duration::from_secs(5));
+    let mut eng = Engine::new(Some(1), 1);
+    eng.start_hand().unwrap();
+    let obs = eng.observation(0).unwrap();
+    let action = agent.act(&obs);
+    assert_eq!(action, A::Raise(1_000_000));
+    assert_eq!(agent.fault_count(), 0);
+    assert!(eng.apply_action(0, action).is_ok());
+}
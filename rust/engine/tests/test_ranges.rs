@@ -0,0 +1,143 @@
+use axm_engine::cards::{parse_cards, Card};
+use axm_engine::equity::{calculate, EquityOptions};
+use axm_engine::range::{range_equity, Range, RangeError, ALL_COMBOS};
+
+fn range(s: &str) -> Range {
+    s.parse().unwrap()
+}
+
+fn cards(s: &str) -> Vec<Card> {
+    parse_cards(s).unwrap()
+}
+
+#[test]
+fn parses_classes_and_open_ends() {
+    assert_eq!(range("AA").len(), 6);
+    assert_eq!(range("AKs").len(), 4);
+    assert_eq!(range("AKo").len(), 12);
+    assert_eq!(range("AK").len(), 16);
+    assert_eq!(range("22+").len(), 13 * 6);
+    assert_eq!(range("TT+").len(), 5 * 6);
+    assert_eq!(range("A2s+").len(), 12 * 4);
+    assert_eq!(range("KTo+").len(), 3 * 12);
+    assert_eq!(range("22-55").len(), 4 * 6);
+    assert_eq!(range("A5s-A2s").len(), 4 * 4);
+    assert_eq!(range("AhKh").len(), 1);
+    // overlapping items count each combo once
+    assert_eq!(range("QQ+, KK, AKs, AhKh").len(), 18 + 4);
+}
+
+#[test]
+fn weights_and_overrides() {
+    let r = range("AKs:0.5, AKo");
+    assert_eq!(r.len(), 16);
+    assert_eq!(r.weighted_len(), 2.0 + 12.0);
+    // a later item replaces the weight, zero removes
+    let r = range("AK, AKo:0");
+    assert_eq!(r.len(), 4);
+}
+
+#[test]
+fn top_percent_picks_best_hands_first() {
+    let all = range("100%");
+    assert_eq!(all.len(), ALL_COMBOS);
+    let top = range("5%");
+    assert!(top.len() >= 66 && top.len() < 90, "{}", top.len());
+    let aces = cards("AhAs");
+    assert!(top
+        .combos()
+        .iter()
+        .any(|(c, _)| c.contains(&aces[0]) && c.contains(&aces[1])));
+    let trash = cards("7h2c");
+    assert!(!top
+        .combos()
+        .iter()
+        .any(|(c, _)| c.contains(&trash[0]) && c.contains(&trash[1])));
+}
+
+#[test]
+fn rejects_bad_items() {
+    assert!(matches!(
+        "AKx".parse::<Range>(),
+        Err(RangeError::InvalidItem(_))
+    ));
+    assert!(matches!(
+        "AAs".parse::<Range>(),
+        Err(RangeError::InvalidItem(_))
+    ));
+    assert!(matches!(
+        "AK2s-AQs".parse::<Range>(),
+        Err(RangeError::InvalidItem(_))
+    ));
+    assert!(matches!(
+        "AKs-KQs".parse::<Range>(),
+        Err(RangeError::InvalidItem(_))
+    ));
+    assert!(matches!(
+        "AKs:2".parse::<Range>(),
+        Err(RangeError::InvalidWeight(_))
+    ));
+}
+
+#[test]
+fn card_removal_against_the_board() {
+    let r = range("AA").without(&cards("Ah7c2d"));
+    assert_eq!(r.len(), 3);
+    let err = range_equity(
+        &[range("KhKd"), range("AA")],
+        &cards("AhAdAc"),
+        &cards("As"),
+        &EquityOptions::default(),
+    );
+    assert_eq!(err, Err(RangeError::Empty(1)));
+}
+
+#[test]
+fn single_combo_ranges_match_hand_equity() {
+    let board = cards("7h8h2cKd");
+    let hands = [
+        [cards("AhKh")[0], cards("AhKh")[1]],
+        [cards("QsQd")[0], cards("QsQd")[1]],
+    ];
+    let opts = EquityOptions::default();
+    let by_hand = calculate(&hands, &board, &[], &opts).unwrap();
+    let by_range = range_equity(&[range("AhKh"), range("QsQd")], &board, &[], &opts).unwrap();
+    assert!(by_range.exact);
+    assert_eq!(by_range.samples, 1);
+    let expected = by_hand.hands[0].equity_pct(by_hand.runouts);
+    assert!((by_range.ranges[0].equity - expected).abs() < 1e-9);
+}
+
+#[test]
+fn range_equity_exact_and_sampled_agree() {
+    let board = cards("7h8h2cKd");
+    let ranges = [range("QQ+"), range("AKs, 99:0.5")];
+    let exact = range_equity(&ranges, &board, &[], &EquityOptions::default()).unwrap();
+    assert!(exact.exact);
+    let total = exact.ranges[0].equity + exact.ranges[1].equity;
+    assert!((total - 100.0).abs() < 1e-9);
+    let opts = EquityOptions {
+        iterations: 4000,
+        seed: 5,
+        exact_limit: 0,
+    };
+    let sampled = range_equity(&ranges, &board, &[], &opts).unwrap();
+    assert!(!sampled.exact);
+    assert_eq!(sampled, range_equity(&ranges, &board, &[], &opts).unwrap());
+    assert!((sampled.ranges[0].equity - exact.ranges[0].equity).abs() < 3.0);
+}
+
+#[test]
+fn ranges_that_always_share_a_card_have_no_matchups() {
+    let ranges = [range("AhAs,AsAd"), range("AhAd,AdAs")];
+    let board = cards("Kc2c3c");
+    let exact = range_equity(&ranges, &board, &[], &EquityOptions::default());
+    assert_eq!(exact, Err(RangeError::NoMatchups));
+    let opts = EquityOptions {
+        iterations: 10,
+        seed: 1,
+        exact_limit: 0,
+    };
+    let sampled = range_equity(&[range("AhAs:1"), range("AhAd:1")], &[], &[], &opts);
+    assert_eq!(sampled, Err(RangeError::NoMatchups));
+}
@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+
+use axm_engine::blinds::BlindSchedule;
+use axm_engine::deck::{commitment, derive_hand_seed, Deck};
+use axm_engine::engine::Engine;
+use axm_engine::session::Session;
+
+#[test]
+fn hand_seeds_are_stable_and_do_not_overlap() {
+    assert_eq!(derive_hand_seed(7, 3), derive_hand_seed(7, 3));
+    let mut seen = HashSet::new();
+    // adjacent sessions used to share streams under `seed + i`
+    for session in 0..20u64 {
+        for hand in 0..50u64 {
+            assert!(seen.insert(derive_hand_seed(session, hand)));
+        }
+    }
+}
+
+#[test]
+fn commitment_covers_the_shuffled_order() {
+    let mut deck = Deck::new_with_seed(11);
+    deck.shuffle();
+    let c = deck.commitment();
+    assert_eq!(c.len(), 64);
+    assert_eq!(c, commitment(deck.order()));
+    let mut other = Deck::new_with_seed(12);
+    other.shuffle();
+    assert_ne!(c, other.commitment());
+    // dealing does not change the committed order
+    deck.deal_card();
+    assert_eq!(c, deck.commitment());
+}
+
+#[test]
+fn engine_commits_to_the_deck_it_deals_from() {
+    let mut eng = Engine::new(Some(21), 1);
+    eng.start_hand().unwrap();
+    let mut deck = Deck::new_with_seed(21);
+    deck.shuffle();
+    assert_eq!(eng.deck_commitment(), deck.commitment());
+    // button is dealt first
+    let button = eng.button();
+    assert_eq!(eng.players()[button].hole_cards()[0], Some(deck.order()[0]));
+}
+
+#[test]
+fn session_hands_use_derived_seeds() {
+    let mut session = Session::new(Some(99), 1, BlindSchedule::default());
+    for i in 0..3u64 {
+        let seed = session.next_hand_seed();
+        assert_eq!(seed, derive_hand_seed(99, i));
+        session
+            .play_hand(|_, _, legal| {
+                if legal.check {
+                    axm_engine::player::PlayerAction::Check
+                } else {
+                    axm_engine::player::PlayerAction::Call
+                }
+            })
+            .unwrap();
+        let mut deck = Deck::new_with_seed(seed);
+        deck.shuffle();
+        assert_eq!(session.engine().deck_commitment(), deck.commitment());
+    }
+}
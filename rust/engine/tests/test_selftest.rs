@@ -0,0 +1,36 @@
+use axm_engine::cards::full_deck;
+use axm_engine::selftest::{check, check_five, check_seven, FIVE_CARD_COUNTS, SEVEN_CARD_COUNTS};
+
+#[test]
+fn known_counts_add_up() {
+    assert_eq!(FIVE_CARD_COUNTS.iter().sum::<u64>(), 2_598_960);
+    assert_eq!(SEVEN_CARD_COUNTS.iter().sum::<u64>(), 133_784_560);
+}
+
+#[test]
+fn all_five_card_hands_match_known_counts() {
+    let report = check_five(4);
+    assert_eq!(report.hands, 2_598_960);
+    assert_eq!(report.counts, FIVE_CARD_COUNTS);
+    assert!(report.passed());
+}
+
+#[test]
+fn seven_card_evaluators_agree_on_a_reduced_deck() {
+    // every other card, 20 in all: C(20, 7) = 77,520
+    let deck: Vec<_> = full_deck().into_iter().step_by(2).take(20).collect();
+    let report = check(&deck, 7, 2);
+    assert_eq!(report.hands, 77_520);
+    assert_eq!(report.mismatches, 0, "{:?}", report.first_mismatch);
+    assert_eq!(report.expected, None);
+    assert!(report.passed());
+}
+
+#[test]
+#[ignore] // 133M hands; run with --release -- --ignored
+fn all_seven_card_hands_match_known_counts() {
+    let threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+    let report = check_seven(threads);
+    assert_eq!(report.counts, SEVEN_CARD_COUNTS);
+    assert!(report.passed(), "{:?}", report.first_mismatch);
+}
@@ -0,0 +1,86 @@
+use axm_engine::blinds::{BlindLevel, BlindSchedule, LevelClock};
+use axm_engine::player::PlayerAction as A;
+use axm_engine::rules::LegalActions;
+use axm_engine::session::Session;
+
+fn passive(legal: &LegalActions) -> A {
+    if legal.check {
+        A::Check
+    } else {
+        A::Call
+    }
+}
+
+#[test]
+fn stacks_carry_over_and_button_rotates() {
+    let mut s = Session::new(Some(3), 1, BlindSchedule::default());
+    s.play_hand(|_, _, l| passive(l)).unwrap();
+    let after_first: Vec<u32> = s.engine().players().iter().map(|p| p.stack()).collect();
+    assert_eq!(s.engine().button(), 1);
+    s.play_hand(|_, _, l| passive(l)).unwrap();
+    assert_eq!(s.engine().button(), 0);
+    assert_eq!(s.hands_played(), 2);
+    let total: u32 = s.engine().players().iter().map(|p| p.stack()).sum();
+    assert_eq!(total, 40_000);
+    // the second hand started from the first hand's final stacks
+    let net = s.engine().net_result().unwrap();
+    let after_second: Vec<i64> = s
+        .engine()
+        .players()
+        .iter()
+        .map(|p| p.stack() as i64)
+        .collect();
+    assert_eq!(after_second[0] - net[0], after_first[0] as i64);
+}
+
+#[test]
+fn blinds_escalate_with_schedule() {
+    let mut s = Session::new(Some(3), 1, BlindSchedule::default());
+    let summary = s.run(Some(16), |_, _, l| passive(l)).unwrap();
+    assert_eq!(summary.hands_played, 16);
+    assert_eq!(summary.level_reached, 2);
+    assert_eq!(s.engine().blinds(), (75, 150));
+}
+
+#[test]
+fn session_stops_at_elimination() {
+    let schedule = BlindSchedule::new(
+        vec![BlindLevel {
+            sb: 500,
+            bb: 1000,
+            ante: 0,
+        }],
+        LevelClock::Hands(100),
+        false,
+    )
+    .unwrap();
+    let mut s = Session::new(Some(9), 1, schedule);
+    // shove every hand: someone busts within a few hands
+    let summary = s
+        .run(None, |_, _, l| {
+            if l.all_in.is_some() {
+                A::AllIn
+            } else {
+                passive(l)
+            }
+        })
+        .unwrap();
+    assert!(s.is_over());
+    let winner = summary.winner.expect("winner");
+    assert_eq!(summary.final_stacks[winner], 40_000);
+    assert_eq!(summary.final_stacks[1 - winner], 0);
+    assert!(s.play_hand(|_, _, l| passive(l)).is_err());
+}
+
+#[test]
+fn rejected_actions_are_played_as_check_or_fold() {
+    let mut s = Session::new(Some(3), 0, BlindSchedule::default());
+    // a raise of 1 chip is never legal
+    s.play_hand(|_, _, _| A::Raise(1)).unwrap();
+    assert_eq!(s.hands_played(), 1);
+    assert_eq!(s.engine().button(), 1);
+    assert!(s.rejected().iter().sum::<usize>() > 0);
+    let total: u32 = s.engine().players().iter().map(|p| p.stack()).sum();
+    assert_eq!(total, 40_000);
+    assert_eq!(s.engine().level(), 1);
+}
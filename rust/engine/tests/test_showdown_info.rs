@@ -11,6 +11,7 @@ fn showdown_info_serializes() {
             player_id: 0,
             street: Street::River,
             action: A::Check,
+            amount: None,
         }],
         board: vec![Card {
             suit: S::Clubs,
@@ -23,6 +24,7 @@ fn showdown_info_serializes() {
             winners: vec![0],
             notes: Some("kicker A".into()),
         }),
+        ..Default::default()
     };
     let s = serde_json::to_string(&rec).unwrap();
     let back: HandRecord = serde_json::from_str(&s).unwrap();
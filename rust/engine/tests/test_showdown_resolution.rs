@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+use axm_engine::cards::Card;
+use axm_engine::engine::{EndReason, Engine};
+use axm_engine::hand::{compare_hands, evaluate_hand};
+use axm_engine::player::PlayerAction as A;
+use axm_engine::pot::split_pot;
+
+fn seven(engine: &Engine, seat: usize) -> [Card; 7] {
+    let [a, b] = engine.players()[seat].hole_cards();
+    let mut cards = [a.unwrap(); 7];
+    cards[1] = b.unwrap();
+    cards[2..].copy_from_slice(engine.board());
+    cards
+}
+
+fn check_down(engine: &mut Engine) {
+    engine.start_hand().unwrap();
+    while let Some(p) = engine.to_act() {
+        let legal = engine.legal_actions().unwrap();
+        let action = if legal.check { A::Check } else { A::Call };
+        engine.apply_action(p, action).unwrap();
+    }
+}
+
+#[test]
+fn showdown_awards_pot_to_best_hand() {
+    for seed in 0..200 {
+        let mut eng = Engine::new(Some(seed), 1);
+        check_down(&mut eng);
+        assert_eq!(eng.end_reason(), Some(EndReason::Showdown));
+        let order = compare_hands(
+            &evaluate_hand(&seven(&eng, 0)),
+            &evaluate_hand(&seven(&eng, 1)),
+        );
+        let (expected_winners, expected_net): (Vec<usize>, [i64; 2]) = match order {
+            Ordering::Greater => (vec![0], [100, -100]),
+            Ordering::Less => (vec![1], [-100, 100]),
+            Ordering::Equal => (vec![0, 1], [0, 0]),
+        };
+        assert_eq!(eng.winners(), &expected_winners[..], "seed {}", seed);
+        assert_eq!(eng.showdown().unwrap().winners, expected_winners);
+        assert_eq!(eng.net_result(), Some(expected_net), "seed {}", seed);
+        let total: u32 = eng.players().iter().map(|p| p.stack()).sum();
+        assert_eq!(total, 40_000);
+    }
+}
+
+#[test]
+fn fold_has_net_result_but_no_showdown() {
+    let mut eng = Engine::new(Some(4), 1);
+    eng.start_hand().unwrap();
+    assert_eq!(eng.net_result(), None);
+    eng.apply_action(0, A::Fold).unwrap();
+    assert_eq!(eng.showdown(), None);
+    assert_eq!(eng.net_result(), Some([-50, 50]));
+}
+
+#[test]
+fn all_in_showdown_can_bust_a_player() {
+    let mut busted = false;
+    for seed in 0..50 {
+        let mut eng = Engine::new(Some(seed), 1);
+        eng.start_hand().unwrap();
+        eng.apply_action(0, A::AllIn).unwrap();
+        eng.apply_action(1, A::Call).unwrap();
+        let stacks = [eng.players()[0].stack(), eng.players()[1].stack()];
+        assert_eq!(stacks[0] + stacks[1], 40_000);
+        busted |= stacks.contains(&0);
+    }
+    assert!(busted, "some all-in should end with a zero stack");
+}
+
+#[test]
+fn split_pot_gives_odd_unit_to_first_winner() {
+    assert_eq!(split_pot(200, 2), vec![100, 100]);
+    assert_eq!(split_pot(175, 2), vec![100, 75]);
+    assert_eq!(split_pot(110, 2), vec![60, 50]);
+    assert_eq!(split_pot(300, 1), vec![300]);
+    assert!(split_pot(300, 0).is_empty());
+}
@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+
+use axm_engine::cards::parse_cards;
+use axm_engine::logger::{ActionRecord, HandRecord, PlayerRecord, Street};
+use axm_engine::player::PlayerAction as A;
+use axm_engine::verify::{
+    check_betting, check_board, check_dealing, check_roster, verify_hand, DealingMeta, HandContext,
+    Rule,
+};
+
+fn ctx(stacks: &[(&str, i64)]) -> HandContext {
+    HandContext {
+        hand_id: "19700101-000001".to_string(),
+        hand_index: 1,
+        small_blind: 50,
+        big_blind: 100,
+        button: None,
+        starting_stacks: stacks.iter().map(|(id, s)| (id.to_string(), *s)).collect(),
+        antes: BTreeMap::new(),
+        dealing: None,
+    }
+}
+
+fn act(player_id: usize, street: Street, action: A) -> ActionRecord {
+    ActionRecord {
+        player_id,
+        street,
+        action,
+        amount: None,
+    }
+}
+
+#[test]
+fn legal_hand_has_no_violations() {
+    let mut c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    c.button = Some("p0".to_string());
+    let actions = [
+        act(0, Street::Preflop, A::Raise(100)),
+        act(1, Street::Preflop, A::Call),
+        act(1, Street::Flop, A::Bet(200)),
+        act(0, Street::Flop, A::Call),
+        act(1, Street::Turn, A::Check),
+        act(0, Street::Turn, A::Check),
+    ];
+    assert!(verify_hand(&c, &actions).is_empty());
+}
+
+#[test]
+fn short_raise_reports_action_index_and_rule() {
+    let c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    let actions = [
+        act(0, Street::Flop, A::Bet(100)),
+        act(1, Street::Flop, A::Raise(50)),
+    ];
+    let v = check_betting(&c, &actions);
+    assert_eq!(v.len(), 1);
+    assert_eq!(v[0].rule, Rule::MinimumRaise);
+    assert_eq!(v[0].action_index, Some(1));
+    assert_eq!(v[0].hand_id, "19700101-000001");
+    assert!(v[0].message.contains("below minimum 100"));
+}
+
+#[test]
+fn off_unit_bet_is_a_chip_unit_violation() {
+    let c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    let v = check_betting(&c, &[act(0, Street::Flop, A::Bet(130))]);
+    assert_eq!(v[0].rule, Rule::ChipUnit);
+}
+
+#[test]
+fn raise_after_short_all_in_is_flagged() {
+    let c = ctx(&[("p0", 1_000), ("p1", 250)]);
+    let actions = [
+        act(0, Street::Flop, A::Bet(200)),
+        act(1, Street::Flop, A::AllIn),
+        act(0, Street::Flop, A::Raise(200)),
+    ];
+    let v = check_betting(&c, &actions);
+    assert_eq!(v[0].rule, Rule::ReopenAfterShortAllIn);
+    assert_eq!(v[0].action_index, Some(2));
+}
+
+#[test]
+fn unknown_seat_is_reported() {
+    let c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    let v = check_betting(&c, &[act(9, Street::Preflop, A::Check)]);
+    assert_eq!(v[0].rule, Rule::UnknownPlayer);
+    assert!(v[0].message.contains("Unknown player 9"));
+}
+
+#[test]
+fn checking_facing_blind_is_illegal_when_button_known() {
+    let mut c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    c.button = Some("p0".to_string());
+    let v = check_betting(&c, &[act(0, Street::Preflop, A::Check)]);
+    assert_eq!(v[0].rule, Rule::IllegalAction);
+}
+
+#[test]
+fn dealing_meta_checks_order_and_burns() {
+    let mut c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    c.button = Some("p0".to_string());
+    c.dealing = Some(DealingMeta {
+        small_blind: Some("p0".to_string()),
+        big_blind: Some("p1".to_string()),
+        deal_sequence: Some(vec!["p1".into(), "p0".into(), "p1".into(), "p0".into()]),
+        burn_positions: Some(vec![5, 9, 11]),
+    });
+    let v = check_dealing(&c);
+    assert_eq!(v[0].rule, Rule::DealingOrder);
+
+    if let Some(meta) = c.dealing.as_mut() {
+        meta.deal_sequence = Some(vec!["p0".into(), "p1".into(), "p0".into(), "p1".into()]);
+        meta.burn_positions = Some(vec![4, 8, 10]);
+    }
+    let v = check_dealing(&c);
+    assert_eq!(v.len(), 1);
+    assert_eq!(v[0].rule, Rule::BurnPositions);
+}
+
+#[test]
+fn roster_changes_between_hands_are_reported() {
+    let mut c = ctx(&[("p0", 1_000), ("p2", 1_000)]);
+    c.hand_index = 2;
+    let prev: BTreeMap<String, i64> = [("p0".to_string(), 900), ("p1".to_string(), 1_100)]
+        .into_iter()
+        .collect();
+    let rules: Vec<Rule> = check_roster(&c, Some(&prev))
+        .into_iter()
+        .map(|v| v.rule)
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            Rule::StackMismatch,
+            Rule::UnexpectedPlayer,
+            Rule::MissingPlayer
+        ]
+    );
+}
+
+#[test]
+fn context_from_record_reads_blinds_stacks_and_meta() {
+    let rec = HandRecord {
+        hand_id: "19700101-000002".to_string(),
+        bb: Some(200),
+        button: Some("p1".to_string()),
+        players: vec![
+            PlayerRecord {
+                id: "p0".to_string(),
+                stack_start: Some(9_000),
+                ..Default::default()
+            },
+            PlayerRecord {
+                id: "p1".to_string(),
+                stack_start: None,
+                ..Default::default()
+            },
+        ],
+        meta: Some(serde_json::json!({"small_blind": "p1", "big_blind": "p0"})),
+        ..Default::default()
+    };
+    let c = HandContext::from_record(&rec, 2).unwrap();
+    assert_eq!(c.hand_index, 2);
+    assert_eq!((c.small_blind, c.big_blind), (0, 200));
+    assert_eq!(c.starting_stacks.len(), 1);
+    assert_eq!(c.dealing.unwrap().big_blind.as_deref(), Some("p0"));
+
+    let bad = HandRecord {
+        meta: Some(serde_json::json!({"burn_positions": "x"})),
+        ..rec
+    };
+    let v = HandContext::from_record(&bad, 2).unwrap_err();
+    assert_eq!(v.rule, Rule::DealingOrder);
+}
+
+#[test]
+fn folded_hands_may_stop_before_the_river() {
+    let c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    let mut rec = HandRecord {
+        actions: vec![
+            act(0, Street::Preflop, A::Call),
+            act(1, Street::Preflop, A::Check),
+            act(1, Street::Flop, A::Bet(100)),
+            act(0, Street::Flop, A::Fold),
+        ],
+        board: parse_cards("AhKd7s").unwrap(),
+        end_reason: Some("fold".to_string()),
+        ..Default::default()
+    };
+    assert!(check_board(&c, &rec).is_empty());
+
+    rec.board = parse_cards("AhKd7s2c9h").unwrap();
+    let v = check_board(&c, &rec);
+    assert_eq!(v[0].rule, Rule::BoardLength);
+    assert!(v[0].message.contains("expected 3 cards but found 5"));
+
+    rec.board.truncate(3);
+    rec.end_reason = Some("showdown".to_string());
+    assert!(check_board(&c, &rec)[0]
+        .message
+        .contains("expected 5 cards but found 3"));
+}
+
+#[test]
+fn antes_come_off_the_stacks_before_the_betting() {
+    use axm_engine::blinds::{BlindLevel, BlindSchedule, LevelClock};
+    use axm_engine::engine::Engine;
+
+    for big_blind_ante in [false, true] {
+        let level = BlindLevel {
+            sb: 100,
+            bb: 200,
+            ante: 25,
+        };
+        let schedule =
+            BlindSchedule::new(vec![level], LevelClock::Hands(10), big_blind_ante).unwrap();
+        let mut engine = Engine::with_schedule(Some(4), 1, schedule);
+        engine.start_hand().unwrap();
+        // shove and call: the all-in amounts only add up once antes are out
+        while let Some(seat) = engine.to_act() {
+            let legal = engine.legal_actions().unwrap();
+            let action = if legal.call.is_some() {
+                A::Call
+            } else {
+                A::AllIn
+            };
+            engine.apply_action(seat, action).unwrap();
+        }
+        let rec = engine.hand_record("19700101-000001".into()).unwrap();
+        assert_eq!(rec.ante, Some(25));
+        let antes: Vec<Option<u32>> = rec.players.iter().map(|p| p.ante).collect();
+        if big_blind_ante {
+            assert_eq!(antes.iter().flatten().sum::<u32>(), 50);
+            assert_eq!(antes.iter().filter(|a| a.is_some()).count(), 1);
+        } else {
+            assert_eq!(antes, [Some(25), Some(25)]);
+        }
+        let back: HandRecord = serde_json::from_str(&serde_json::to_string(&rec).unwrap()).unwrap();
+        let c = HandContext::from_record(&back, 1).unwrap();
+        assert_eq!(verify_hand(&c, &back.actions), vec![]);
+    }
+
+    // 200 less a 25 ante and the 50 small blind leaves 125, so raising 75
+    // more than the 50 to call is all-in rather than a short raise
+    let mut c = ctx(&[("p0", 200), ("p1", 200)]);
+    c.button = Some("p0".to_string());
+    c.antes = [("p0".to_string(), 25), ("p1".to_string(), 25)].into();
+    let raise = [act(0, Street::Preflop, A::Raise(75))];
+    assert_eq!(check_betting(&c, &raise), vec![]);
+    c.antes.clear();
+    let v = check_betting(&c, &raise);
+    assert_eq!(v.len(), 1, "{:?}", v);
+    assert_eq!(v[0].rule, Rule::MinimumRaise);
+}
+
+#[test]
+fn recorded_amounts_must_match_the_replay() {
+    let mut c = ctx(&[("p0", 1_000), ("p1", 1_000)]);
+    c.button = Some("p0".to_string());
+    let with = |action: A, amount: u32| ActionRecord {
+        amount: Some(amount),
+        ..act(0, Street::Preflop, action)
+    };
+    assert_eq!(check_betting(&c, &[with(A::Raise(100), 150)]), vec![]);
+    let v = check_betting(
+        &c,
+        &[
+            with(A::Call, 50),
+            ActionRecord {
+                amount: Some(20),
+                ..act(1, Street::Preflop, A::Check)
+            },
+        ],
+    );
+    assert_eq!(v.len(), 1, "{:?}", v);
+    assert_eq!(v[0].rule, Rule::ActionAmount);
+    assert_eq!(v[0].action_index, Some(1));
+}